@@ -0,0 +1,285 @@
+//! Axis-aligned boxes against hulls, for the hand-off from a broadphase.
+use super::{Float, Vector3, farthest, gjk};
+
+/// An axis-aligned box spanning from `min` to `max`
+///
+/// Each component of `min` must not exceed that of `max`. Boxes without
+/// extent along some axes are rectangles, segments or points.
+///
+/// ```
+/// use bgjk::{Aabb, Vec3};
+///
+/// let a = Aabb::from_points(&[Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 2.0, 1.0)]).unwrap();
+/// let b = Aabb { min: Vec3::new(1.0, 1.0, 0.0), max: Vec3::new(2.0, 2.0, 2.0) };
+/// assert![a.overlaps(&b)];
+/// let shared = a.intersection(&b).unwrap();
+/// assert_eq![(shared.min, shared.max), (Vec3::new(1.0, 1.0, 0.0), Vec3::new(1.0, 2.0, 1.0))];
+/// assert![a.intersection(&b.expand(-0.5)).is_none()];
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Aabb<T = f32> {
+	/// The corner with the smallest components
+	pub min: Vector3<T>,
+	/// The corner with the largest components
+	pub max: Vector3<T>,
+}
+
+impl<T: Float> Aabb<T> {
+	/// The smallest box around the points, `None` for no points
+	pub fn from_points(points: &[Vector3<T>]) -> Option<Aabb<T>> {
+		let first = *points.first()?;
+		Some(points[1..].iter().fold(Aabb { min: first, max: first }, |aabb, &point| {
+			Aabb {
+				min: aabb.min.min(point),
+				max: aabb.max.max(point),
+			}
+		}))
+	}
+
+	/// The smallest box around both boxes
+	pub fn union(&self, other: &Aabb<T>) -> Aabb<T> {
+		Aabb {
+			min: self.min.min(other.min),
+			max: self.max.max(other.max),
+		}
+	}
+
+	/// The box both boxes share, `None` where they are apart
+	///
+	/// Boxes that only touch share a box without extent along some axes.
+	pub fn intersection(&self, other: &Aabb<T>) -> Option<Aabb<T>> {
+		let shared = Aabb {
+			min: self.min.max(other.min),
+			max: self.max.min(other.max),
+		};
+		let (min, max) = (shared.min, shared.max);
+		if min.0 <= max.0 && min.1 <= max.1 && min.2 <= max.2 {
+			Some(shared)
+		} else {
+			None
+		}
+	}
+
+	/// Whether the boxes overlap, which they do where they only touch
+	pub fn overlaps(&self, other: &Aabb<T>) -> bool {
+		self.min.0 <= other.max.0 && other.min.0 <= self.max.0 && self.min.1 <= other.max.1 &&
+		other.min.1 <= self.max.1 && self.min.2 <= other.max.2 && other.min.2 <= self.max.2
+	}
+
+	/// Whether the point is inside the box or on its boundary
+	pub fn contains_point(&self, point: Vector3<T>) -> bool {
+		self.min.0 <= point.0 && point.0 <= self.max.0 && self.min.1 <= point.1 &&
+		point.1 <= self.max.1 && self.min.2 <= point.2 && point.2 <= self.max.2
+	}
+
+	/// The box grown by `margin` on every side
+	///
+	/// A negative margin shrinks it instead, but along each axis no further
+	/// than down to its center, so the box never turns inside out.
+	pub fn expand(&self, margin: T) -> Aabb<T> {
+		let center = (self.min + self.max) / T::from_f32(2.0);
+		let by = Vector3(margin, margin, margin);
+		Aabb {
+			min: (self.min - by).min(center),
+			max: (self.max + by).max(center),
+		}
+	}
+
+	/// The eight corners, the `x` of the `i`th from `max` where bit 0 of
+	/// `i` is set, the `y` where bit 1 is and the `z` where bit 2 is
+	pub fn corners(&self) -> [Vector3<T>; 8] {
+		let mut corners = [self.min; 8];
+		for (i, corner) in corners.iter_mut().enumerate() {
+			if i & 1 != 0 {
+				corner.0 = self.max.0;
+			}
+			if i & 2 != 0 {
+				corner.1 = self.max.1;
+			}
+			if i & 4 != 0 {
+				corner.2 = self.max.2;
+			}
+		}
+		corners
+	}
+
+	// The corner farthest along `direction`, from `min` where it ties
+	fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+		let pick = |toward: T, min: T, max: T| if toward > T::ZERO { max } else { min };
+		Vector3(pick(direction.0, self.min.0, self.max.0),
+		        pick(direction.1, self.min.1, self.max.1),
+		        pick(direction.2, self.min.2, self.max.2))
+	}
+}
+
+/// Whether the box intersects `hull`
+///
+/// Gives the same answer as `bgjk` on the eight corners of the box, but
+/// the corner farthest along each search direction is picked component by
+/// component, so no corners are built. Boxes and hulls that only touch
+/// intersect.
+///
+/// ```
+/// use bgjk::{aabb_hits_hull, Aabb, Vec3};
+///
+/// let triangle = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// let near = Aabb { min: Vec3::new(0.4, 0.4, -1.0), max: Vec3::new(1.0, 1.0, 1.0) };
+/// let far = Aabb { min: Vec3::new(0.6, 0.6, -1.0), max: Vec3::new(1.0, 1.0, 1.0) };
+/// assert![aabb_hits_hull(&near, &triangle)];
+/// assert![!aabb_hits_hull(&far, &triangle)];
+/// ```
+pub fn aabb_hits_hull<T: Float>(aabb: &Aabb<T>, hull: &[Vector3<T>]) -> bool {
+	debug_assert![aabb.min.is_finite() && aabb.max.is_finite() &&
+	              hull.iter().all(Vector3::is_finite),
+	              "bgjk called with a non-finite point in a hull"];
+	let (min, max) = (aabb.min, aabb.max);
+	debug_assert![min.0 <= max.0 && min.1 <= max.1 && min.2 <= max.2,
+	              "aabb_hits_hull called with min above max: {:?}, {:?}",
+	              min,
+	              max];
+	if hull.is_empty() {
+		return false;
+	}
+	gjk(|direction| aabb.support(direction) - farthest(hull, -direction).unwrap_or(Vector3::ZERO))
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+
+	use super::super::{Aabb, Vec3, aabb_hits_hull, bgjk};
+	use super::super::test_util::{cube_at, unit_cube};
+
+	fn hits(aabb: &Aabb, hull: &[Vec3]) -> bool {
+		let hit = aabb_hits_hull(aabb, hull);
+		assert_eq![hit, bgjk(&aabb.corners(), hull)];
+		hit
+	}
+
+	#[test]
+	fn matches_corners() {
+		let hull: Vec<Vec3> = unit_cube()
+			.iter()
+			.map(|v| (*v - Vec3::ONE * 0.5).rotated_about(Vec3(1.0, 2.0, 0.5), 0.7))
+			.collect();
+		let mut count = 0;
+		let steps = [-1.6, -1.1, -0.7, -0.2, 0.3, 0.8];
+		for &x in &steps {
+			for &y in &steps {
+				for &z in &steps {
+					let min = Vec3(x, y, z);
+					let aabb = Aabb { min, max: min + Vec3(0.5, 0.8, 0.3) };
+					count += hits(&aabb, &hull) as usize;
+				}
+			}
+		}
+		assert![count > 0 && count < steps.len() * steps.len() * steps.len()];
+	}
+
+	#[test]
+	fn touching() {
+		let cube = unit_cube();
+		let beside = Aabb { min: Vec3(1.0, 0.2, 0.3), max: Vec3(2.0, 0.8, 0.6) };
+		assert_eq![hits(&beside, &cube), true];
+		let corner = Aabb { min: Vec3::ONE, max: Vec3::ONE * 2.0 };
+		assert_eq![hits(&corner, &cube), true];
+		let apart = Aabb { min: Vec3(1.01, 0.2, 0.3), max: Vec3(2.0, 0.8, 0.6) };
+		assert_eq![hits(&apart, &cube), false];
+	}
+
+	#[test]
+	fn rectangle() {
+		let square = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let overlapping = Aabb { min: Vec3(0.5, 0.5, 0.0), max: Vec3(1.5, 1.5, 0.0) };
+		assert_eq![hits(&overlapping, &square), true];
+		let beside = Aabb { min: Vec3(-3.0, 0.0, 0.0), max: Vec3(-2.0, 1.0, 0.0) };
+		assert_eq![hits(&beside, &square), false];
+		let above = Aabb { min: Vec3(0.0, 0.0, 0.1), max: Vec3(1.0, 1.0, 0.1) };
+		assert_eq![hits(&above, &square), false];
+	}
+
+	#[test]
+	fn segment() {
+		let line = pts![(0.5, 1.0, 0.0), (0.5, -1.0, 0.0)];
+		let across = Aabb { min: Vec3(0.0, 0.0, 0.0), max: Vec3(1.0, 0.0, 0.0) };
+		assert_eq![hits(&across, &line), true];
+		let short = Aabb { min: Vec3(0.0, 0.0, 0.0), max: Vec3(0.4, 0.0, 0.0) };
+		assert_eq![hits(&short, &line), false];
+	}
+
+	#[test]
+	fn point() {
+		let cube = unit_cube();
+		let inside = Aabb { min: Vec3(0.5, 0.5, 0.5), max: Vec3(0.5, 0.5, 0.5) };
+		assert_eq![hits(&inside, &cube), true];
+		let on_face = Aabb { min: Vec3(1.0, 0.5, 0.5), max: Vec3(1.0, 0.5, 0.5) };
+		assert_eq![hits(&on_face, &cube), true];
+		let outside = Aabb { min: Vec3(1.1, 0.5, 0.5), max: Vec3(1.1, 0.5, 0.5) };
+		assert_eq![hits(&outside, &cube), false];
+	}
+
+	#[test]
+	fn around_cubes() {
+		assert![Aabb::<f32>::from_points(&[]).is_none()];
+		let by = Vec3(-2.0, 0.5, 3.0);
+		let aabb = Aabb::from_points(&cube_at(by)).unwrap();
+		assert_eq![(aabb.min, aabb.max), (by, by + Vec3::ONE)];
+		assert![aabb.contains_point(by + Vec3::ONE * 0.5)];
+		assert![aabb.contains_point(by + Vec3::ONE)];
+		assert![!aabb.contains_point(by - Vec3::Z * 0.01)];
+		let point = Aabb::from_points(&[Vec3(1.0, 2.0, 3.0)]).unwrap();
+		assert_eq![(point.min, point.max), (Vec3(1.0, 2.0, 3.0), Vec3(1.0, 2.0, 3.0))];
+
+		let other = Aabb::from_points(&cube_at(Vec3(0.5, 0.5, 0.5))).unwrap();
+		let cube = Aabb::from_points(&cube_at(Vec3::ZERO)).unwrap();
+		let union = cube.union(&other);
+		assert_eq![(union.min, union.max), (Vec3::ZERO, Vec3::ONE * 1.5)];
+		let shared = cube.intersection(&other).unwrap();
+		assert_eq![(shared.min, shared.max), (Vec3::ONE * 0.5, Vec3::ONE)];
+		assert![cube.overlaps(&other) && other.overlaps(&cube)];
+		let far = Aabb::from_points(&cube_at(Vec3(0.5, 3.0, 0.5))).unwrap();
+		assert![cube.intersection(&far).is_none()];
+		assert![!cube.overlaps(&far) && !far.overlaps(&cube)];
+
+		let grown = cube.expand(0.25);
+		assert_eq![(grown.min, grown.max), (Vec3::ONE * -0.25, Vec3::ONE * 1.25)];
+		let shrunk = cube.expand(-0.25);
+		assert_eq![(shrunk.min, shrunk.max), (Vec3::ONE * 0.25, Vec3::ONE * 0.75)];
+		let collapsed = cube.expand(-2.0);
+		assert_eq![(collapsed.min, collapsed.max), (Vec3::ONE * 0.5, Vec3::ONE * 0.5)];
+	}
+
+	#[test]
+	fn touching_boxes() {
+		let cube = Aabb::from_points(&cube_at(Vec3::ZERO)).unwrap();
+		for i in 0..27 {
+			let by = Vec3((i % 3 - 1) as f32, (i / 3 % 3 - 1) as f32, (i / 9 - 1) as f32);
+			let other = Aabb::from_points(&cube_at(by)).unwrap();
+			assert![cube.overlaps(&other)];
+			let shared = cube.intersection(&other).unwrap();
+			// Sharing a face, an edge or a corner, or all of it where not moved
+			let extent = shared.max - shared.min;
+			let flat = [extent.0, extent.1, extent.2].iter().filter(|&&e| e == 0.0).count();
+			assert_eq![flat, [by.0, by.1, by.2].iter().filter(|&&b| b != 0.0).count()];
+			let apart = Aabb::from_points(&cube_at(by * 1.001)).unwrap();
+			assert_eq![cube.overlaps(&apart), i == 13];
+			assert_eq![cube.intersection(&apart).is_some(), i == 13];
+		}
+	}
+
+	#[test]
+	fn corners_round_trip() {
+		let cube = Aabb::from_points(&cube_at(Vec3::ZERO)).unwrap();
+		assert_eq![cube.corners(), unit_cube()];
+		let aabb = Aabb { min: Vec3(-1.5, 0.25, 2.0), max: Vec3(3.0, 0.5, 2.0) };
+		let again = Aabb::from_points(&aabb.corners()).unwrap();
+		assert_eq![(again.min, again.max), (aabb.min, aabb.max)];
+		assert![aabb.corners().iter().all(|&corner| aabb.contains_point(corner))];
+	}
+
+	#[test]
+	fn empty_hull() {
+		let around = Aabb { min: -Vec3::ONE, max: Vec3::ONE };
+		assert_eq![aabb_hits_hull(&around, &[]), false];
+	}
+}
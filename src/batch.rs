@@ -0,0 +1,199 @@
+//! Many hull pairs tested in one call, for the narrowphase of a frame.
+use super::{Aabb, Float, Vector3, bgjk};
+
+/// Two hulls to test against each other, see `bgjk_batch`
+pub type HullPair<'a, T = f32> = (&'a [Vector3<T>], &'a [Vector3<T>]);
+
+/// Whether each pair of hulls intersects, see `bgjk`
+///
+/// The result at each index is what `bgjk` gives for the pair at that
+/// index. The slices of a pair may alias each other and those of other
+/// pairs, so a hull tested against many others is borrowed once and
+/// referenced from each pair. See `bgjk_batch_into` to reuse the output.
+///
+/// ```
+/// use bgjk::{bgjk_batch, HullPair, Vec3};
+///
+/// let triangle = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// let near = [Vec3::new(0.2, 0.2, 0.0)];
+/// let far = [Vec3::new(2.0, 0.2, 0.0)];
+/// let pairs: [HullPair; 3] = [(&triangle, &near), (&triangle, &far), (&far, &far)];
+/// assert_eq![bgjk_batch(&pairs), [true, false, true]];
+/// ```
+pub fn bgjk_batch<T: Float>(pairs: &[HullPair<T>]) -> Vec<bool> {
+	let mut out = vec![false; pairs.len()];
+	bgjk_batch_into(pairs, &mut out);
+	out
+}
+
+/// Like `bgjk_batch`, writing the results into `out` instead
+///
+/// Allocates nothing, so a buffer kept across frames costs nothing per
+/// frame. Panics unless `out` has one entry per pair.
+///
+/// ```
+/// use bgjk::{bgjk_batch_into, Vec3};
+///
+/// let point = [Vec3::new(0.0, 0.0, 0.0)];
+/// let mut out = [false; 2];
+/// bgjk_batch_into(&[(&point[..], &point[..]), (&point, &[Vec3::X])], &mut out);
+/// assert_eq![out, [true, false]];
+/// ```
+pub fn bgjk_batch_into<T: Float>(pairs: &[HullPair<T>], out: &mut [bool]) {
+	assert_eq![pairs.len(),
+	           out.len(),
+	           "bgjk_batch_into needs one output per pair"];
+	for (hit, &(hull1, hull2)) in out.iter_mut().zip(pairs) {
+		*hit = bgjk(hull1, hull2);
+	}
+}
+
+// The box bounding the hull, the origin for no points like the support
+pub fn bounds<T: Float>(hull: &[Vector3<T>]) -> Aabb<T> {
+	Aabb::from_points(hull).unwrap_or(Aabb { min: Vector3::ZERO, max: Vector3::ZERO })
+}
+
+/// Every pair of intersecting hulls, by index
+///
+/// Tests each pair `(i, j)` with `i < j` and returns those `bgjk` finds to
+/// intersect, sorted and each pair once. The bounding boxes of the hulls
+/// are computed first and pairs whose boxes are apart are skipped, as they
+/// cannot intersect, which makes the quadratic number of pairs cheap for
+/// scenes of up to a few hundred hulls. Larger scenes want a broadphase.
+///
+/// ```
+/// use bgjk::{find_overlaps, Vec3};
+///
+/// let a = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0)];
+/// let b = [Vec3::new(1.0, -1.0, 0.0), Vec3::new(1.0, 1.0, 0.0)];
+/// let c = [Vec3::new(5.0, 0.0, 0.0)];
+/// assert_eq![find_overlaps(&[&a[..], &b, &c, &b]), [(0, 1), (0, 3), (1, 3)]];
+/// ```
+pub fn find_overlaps<T: Float>(hulls: &[&[Vector3<T>]]) -> Vec<(usize, usize)> {
+	let boxes: Vec<Aabb<T>> = hulls.iter().map(|hull| bounds(hull)).collect();
+	let mut overlaps = Vec::new();
+	for (i, first) in boxes.iter().enumerate() {
+		for (j, second) in boxes.iter().enumerate().skip(i + 1) {
+			if first.overlaps(second) && bgjk(hulls[i], hulls[j]) {
+				overlaps.push((i, j));
+			}
+		}
+	}
+	overlaps
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::super::{HullPair, Vec3, bgjk, bgjk_batch, bgjk_batch_into, find_overlaps};
+
+	// Hulls of a few points each, spread over a small region so that about
+	// half of the pairs intersect
+	fn hulls() -> Vec<Vec<Vec3>> {
+		(0..40)
+			.map(|i| {
+				let (sin, cos) = (i as f32 * 0.83).sin_cos();
+				let center = Vec3(cos, sin, (i % 5) as f32 * 0.3) * (i % 7) as f32 * 0.4;
+				(0..1 + i % 6)
+					.map(|j| {
+						let (sin, cos) = (j as f32 * 1.7 + i as f32).sin_cos();
+						center + Vec3(cos, sin, (j % 3) as f32 - 1.0) * 0.6
+					})
+					.collect()
+			})
+			.collect()
+	}
+
+	#[test]
+	fn matches_bgjk() {
+		let hulls = hulls();
+		let mut pairs: Vec<HullPair> = Vec::new();
+		for (i, hull1) in hulls.iter().enumerate() {
+			for hull2 in hulls.iter().skip(i).step_by(3) {
+				pairs.push((hull1, hull2));
+			}
+		}
+		assert![pairs.len() > 200];
+		let results = bgjk_batch(&pairs);
+		let hits = results.iter().filter(|&&hit| hit).count();
+		assert![hits > 0 && hits < pairs.len()];
+		for (&hit, &(hull1, hull2)) in results.iter().zip(&pairs) {
+			assert_eq![hit, bgjk(hull1, hull2)];
+		}
+		let mut out = vec![false; pairs.len()];
+		bgjk_batch_into(&pairs, &mut out);
+		assert_eq![out, results];
+	}
+
+	#[test]
+	fn empty() {
+		let pairs: [HullPair; 0] = [];
+		assert![bgjk_batch(&pairs).is_empty()];
+		bgjk_batch_into(&pairs, &mut []);
+	}
+
+	#[test]
+	#[should_panic(expected = "bgjk_batch_into needs one output per pair")]
+	fn output_length() {
+		let point = [Vec3::ZERO];
+		bgjk_batch_into(&[(&point[..], &point[..])], &mut [false; 2]);
+	}
+
+	fn cube_at(corner: Vec3, size: f32) -> Vec<Vec3> {
+		(0..8)
+			.map(|i| {
+				let pick = |bit: usize| if i & bit == 0 { 0.0 } else { size };
+				corner + Vec3(pick(1), pick(2), pick(4))
+			})
+			.collect()
+	}
+
+	#[test]
+	fn touching_grid() {
+		// Neighbors share a face, an edge or a corner, all of which intersect
+		let cells: Vec<Vec3> = (0..18)
+			.map(|i| Vec3((i % 3) as f32, (i / 3 % 3) as f32, (i / 9) as f32))
+			.collect();
+		let cubes: Vec<Vec<Vec3>> = cells.iter().map(|&cell| cube_at(cell, 1.0)).collect();
+		let slices: Vec<&[Vec3]> = cubes.iter().map(Vec::as_slice).collect();
+		let mut expected = Vec::new();
+		for i in 0..cells.len() {
+			for j in i + 1..cells.len() {
+				let apart = (cells[i] - cells[j]).abs();
+				if apart.0 <= 1.0 && apart.1 <= 1.0 && apart.2 <= 1.0 {
+					expected.push((i, j));
+				}
+			}
+		}
+		assert_eq![find_overlaps(&slices), expected];
+	}
+
+	#[test]
+	fn apart() {
+		let cubes: Vec<Vec<Vec3>> = (0..27)
+			.map(|i| cube_at(Vec3((i % 3) as f32, (i / 3 % 3) as f32, (i / 9) as f32) * 1.5, 1.0))
+			.collect();
+		let slices: Vec<&[Vec3]> = cubes.iter().map(Vec::as_slice).collect();
+		assert_eq![find_overlaps(&slices), []];
+		assert_eq![find_overlaps::<f32>(&[]), []];
+		assert_eq![find_overlaps(&slices[..1]), []];
+		// Diagonal slivers whose boxes overlap but whose hulls do not
+		let sliver = [Vec3(0.0, 0.0, 0.0), Vec3(1.0, 1.0, 0.0)];
+		let beside = [Vec3(0.6, 0.0, 0.0), Vec3(1.0, 0.4, 0.0)];
+		assert![!bgjk(&sliver, &beside)];
+		assert_eq![find_overlaps(&[&sliver[..], &beside]), []];
+	}
+
+	#[test]
+	fn containing() {
+		let big = cube_at(Vec3::ZERO, 10.0);
+		let smalls: Vec<Vec<Vec3>> = (0..5)
+			.map(|i| cube_at(Vec3(1.0 + i as f32 * 1.7, 2.0, 3.0 + (i % 2) as f32), 1.0))
+			.collect();
+		let outside = cube_at(Vec3(11.0, 0.0, 0.0), 1.0);
+		let mut slices: Vec<&[Vec3]> = smalls.iter().map(Vec::as_slice).collect();
+		slices.insert(2, &big);
+		slices.push(&outside);
+		assert_eq![find_overlaps(&slices), [(0, 2), (1, 2), (2, 3), (2, 4), (2, 5)]];
+	}
+}
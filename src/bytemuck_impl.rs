@@ -0,0 +1,35 @@
+//! Plain-old-data markers for `Vec3`, relying on its `repr(C)` layout.
+use bytemuck::{Pod, Zeroable};
+use super::Vec3;
+
+// Safety: `Vec3` is `repr(C)` with three `f32` fields and thus has no
+// padding, and every bit pattern (including all zeroes) is a valid `f32`.
+unsafe impl Zeroable for Vec3 {}
+unsafe impl Pod for Vec3 {}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+
+	use bytemuck;
+	use super::super::{Vec3, bgjk};
+
+	#[test]
+	fn zeroed() {
+		assert_eq![<Vec3 as bytemuck::Zeroable>::zeroed(), Vec3::ZERO];
+	}
+
+	#[test]
+	fn cast_vertex_buffer() {
+		let floats: [f32; 12] = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0];
+		let bytes: &[u8] = bytemuck::cast_slice(&floats);
+		assert_eq![bytes.len(), 48];
+		let hull: &[Vec3] = bytemuck::cast_slice(bytes);
+		assert_eq![hull, &[Vec3::ZERO, Vec3::X, Vec3::Y, Vec3(1.0, 1.0, 0.0)][..]];
+		assert_eq![bgjk(hull, &[Vec3(0.5, 0.5, 0.0)]), true];
+		assert_eq![bgjk(hull, &[Vec3(0.5, 0.5, 0.5)]), false];
+		let back: &[u8] = bytemuck::cast_slice(hull);
+		assert_eq![back, bytes];
+	}
+
+}
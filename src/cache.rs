@@ -0,0 +1,224 @@
+//! Separating axes kept between frames, for pairs tested again and again.
+use std::collections::HashMap;
+use super::{Float, GjkConfig, Vector3, either_empty, gjk_search, hull_support};
+
+// What the last test of a pair found, and when it was
+#[derive(Clone, Copy, Debug)]
+struct Entry<T> {
+	axis: Vector3<T>,
+	colliding: bool,
+	used: u64,
+}
+
+/// The last search direction of each pair of hulls, for `bgjk_cached`
+///
+/// Pairs are told apart by an id of the caller's choosing, such as the
+/// indices of both bodies packed into a `u64`. Holds at most `capacity`
+/// pairs: once full, the half of them tested least recently is dropped to
+/// make room, so pairs tested every frame stay and pairs that drifted out
+/// of the broadphase go.
+///
+/// Counts the queries and the iterations they took, see `GjkStats`, over
+/// every `bgjk_cached` on it, for telling how well the cache works.
+#[derive(Clone, Debug)]
+pub struct PairCache<T = f32> {
+	entries: HashMap<u64, Entry<T>>,
+	capacity: usize,
+	queries: u64,
+	iterations: u64,
+}
+
+impl<T: Float> PairCache<T> {
+	/// A cache holding at most `capacity` pairs, and at least one
+	pub fn new(capacity: usize) -> PairCache<T> {
+		PairCache {
+			entries: HashMap::new(),
+			capacity: capacity.max(1),
+			queries: 0,
+			iterations: 0,
+		}
+	}
+
+	/// The most pairs the cache holds
+	pub fn capacity(&self) -> usize {
+		self.capacity
+	}
+
+	/// How many pairs the cache holds
+	pub fn len(&self) -> usize {
+		self.entries.len()
+	}
+
+	/// Whether the cache holds no pairs
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	/// The last search direction of the pair and whether it was colliding,
+	/// `None` for pairs not tested or since dropped
+	///
+	/// Where the pair was apart, the direction is an axis it was separated
+	/// along, like `bgjk_separating_axis` gives.
+	pub fn get(&self, id: u64) -> Option<(Vector3<T>, bool)> {
+		self.entries.get(&id).map(|entry| (entry.axis, entry.colliding))
+	}
+
+	/// Forget the pair, such as when one of its bodies is removed
+	pub fn remove(&mut self, id: u64) {
+		self.entries.remove(&id);
+	}
+
+	/// Forget every pair, keeping the counts
+	pub fn clear(&mut self) {
+		self.entries.clear();
+	}
+
+	/// How many times `bgjk_cached` ran on the cache
+	pub fn queries(&self) -> u64 {
+		self.queries
+	}
+
+	/// The iterations of every `bgjk_cached` on the cache added up
+	pub fn iterations(&self) -> u64 {
+		self.iterations
+	}
+
+	fn insert(&mut self, id: u64, axis: Vector3<T>, colliding: bool) {
+		let used = self.queries;
+		if self.entries.len() >= self.capacity && !self.entries.contains_key(&id) {
+			// The queries numbered after `recent` tested no more than half
+			// the capacity of pairs, so at least half of the cache goes
+			let recent = used.saturating_sub((self.capacity / 2) as u64);
+			self.entries.retain(|_, entry| entry.used > recent);
+		}
+		self.entries.insert(id, Entry { axis, colliding, used });
+	}
+}
+
+/// The BGJK algorithm starting from the direction the pair ended with the
+/// last time, see `bgjk` and `bgjk_with_hint`
+///
+/// The answer is always that of `bgjk`, only the work differs. Hulls that
+/// were apart and moved little since are mostly still apart along the same
+/// axis, found again by the first support point or two. The direction the
+/// search ends with is kept for the next time, whether the hulls intersect
+/// or not.
+///
+/// ```
+/// use bgjk::{bgjk, bgjk_cached, PairCache, Vec3};
+///
+/// let hull1 = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// let mut cache = PairCache::new(1024);
+/// for frame in 0..10 {
+///     let by = Vec3::new(3.0 - frame as f32 * 0.1, 0.0, 0.0);
+///     let hull2: Vec<Vec3> = hull1.iter().map(|v| *v + by).collect();
+///     assert_eq![bgjk_cached(&mut cache, 7, &hull1, &hull2), bgjk(&hull1, &hull2)];
+/// }
+/// assert_eq![cache.get(7).map(|(_, colliding)| colliding), Some(false)];
+/// ```
+pub fn bgjk_cached<T: Float>(cache: &mut PairCache<T>,
+                             id: u64,
+                             hull1: &[Vector3<T>],
+                             hull2: &[Vector3<T>])
+                             -> bool {
+	debug_assert![hull1.iter().chain(hull2).all(Vector3::is_finite),
+	              "bgjk called with a non-finite point in a hull"];
+	if either_empty(hull1, hull2) {
+		cache.queries += 1;
+		return false;
+	}
+	let initial_direction = cache.entries.get(&id).map_or(Vector3::ONE, |entry| entry.axis);
+	let config = GjkConfig { initial_direction, ..GjkConfig::default() };
+	let search = gjk_search(|direction| hull_support(hull1, hull2, direction), &config);
+	let hit = search.hit(&config);
+	cache.insert(id, search.direction, hit);
+	cache.queries += 1;
+	cache.iterations += search.support_calls.saturating_sub(2) as u64;
+	hit
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::super::{PairCache, Vec3, bgjk, bgjk_cached, bgjk_stats};
+	use super::super::test_util::cube_at;
+
+	// Pair `pair` in frame `frame`: a cube and a jittered, slowly orbiting
+	// octahedron
+	fn hulls(pair: u64, frame: u32) -> (Vec<Vec3>, Vec<Vec3>) {
+		let t = frame as f32 * 0.02 + pair as f32 * 0.9;
+		let center = Vec3(t.cos(), t.sin(), (t * 0.3).sin()) * (0.9 + (pair % 5) as f32 * 0.4);
+		let octahedron = [Vec3::X, -Vec3::X, Vec3::Y, -Vec3::Y, Vec3::Z, -Vec3::Z]
+			.iter()
+			.enumerate()
+			.map(|(i, corner)| {
+				let jitter = ((frame as usize * 7 + i * 13) % 17) as f32 * 0.001;
+				center + *corner * (0.5 + jitter)
+			})
+			.collect();
+		(cube_at(Vec3::ONE * -0.5), octahedron)
+	}
+
+	#[test]
+	fn same_as_bgjk() {
+		let mut cache = PairCache::new(64);
+		let mut hits = 0;
+		for frame in 0..100 {
+			for pair in 0..20 {
+				let (hull1, hull2) = hulls(pair, frame);
+				let expected = bgjk(&hull1, &hull2);
+				assert_eq![bgjk_cached(&mut cache, pair, &hull1, &hull2), expected];
+				assert_eq![cache.get(pair).map(|(_, colliding)| colliding), Some(expected)];
+				hits += expected as usize;
+			}
+		}
+		assert![hits > 0 && hits < 2000];
+		assert_eq![(cache.len(), cache.queries()), (20, 2000)];
+	}
+
+	#[test]
+	fn fewer_iterations_warm() {
+		let mut cache = PairCache::new(64);
+		let mut cold = 0;
+		for frame in 0..100 {
+			for pair in 0..20 {
+				let (hull1, hull2) = hulls(pair, frame);
+				bgjk_cached(&mut cache, pair, &hull1, &hull2);
+				cold += bgjk_stats(&hull1, &hull2).1.iterations as u64;
+			}
+		}
+		assert![cache.iterations() * 2 < cold];
+	}
+
+	#[test]
+	fn eviction() {
+		let mut cache = PairCache::new(8);
+		let (hull1, hull2) = hulls(0, 0);
+		for id in 1..100 {
+			// The pair tested every time stays
+			bgjk_cached(&mut cache, 0, &hull1, &hull2);
+			bgjk_cached(&mut cache, id, &hull1, &hull2);
+			assert![cache.len() <= 8];
+			assert![cache.get(0).is_some() && cache.get(id).is_some()];
+		}
+		assert![cache.get(1).is_none()];
+		cache.remove(0);
+		assert![cache.get(0).is_none()];
+		cache.clear();
+		assert![cache.is_empty()];
+		assert_eq![cache.queries(), 198];
+		let mut tiny = PairCache::<f32>::new(0);
+		bgjk_cached(&mut tiny, 3, &hull1, &hull2);
+		bgjk_cached(&mut tiny, 4, &hull1, &hull2);
+		assert_eq![(tiny.capacity(), tiny.len()), (1, 1)];
+		assert![tiny.get(4).is_some()];
+	}
+
+	#[test]
+	fn empty_hull() {
+		let cube = cube_at(Vec3(-0.5, -0.5, -0.5));
+		let mut cache = PairCache::new(4);
+		assert![!bgjk_cached(&mut cache, 0, &[], &cube)];
+		assert![!bgjk_cached(&mut cache, 1, &cube, &[])];
+	}
+}
@@ -0,0 +1,93 @@
+//! The centroid of the points of a hull.
+use super::{Float, Vector3};
+
+// Neumaier's compensated summation, adding `value` to `sum` and the
+// rounding error of that to `compensation`
+fn add<T: Float>(sum: &mut T, compensation: &mut T, value: T) {
+	let total = *sum + value;
+	if sum.abs() >= value.abs() {
+		*compensation = *compensation + ((*sum - total) + value);
+	} else {
+		*compensation = *compensation + ((value - total) + *sum);
+	}
+	*sum = total;
+}
+
+/// The mean of the points, `None` for no points
+///
+/// The points are summed as offsets from the first, with the rounding
+/// errors of the sum carried along, so neither many points nor points far
+/// from the origin make it drift. The result is within about a unit in the
+/// last place of the centroid plus an epsilon of the spread of the points,
+/// where adding a hundred thousand points around `1e6` one by one in `f32`
+/// would be off by thousands.
+///
+/// The vertices of a hull are not spread evenly over it, so this is not
+/// its center of mass, but it is inside it and cheap.
+///
+/// ```
+/// use bgjk::{centroid, Vec3};
+///
+/// let triangle = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(3.0, 0.0, 0.0), Vec3::new(0.0, 3.0, 0.0)];
+/// assert_eq![centroid(&triangle), Some(Vec3::new(1.0, 1.0, 0.0))];
+/// assert_eq![centroid::<f32>(&[]), None];
+/// ```
+pub fn centroid<T: Float>(points: &[Vector3<T>]) -> Option<Vector3<T>> {
+	let origin = *points.first()?;
+	let (mut sum, mut compensation) = (Vector3::ZERO, Vector3::ZERO);
+	for point in &points[1..] {
+		let offset = *point - origin;
+		add(&mut sum.0, &mut compensation.0, offset.0);
+		add(&mut sum.1, &mut compensation.1, offset.1);
+		add(&mut sum.2, &mut compensation.2, offset.2);
+	}
+	Some(origin + (sum + compensation) / T::from_f32(points.len() as f32))
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::super::{DVec3, Vec3, centroid};
+	use super::super::test_util::cube_at;
+
+	#[test]
+	fn small() {
+		assert_eq![centroid(&cube_at(Vec3::ZERO)), Some(Vec3::ONE * 0.5)];
+		assert_eq![centroid(&cube_at(Vec3(-3.0, 2.0, 10.0))), Some(Vec3(-2.5, 2.5, 10.5))];
+		assert_eq![centroid(&[Vec3(1.5, -2.0, 7.0)]), Some(Vec3(1.5, -2.0, 7.0))];
+		assert_eq![centroid::<f32>(&[]), None];
+		let cube: Vec<DVec3> = cube_at(Vec3::ZERO).iter().map(|v| DVec3::from(*v)).collect();
+		assert_eq![centroid(&cube), Some(DVec3::ONE * 0.5)];
+	}
+
+	#[test]
+	fn far_and_many() {
+		// A hundred thousand points around a million, compared with the mean
+		// taken in `f64`
+		let points: Vec<Vec3> = (0..100_000)
+			.map(|i| {
+				let t = i as f32;
+				Vec3(1e6 + (t * 0.37).sin() * 50.0,
+				     -2e6 + (t * 1.91).cos() * 10.0,
+				     1e6 + (i % 1000) as f32)
+			})
+			.collect();
+		let found = centroid(&points).unwrap();
+		let mut exact = [0.0f64; 3];
+		for point in &points {
+			exact[0] += point.0 as f64;
+			exact[1] += point.1 as f64;
+			exact[2] += point.2 as f64;
+		}
+		for axis in 0..3 {
+			let mean = exact[axis] / points.len() as f64;
+			// A unit in the last place of the centroid and an epsilon of the
+			// spread, a few hundredths here
+			let bound = (mean.abs() + 1000.0) * f32::EPSILON as f64;
+			assert![(found[axis] as f64 - mean).abs() <= bound];
+		}
+		// Summed one by one in `f32`, far off
+		let naive = points.iter().fold(Vec3::ZERO, |sum, point| sum + *point) / 100_000.0;
+		assert![(naive.0 as f64 - exact[0] / 1e5).abs() > 1.0];
+	}
+}
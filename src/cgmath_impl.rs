@@ -0,0 +1,99 @@
+//! Conversions between `Vec3` and the `cgmath` point and vector types.
+use cgmath::{Point3, Vector3};
+use super::Vec3;
+
+impl From<Point3<f32>> for Vec3 {
+	fn from(point: Point3<f32>) -> Vec3 {
+		Vec3(point.x, point.y, point.z)
+	}
+}
+
+impl From<Vec3> for Point3<f32> {
+	fn from(vector: Vec3) -> Point3<f32> {
+		Point3::new(vector.0, vector.1, vector.2)
+	}
+}
+
+impl From<Vector3<f32>> for Vec3 {
+	fn from(vector: Vector3<f32>) -> Vec3 {
+		Vec3(vector.x, vector.y, vector.z)
+	}
+}
+
+impl From<Vec3> for Vector3<f32> {
+	fn from(vector: Vec3) -> Vector3<f32> {
+		Vector3::new(vector.0, vector.1, vector.2)
+	}
+}
+
+impl Vec3 {
+	/// Convert a slice of `cgmath` points into a hull
+	pub fn slice_from_cgmath(points: &[Point3<f32>]) -> Vec<Vec3> {
+		points.iter().map(|point| Vec3::from(*point)).collect()
+	}
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+
+	use std::f32;
+	use cgmath::{Point3, Vector3};
+	use super::super::{Vec3, bgjk};
+	static EPS: f32 = f32::EPSILON;
+
+	macro_rules! pts {
+		($($e:expr),*) => {
+			Vec3::slice_from_cgmath(&[$(
+				Point3::new($e.0, $e.1, $e.2)
+			),*])
+		};
+	}
+
+	#[test]
+	fn round_trip() {
+		let v = Vec3(1.0, -2.0, 3.5);
+		let point: Point3<f32> = v.into();
+		assert_eq![(point.x, point.y, point.z), (1.0, -2.0, 3.5)];
+		assert_eq![Vec3::from(point), v];
+		let vector: Vector3<f32> = v.into();
+		assert_eq![(vector.x, vector.y, vector.z), (1.0, -2.0, 3.5)];
+		assert_eq![Vec3::from(vector), v];
+	}
+
+	#[test]
+	fn square1() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let shape2 = pts![(-2.0, 0.0, 0.0), (-3.0, 0.0, 0.0), (-2.0, 1.0, 0.0), (-3.0, 1.0, 0.0)];
+		assert_eq![bgjk(&shape1, &shape2), false];
+	}
+
+	#[test]
+	fn side_by_side_squares() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let shape2 = pts![(1.0, 0.0, 0.0), (2.0, 0.0, 0.0), (1.0, 1.0, 0.0), (2.0, 1.0, 0.0)];
+		assert_eq![bgjk(&shape1, &shape2), true];
+	}
+
+	#[test]
+	fn single_point_shape_non_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0),
+		                 (1.0, 0.0, 0.0),
+		                 (0.0, 1.0, 0.0),
+		                 (1.0, 1.0, 0.0),
+		                 (0.0, 0.0, 1.0),
+		                 (1.0, 0.0, 1.0),
+		                 (0.0, 1.0, 1.0),
+		                 (1.0, 1.0, 1.0)];
+		let shape2 = pts![(1.0, 1.0, 1.0 + EPS),
+		                 (2.0, 1.0, 1.0 + EPS),
+		                 (1.0, 2.0, 1.0 + EPS),
+		                 (2.0, 2.0, 1.0 + EPS),
+		                 (1.0, 1.0, 2.0),
+		                 (2.0, 1.0, 2.0),
+		                 (1.0, 2.0, 2.0),
+		                 (2.0, 2.0, 2.0)];
+		assert_eq![bgjk(&shape1, &shape2), false];
+	}
+
+}
@@ -0,0 +1,242 @@
+//! Limits on the BGJK search, see `bgjk_config`.
+use super::{Float, Vector3};
+
+/// What `bgjk_config` answers when it runs out of iterations
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OnLimit {
+	/// Report an intersection, so a hit is never missed
+	Hit,
+	/// Report no intersection, so a miss is never reported as a hit
+	Miss,
+}
+
+/// Whether hulls that only touch intersect, see `GjkConfig`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Boundary {
+	/// Hulls sharing only a face, an edge or a corner intersect
+	Inclusive,
+	/// Only hulls whose interiors overlap intersect. Hulls without volume,
+	/// such as squares or an empty hull taken as the origin, have no interior
+	/// and never do.
+	Exclusive,
+}
+
+/// What a hull with no points is, see `GjkConfig`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EmptyHull {
+	/// A hull with no points intersects nothing, not even another one, as
+	/// in every other query of the crate
+	Miss,
+	/// A hull with no points is a single point at the origin, as it was for
+	/// `bgjk` before
+	Origin,
+}
+
+/// Iteration cap, convergence tolerance and start of `bgjk_config`
+///
+/// The default visits at most 64 support points, which well-behaved hulls
+/// never need, has no tolerance, treats running out of iterations as a hit,
+/// counts touching hulls as intersecting, has empty hulls intersect nothing
+/// and starts searching along `Vec3::ONE`. `bgjk` uses it.
+#[derive(Clone, Copy, Debug)]
+pub struct GjkConfig<T = f32> {
+	/// The most support points to visit before answering with `on_limit`
+	pub max_iterations: u32,
+	/// How far apart the hulls may be and still count as touching. Once the
+	/// simplex of the search comes this close to the origin of the Minkowski
+	/// difference, the search stops and reports a hit instead of creeping
+	/// along a glancing contact. Zero never stops early. With an exclusive
+	/// `boundary` it is instead how deep hulls may overlap and still only
+	/// count as touching.
+	pub tolerance: T,
+	/// The answer when `max_iterations` runs out
+	pub on_limit: OnLimit,
+	/// Whether touching hulls intersect
+	pub boundary: Boundary,
+	/// Whether a hull with no points intersects hulls around the origin
+	pub empty: EmptyHull,
+	/// The direction to look for the first support point in, see
+	/// `bgjk_with_hint`. Zero or non-finite directions start along
+	/// `Vec3::ONE` instead.
+	pub initial_direction: Vector3<T>,
+}
+
+impl<T: Float> PartialEq for GjkConfig<T> {
+	fn eq(&self, other: &GjkConfig<T>) -> bool {
+		self.max_iterations == other.max_iterations && self.tolerance == other.tolerance &&
+		self.on_limit == other.on_limit && self.boundary == other.boundary &&
+		self.empty == other.empty && self.initial_direction == other.initial_direction
+	}
+}
+
+impl<T: Float> Default for GjkConfig<T> {
+	fn default() -> GjkConfig<T> {
+		GjkConfig {
+			max_iterations: 64,
+			tolerance: T::ZERO,
+			on_limit: OnLimit::Hit,
+			boundary: Boundary::Inclusive,
+			empty: EmptyHull::Miss,
+			initial_direction: Vector3::ONE,
+		}
+	}
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+
+	use super::super::{Boundary, EmptyHull, GjkConfig, OnLimit, Vec3, bgjk, bgjk_config,
+	                   bgjk_stats};
+	use super::super::test_util::{shifted, unit_cube};
+
+	#[test]
+	fn default_matches_bgjk() {
+		let cube = unit_cube();
+		for &by in &[Vec3(0.5, 0.5, 0.5), Vec3(1.0, 0.0, 0.0), Vec3(1.1, 0.2, 0.0), Vec3(0.9, 0.9, 0.9)] {
+			let other = shifted(&cube, by);
+			assert_eq![bgjk_config(&cube, &other, &GjkConfig::default()), bgjk(&cube, &other)];
+		}
+	}
+
+	#[test]
+	fn limit() {
+		let cube = unit_cube();
+		let near = shifted(&cube, Vec3(1.001, 0.3, 0.2));
+		let hit = GjkConfig { max_iterations: 1, ..GjkConfig::default() };
+		let miss = GjkConfig { on_limit: OnLimit::Miss, ..hit };
+		assert_eq![bgjk_config(&cube, &near, &hit), true];
+		assert_eq![bgjk_config(&cube, &near, &miss), false];
+		let through = shifted(&cube, Vec3(0.999, 0.3, 0.2));
+		assert_eq![bgjk_config(&cube, &through, &hit), true];
+		assert_eq![bgjk_config(&cube, &through, &miss), false];
+		assert_eq![bgjk_config(&cube, &near, &GjkConfig { max_iterations: 64, ..miss }), false];
+		assert_eq![bgjk_config(&cube, &through, &GjkConfig { max_iterations: 64, ..miss }), true];
+	}
+
+	#[test]
+	fn default_limit() {
+		// Touching at a corner, which the search creeps towards without end
+		let tetrahedron = pts![(-0.2, 0.1, -0.3),
+		                       (-0.4, 0.3, 0.0),
+		                       (0.0, -0.4, -0.1),
+		                       (0.0, -0.1, -0.4)];
+		let corner = [tetrahedron[0]];
+		assert_eq![bgjk_stats(&tetrahedron, &corner).1.iterations, 64];
+		assert_eq![bgjk(&tetrahedron, &corner), true];
+		let miss = GjkConfig { on_limit: OnLimit::Miss, ..GjkConfig::default() };
+		assert_eq![bgjk_config(&tetrahedron, &corner, &miss), false];
+		let longer = GjkConfig { max_iterations: 10_000, ..miss };
+		assert_eq![bgjk_config(&tetrahedron, &corner, &longer), false];
+	}
+
+	#[test]
+	fn tolerance() {
+		let cube = unit_cube();
+		let loose = GjkConfig { tolerance: 1e-3, on_limit: OnLimit::Miss, ..GjkConfig::default() };
+		let glancing = shifted(&cube, Vec3(1.0005, 0.3, 0.2));
+		assert_eq![bgjk(&cube, &glancing), false];
+		assert_eq![bgjk_config(&cube, &glancing, &loose), true];
+		let apart = shifted(&cube, Vec3(1.01, 0.3, 0.2));
+		assert_eq![bgjk_config(&cube, &apart, &loose), false];
+	}
+
+	#[test]
+	fn exclusive_boundary() {
+		let cube = unit_cube();
+		let exclusive = GjkConfig { boundary: Boundary::Exclusive, ..GjkConfig::default() };
+		let touching = [Vec3(1.0, 0.0, 0.0), // Faces
+		                Vec3(1.0, 0.3, -0.2), // Faces, offset
+		                Vec3(1.0, 1.0, 0.0), // Edges
+		                Vec3(0.0, -1.0, 1.0), // Edges
+		                Vec3(1.0, 1.0, 1.0), // Corners
+		                Vec3(-1.0, 1.0, -1.0)]; // Corners
+		for &by in &touching {
+			let other = shifted(&cube, by);
+			assert_eq![bgjk(&cube, &other), true];
+			assert_eq![bgjk_config(&cube, &other, &exclusive), false];
+			assert_eq![bgjk_config(&other, &cube, &exclusive), false];
+		}
+		for &by in &[Vec3(0.99, 0.0, 0.0), Vec3(0.5, 0.5, 0.5), Vec3(0.99, 0.99, 0.99), Vec3::ZERO] {
+			let other = shifted(&cube, by);
+			assert_eq![bgjk_config(&cube, &other, &exclusive), true];
+			assert_eq![bgjk_config(&other, &cube, &exclusive), true];
+		}
+		assert_eq![bgjk_config(&cube, &shifted(&cube, Vec3(1.5, 0.0, 0.0)), &exclusive), false];
+	}
+
+	#[test]
+	fn exclusive_squares() {
+		let square = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let beside = shifted(&square, Vec3::X);
+		let exclusive = GjkConfig { boundary: Boundary::Exclusive, ..GjkConfig::default() };
+		assert_eq![bgjk(&square, &beside), true];
+		assert_eq![bgjk_config(&square, &beside, &exclusive), false];
+		// Squares have no interior at all
+		assert_eq![bgjk_config(&square, &square, &exclusive), false];
+	}
+
+	#[test]
+	fn exclusive_crossing_squares() {
+		let flat = pts![(-1.0, -1.0, 0.0), (1.0, -1.0, 0.0), (-1.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let upright = pts![(-1.0, 0.0, -1.0), (1.0, 0.0, -1.0), (-1.0, 0.0, 1.0), (1.0, 0.0, 1.0)];
+		let exclusive = GjkConfig { boundary: Boundary::Exclusive, ..GjkConfig::default() };
+		assert_eq![bgjk(&flat, &upright), true];
+		assert_eq![bgjk_config(&flat, &upright, &exclusive), false];
+		assert_eq![bgjk_config(&upright, &flat, &exclusive), false];
+		// A square through a cube still has no interior
+		let cube = shifted(&unit_cube(), Vec3(-0.5, -0.5, -0.5));
+		assert_eq![bgjk_config(&flat, &cube, &exclusive), false];
+	}
+
+	#[test]
+	fn exclusive_empty_origin() {
+		let cube = shifted(&unit_cube(), Vec3(-0.5, -0.5, -0.5));
+		let exclusive = GjkConfig {
+			boundary: Boundary::Exclusive,
+			empty: EmptyHull::Origin,
+			..GjkConfig::default()
+		};
+		assert_eq![bgjk_config(&[], &cube, &exclusive), false];
+		assert_eq![bgjk_config(&cube, &[], &exclusive), false];
+	}
+
+	#[test]
+	fn exclusive_limit() {
+		let cube = unit_cube();
+		let inside = shifted(&cube, Vec3(0.5, 0.3, 0.2));
+		let exclusive = GjkConfig {
+			boundary: Boundary::Exclusive,
+			max_iterations: 1,
+			..GjkConfig::default()
+		};
+		assert_eq![bgjk_config(&cube, &inside, &exclusive), true];
+		let miss = GjkConfig { on_limit: OnLimit::Miss, ..exclusive };
+		assert_eq![bgjk_config(&cube, &inside, &miss), false];
+	}
+
+	#[test]
+	fn exclusive_tolerance() {
+		let cube = unit_cube();
+		let barely = shifted(&cube, Vec3(0.9995, 0.3, 0.2));
+		let exclusive = GjkConfig { boundary: Boundary::Exclusive, ..GjkConfig::default() };
+		assert_eq![bgjk_config(&cube, &barely, &exclusive), true];
+		let loose = GjkConfig { tolerance: 1e-3, ..exclusive };
+		assert_eq![bgjk_config(&cube, &barely, &loose), false];
+		assert_eq![bgjk_config(&cube, &shifted(&cube, Vec3(0.99, 0.3, 0.2)), &loose), true];
+	}
+
+	#[test]
+	fn warm_start() {
+		let cube = unit_cube();
+		let behind = shifted(&cube, Vec3(-1.5, 0.0, 0.0));
+		let cold = GjkConfig { max_iterations: 0, ..GjkConfig::default() };
+		assert_eq![bgjk_config(&cube, &behind, &cold), true];
+		let warm = GjkConfig { initial_direction: -Vec3::X, ..cold };
+		assert_eq![bgjk_config(&cube, &behind, &warm), false];
+		let backwards = GjkConfig { initial_direction: Vec3::X, ..GjkConfig::default() };
+		assert_eq![bgjk_config(&cube, &behind, &backwards), false];
+		let broken = GjkConfig { initial_direction: Vec3(f32::NAN, 0.0, 0.0), ..GjkConfig::default() };
+		assert_eq![bgjk_config(&cube, &behind, &broken), false];
+	}
+}
@@ -0,0 +1,1304 @@
+//! Separation distance between hulls, the distance variant of GJK.
+use super::{Float, Vector3, bgjk_simplex, either_empty, farthest, point_in_hull, support};
+use super::Simplex as GjkSimplex;
+use super::penetration::expand;
+
+// Upper bound on the iterations, the loop normally stops on convergence
+// after a handful of steps but rounding can make it stall
+const MAX_ITERATIONS: usize = 64;
+
+// The simplex of the distance algorithm, points of the Minkowski
+// difference `hull1 - hull2` of which the first `len` are in use, along
+// with the hull points they are the difference of and the barycentric
+// weights of the closest point
+#[derive(Clone, Copy)]
+pub struct Simplex<T> {
+	points: [Vector3<T>; 4],
+	on1: [Vector3<T>; 4],
+	on2: [Vector3<T>; 4],
+	weights: [T; 3],
+	len: usize,
+}
+
+impl<T: Float> Simplex<T> {
+	pub fn new(on1: Vector3<T>, on2: Vector3<T>) -> Simplex<T> {
+		Simplex {
+			points: [on1 - on2; 4],
+			on1: [on1; 4],
+			on2: [on2; 4],
+			weights: [T::ONE, T::ZERO, T::ZERO],
+			len: 1,
+		}
+	}
+
+	pub fn contains(&self, point: Vector3<T>) -> bool {
+		self.points[..self.len].contains(&point)
+	}
+
+	pub fn push(&mut self, on1: Vector3<T>, on2: Vector3<T>) {
+		self.points[self.len] = on1 - on2;
+		self.on1[self.len] = on1;
+		self.on2[self.len] = on2;
+		self.len += 1;
+	}
+
+	// Move the points of the first hull by `by`, as the ray cast does when
+	// it advances along the ray
+	pub fn moved(&mut self, by: Vector3<T>) {
+		for i in 0..self.len {
+			self.on1[i] += by;
+			self.points[i] = self.on1[i] - self.on2[i];
+		}
+	}
+
+	// The indices of the points of each hull the corner is the difference
+	// of, the first of equal points, found by looking for them
+	pub fn vertex(&self,
+	              hull1: &[Vector3<T>],
+	              hull2: &[Vector3<T>],
+	              corner: usize) -> (usize, usize) {
+		let find = |hull: &[Vector3<T>], point| hull.iter().position(|&p| p == point).unwrap_or(0);
+		(find(hull1, self.on1[corner]), find(hull2, self.on2[corner]))
+	}
+
+	// The closest points on each hull, mapped back through the weights
+	pub fn witnesses(&self) -> (Vector3<T>, Vector3<T>) {
+		(0..self.len).fold((Vector3::ZERO, Vector3::ZERO), |(on1, on2), i| {
+			(on1 + self.on1[i] * self.weights[i], on2 + self.on2[i] * self.weights[i])
+		})
+	}
+
+	// The direction from the first hull towards the second, perpendicular to
+	// the simplex where it has an extent. The difference of the witnesses
+	// alone is inaccurate across a small gap.
+	fn normal(&self) -> Option<Vector3<T>> {
+		let (on1, on2) = self.witnesses();
+		let across = on2 - on1;
+		let [a, b, c, _] = self.points;
+		match self.len {
+			3 => {
+				(b - a).cross(c - a).try_normalized().map(|normal| {
+					if normal.dot(across) < T::ZERO { -normal } else { normal }
+				})
+			}
+			2 => across.reject_from(b - a).try_normalized(),
+			_ => across.try_normalized(),
+		}
+	}
+
+	// The points in use, the first repeated after them to fill four
+	pub fn corners(&self) -> [Vector3<T>; 4] {
+		let mut corners = [self.points[0]; 4];
+		corners[..self.len].copy_from_slice(&self.points[..self.len]);
+		corners
+	}
+
+	// The largest squared length of the points, the scale of the rounding
+	// errors in the closest point
+	pub fn max_length_squared(&self) -> T {
+		self.points[..self.len].iter().fold(T::ZERO, |max, point| max.max(point.length_squared()))
+	}
+
+	// Reduce the simplex to the smallest feature containing the point
+	// closest to the origin and return that point, or `None` if the
+	// simplex is a tetrahedron enclosing the origin
+	pub fn reduce(&mut self) -> Option<Vector3<T>> {
+		let feature = match self.len {
+			1 => Feature::vertex(0),
+			2 => closest_on_segment(&self.points, [0, 1]),
+			3 => closest_on_triangle(&self.points, [0, 1, 2]),
+			_ => closest_on_tetrahedron(&self.points)?,
+		};
+		let closest = feature.point(&self.points);
+		let old = *self;
+		for i in 0..feature.len {
+			self.points[i] = old.points[feature.indices[i]];
+			self.on1[i] = old.on1[feature.indices[i]];
+			self.on2[i] = old.on2[feature.indices[i]];
+		}
+		self.weights = feature.weights;
+		self.len = feature.len;
+		Some(closest)
+	}
+}
+
+// The vertices of a simplex spanning the feature closest to the origin, by
+// index, and the barycentric weights of the closest point
+#[derive(Clone, Copy)]
+struct Feature<T> {
+	indices: [usize; 3],
+	weights: [T; 3],
+	len: usize,
+}
+
+impl<T: Float> Feature<T> {
+	fn vertex(index: usize) -> Feature<T> {
+		Feature {
+			indices: [index; 3],
+			weights: [T::ONE, T::ZERO, T::ZERO],
+			len: 1,
+		}
+	}
+
+	fn point(&self, points: &[Vector3<T>; 4]) -> Vector3<T> {
+		(0..self.len).fold(Vector3::ZERO, |sum, i| sum + points[self.indices[i]] * self.weights[i])
+	}
+}
+
+// The closest point to the origin on the segment between two of the points
+fn closest_on_segment<T: Float>(points: &[Vector3<T>; 4], ids: [usize; 2]) -> Feature<T> {
+	let (a, b) = (points[ids[0]], points[ids[1]]);
+	let ab = b - a;
+	let t = -a.dot(ab);
+	let length_squared = ab.length_squared();
+	if t <= T::ZERO {
+		Feature::vertex(ids[0])
+	} else if t >= length_squared {
+		Feature::vertex(ids[1])
+	} else {
+		let t = t / length_squared;
+		Feature {
+			indices: [ids[0], ids[1], ids[1]],
+			weights: [T::ONE - t, t, T::ZERO],
+			len: 2,
+		}
+	}
+}
+
+// The closest point to the origin on the triangle spanned by three of the
+// points, following the region tests in Ericson's Real-Time Collision
+// Detection
+fn closest_on_triangle<T: Float>(points: &[Vector3<T>; 4], ids: [usize; 3]) -> Feature<T> {
+	let (a, b, c) = (points[ids[0]], points[ids[1]], points[ids[2]]);
+	let ab = b - a;
+	let ac = c - a;
+	let d1 = -ab.dot(a);
+	let d2 = -ac.dot(a);
+	if d1 <= T::ZERO && d2 <= T::ZERO {
+		return Feature::vertex(ids[0]);
+	}
+	let d3 = -ab.dot(b);
+	let d4 = -ac.dot(b);
+	if d3 >= T::ZERO && d4 <= d3 {
+		return Feature::vertex(ids[1]);
+	}
+	let vc = d1 * d4 - d3 * d2;
+	if vc <= T::ZERO && d1 >= T::ZERO && d3 <= T::ZERO {
+		return closest_on_segment(points, [ids[0], ids[1]]);
+	}
+	let d5 = -ab.dot(c);
+	let d6 = -ac.dot(c);
+	if d6 >= T::ZERO && d5 <= d6 {
+		return Feature::vertex(ids[2]);
+	}
+	let vb = d5 * d2 - d1 * d6;
+	if vb <= T::ZERO && d2 >= T::ZERO && d6 <= T::ZERO {
+		return closest_on_segment(points, [ids[0], ids[2]]);
+	}
+	let va = d3 * d6 - d5 * d4;
+	if va <= T::ZERO && d4 - d3 >= T::ZERO && d5 - d6 >= T::ZERO {
+		return closest_on_segment(points, [ids[1], ids[2]]);
+	}
+	let sum = va + vb + vc;
+	if sum <= T::ZERO {
+		// Collinear points, the closest point lies on one of the edges
+		return closest_of(points,
+		                  &[closest_on_segment(points, [ids[0], ids[1]]),
+		                    closest_on_segment(points, [ids[0], ids[2]]),
+		                    closest_on_segment(points, [ids[1], ids[2]])]);
+	}
+	let (v, w) = (vb / sum, vc / sum);
+	Feature {
+		indices: ids,
+		weights: [T::ONE - v - w, v, w],
+		len: 3,
+	}
+}
+
+// The closest point to the origin on the tetrahedron, `None` if the
+// origin is inside
+fn closest_on_tetrahedron<T: Float>(points: &[Vector3<T>; 4]) -> Option<Feature<T>> {
+	let faces = [([0, 1, 2], 3), ([0, 1, 3], 2), ([0, 2, 3], 1), ([1, 2, 3], 0)];
+	let mut candidates = Vec::with_capacity(4);
+	for &(ids, opposite) in &faces {
+		let a = points[ids[0]];
+		let normal = (points[ids[1]] - a).cross(points[ids[2]] - a);
+		// Faces of a flat tetrahedron count as facing the origin, the
+		// union of the four triangles then covers it
+		if -normal.dot(a) * normal.dot(points[opposite] - a) <= T::ZERO {
+			candidates.push(closest_on_triangle(points, ids));
+		}
+	}
+	if candidates.is_empty() {
+		None
+	} else {
+		Some(closest_of(points, &candidates))
+	}
+}
+
+// The candidate whose point is closest to the origin
+fn closest_of<T: Float>(points: &[Vector3<T>; 4], candidates: &[Feature<T>]) -> Feature<T> {
+	let mut best = candidates[0];
+	let mut best_distance = best.point(points).length_squared();
+	for candidate in &candidates[1..] {
+		let distance = candidate.point(points).length_squared();
+		if distance < best_distance {
+			best = *candidate;
+			best_distance = distance;
+		}
+	}
+	best
+}
+
+// Run the distance variant of GJK, which walks the simplex towards the
+// point of the Minkowski difference closest to the origin until it stops
+// getting closer. Returns the squared distance and the final simplex, or
+// `None` when the hulls touch or intersect.
+fn descend<T: Float>(hull1: &[Vector3<T>], hull2: &[Vector3<T>]) -> Option<(T, Simplex<T>)> {
+	descend_until(hull1, hull2, |_, _, _| false).ok()
+}
+
+// How much closer a support point must bring the simplex, relative to the
+// squared distance, for the descent to go on
+fn tolerance<T: Float>() -> T {
+	T::EPSILON * T::from_f32(100.0)
+}
+
+// Like `descend`, but also stops once `stop` returns `true` for the simplex,
+// its point closest to the origin and the new support point, before the
+// support point is added. Hulls that touch or intersect give the simplex at
+// or around the origin as the error.
+fn descend_until<T, F>(hull1: &[Vector3<T>],
+                       hull2: &[Vector3<T>],
+                       mut stop: F)
+                       -> Result<(T, Simplex<T>), Simplex<T>>
+	where T: Float,
+	      F: FnMut(&Simplex<T>, Vector3<T>, Vector3<T>) -> bool
+{
+	debug_assert![hull1.iter().chain(hull2).all(Vector3::is_finite),
+	              "gjk_distance called with a non-finite point in a hull"];
+	debug_assert![!either_empty(hull1, hull2), "the descent needs points in both hulls"];
+	let tolerance = tolerance::<T>();
+	let at = |hull: &[Vector3<T>], direction| farthest(hull, direction).unwrap_or(Vector3::ZERO);
+	let mut simplex = Simplex::new(at(hull1, Vector3::ONE), at(hull2, -Vector3::ONE));
+	let mut closest = simplex.points[0];
+	let mut distance = closest.length_squared();
+	for _ in 0..MAX_ITERATIONS {
+		if distance <= T::EPSILON * T::EPSILON * simplex.max_length_squared() {
+			return Err(simplex);
+		}
+		let (on1, on2) = (at(hull1, -closest), at(hull2, closest));
+		let point = on1 - on2;
+		if stop(&simplex, closest, point) {
+			break;
+		}
+		// Stop once the support point cannot bring us meaningfully closer
+		if distance - closest.dot(point) <= tolerance * distance || simplex.contains(point) {
+			break;
+		}
+		let mut next = simplex;
+		next.push(on1, on2);
+		let candidate = match next.reduce() {
+			Some(candidate) => candidate,
+			None => return Err(next),
+		};
+		let candidate_distance = candidate.length_squared();
+		if candidate_distance >= distance {
+			// Rounding has stalled the descent
+			break;
+		}
+		closest = candidate;
+		simplex = next;
+		distance = candidate_distance;
+	}
+	Ok((distance, simplex))
+}
+
+/// The squared minimum distance between two convex hulls
+///
+/// Like `gjk_distance`, but avoids the square root.
+pub fn gjk_distance_squared<T: Float>(hull1: &[Vector3<T>], hull2: &[Vector3<T>]) -> T {
+	if either_empty(hull1, hull2) {
+		return T::INFINITY;
+	}
+	descend(hull1, hull2).map_or(T::ZERO, |(distance, _)| distance)
+}
+
+/// The minimum distance between two convex hulls
+///
+/// Runs the distance variant of GJK, which walks the simplex towards the
+/// point of the Minkowski difference closest to the origin until it stops
+/// getting closer. Returns `0.0` when the hulls touch or intersect, like
+/// `bgjk` returning `true`, and infinity when either hull has no points,
+/// which intersects nothing.
+///
+/// ```
+/// use bgjk::{gjk_distance, Vec3};
+///
+/// let hull1 = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// let hull2 = [Vec3::new(3.0, 0.0, 0.0), Vec3::new(4.0, 0.0, 0.0), Vec3::new(3.0, 1.0, 0.0)];
+/// assert_eq![gjk_distance(&hull1, &hull2), 2.0];
+/// ```
+pub fn gjk_distance<T: Float>(hull1: &[Vector3<T>], hull2: &[Vector3<T>]) -> T {
+	match gjk_query(hull1, hull2) {
+		GjkResult::Separated { distance, .. } => distance,
+		GjkResult::Intersecting { .. } => T::ZERO,
+		GjkResult::Empty => T::INFINITY,
+	}
+}
+
+/// What `gjk_query` found out about two convex hulls
+#[derive(Clone, Copy, Debug)]
+pub enum GjkResult<T = f32> {
+	/// The hulls touch or intersect
+	Intersecting {
+		/// The simplex the search ended with, around the origin of the
+		/// Minkowski difference or on it, see `bgjk_simplex`. Its direction
+		/// is zero.
+		simplex: GjkSimplex<T>,
+	},
+	/// The hulls are apart
+	Separated {
+		/// The distance between the hulls, as `gjk_distance` gives it
+		distance: T,
+		/// The point of the first hull closest to the second
+		witness_a: Vector3<T>,
+		/// The point of the second hull closest to the first
+		witness_b: Vector3<T>,
+		/// The unit direction from the first hull towards the second, along
+		/// which they are `distance` apart
+		axis: Vector3<T>,
+	},
+	/// Either hull has no points, so there is nothing to intersect or be
+	/// closest, see `EmptyHull`
+	Empty,
+}
+
+/// Whether two convex hulls intersect, and how far apart and where they are
+/// closest if not, in one search
+///
+/// Runs the descent of `gjk_distance` once and reads off everything it
+/// found, which `gjk_distance` and `closest_points` are built on. The
+/// witnesses are `closest_points`, and `witness_b - witness_a` is `axis`
+/// times `distance` up to rounding. Hulls touching within rounding of the
+/// descent count as intersecting, so `Intersecting` is where `bgjk` gives
+/// `true` except for hulls within about an epsilon of touching. An empty
+/// hull gives `Empty`, where `bgjk` gives `false`. `bgjk` remains the
+/// quicker test where only the answer is needed.
+///
+/// ```
+/// use bgjk::{gjk_query, GjkResult, Vec3};
+///
+/// let segment = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0)];
+/// let point = [Vec3::new(1.0, 3.0, 0.0)];
+/// match gjk_query(&segment, &point) {
+///     GjkResult::Separated { distance, witness_a, witness_b, axis } => {
+///         assert_eq![(distance, witness_a, witness_b), (3.0, Vec3::new(1.0, 0.0, 0.0), point[0])];
+///         assert_eq![axis, Vec3::Y];
+///     }
+///     GjkResult::Intersecting { .. } | GjkResult::Empty => unreachable!(),
+/// }
+/// ```
+pub fn gjk_query<T: Float>(hull1: &[Vector3<T>], hull2: &[Vector3<T>]) -> GjkResult<T> {
+	if either_empty(hull1, hull2) {
+		return GjkResult::Empty;
+	}
+	match descend_until(hull1, hull2, |_, _, _| false) {
+		Ok((squared, simplex)) => {
+			let distance = squared.sqrt();
+			let (witness_a, witness_b) = simplex.witnesses();
+			let closest = (0..simplex.len).fold(Vector3::ZERO, |sum, i| {
+				sum + simplex.points[i] * simplex.weights[i]
+			});
+			GjkResult::Separated {
+				distance,
+				witness_a,
+				witness_b,
+				axis: -closest / distance,
+			}
+		}
+		Err(simplex) => {
+			// The corners in the order found, the last first
+			let mut points = [Vector3::ZERO; 4];
+			let mut vertices = [(0, 0); 4];
+			for (i, corner) in (0..simplex.len).rev().enumerate() {
+				points[i] = simplex.points[corner];
+				vertices[i] = simplex.vertex(hull1, hull2, corner);
+			}
+			GjkResult::Intersecting {
+				simplex: GjkSimplex {
+					points,
+					vertices,
+					len: simplex.len,
+					direction: Vector3::ZERO,
+				},
+			}
+		}
+	}
+}
+
+/// The distance between two convex hulls where they are apart, and minus
+/// their penetration depth where they overlap
+///
+/// One value for both sides of a contact, going through `0.0` where the
+/// hulls touch: the gap shrinks to nothing as they close in, and the depth
+/// grows from nothing as they sink into each other. Each side is only as
+/// exact as `gjk_distance` and `penetration` are, so near touching the
+/// value can be off by about a hundred machine epsilons of the size of the
+/// hulls and their offset, but never jumps. Where either hull has no
+/// points it is infinity, like `gjk_distance`.
+///
+/// The distance is searched for first, and where it finds the hulls to
+/// overlap the polytope algorithm of `penetration` grows from the simplex
+/// that search ended with, instead of from a search of its own.
+///
+/// ```
+/// use bgjk::{signed_distance, Vec3};
+///
+/// let cube = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0),
+///             Vec3::new(1.0, 1.0, 0.0), Vec3::new(0.0, 0.0, 1.0), Vec3::new(1.0, 0.0, 1.0),
+///             Vec3::new(0.0, 1.0, 1.0), Vec3::new(1.0, 1.0, 1.0)];
+/// let apart: Vec<Vec3> = cube.iter().map(|v| *v + Vec3::new(0.25, 0.0, 3.0)).collect();
+/// assert_eq![signed_distance(&cube, &apart), 2.0];
+/// let sunk: Vec<Vec3> = cube.iter().map(|v| *v + Vec3::new(0.25, 0.0, 0.75)).collect();
+/// assert_eq![signed_distance(&cube, &sunk), -0.25];
+/// ```
+pub fn signed_distance<T: Float>(hull1: &[Vector3<T>], hull2: &[Vector3<T>]) -> T {
+	if either_empty(hull1, hull2) {
+		return T::INFINITY;
+	}
+	match descend_until(hull1, hull2, |_, _, _| false) {
+		Ok((distance, _)) => distance.sqrt(),
+		Err(simplex) => {
+			let minkowski = |direction| support(hull1, hull2, direction);
+			-expand(simplex.corners(), minkowski).depth
+		}
+	}
+}
+
+/// Whether two convex hulls are at most `range` apart
+///
+/// Always agrees with `gjk_distance(hull1, hull2) <= range`, so a `range` of
+/// zero is like `bgjk`, but stops as soon as the answer is settled. The
+/// distance only ever shrinks as the search goes on, so once it is within
+/// `range` the hulls are. Each support point also gives a lower bound on the
+/// distance, its projection onto the direction the search looked in, and
+/// once that clearly exceeds `range` the hulls are farther apart. Hulls that
+/// are nowhere near `range` apart thus take only an iteration or two.
+///
+/// ```
+/// use bgjk::{within_distance, Vec3};
+///
+/// let hull1 = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// let hull2 = [Vec3::new(3.0, 0.0, 0.0), Vec3::new(4.0, 0.0, 0.0), Vec3::new(3.0, 1.0, 0.0)];
+/// assert![within_distance(&hull1, &hull2, 2.5)];
+/// assert![within_distance(&hull1, &hull2, 2.0)];
+/// assert![!within_distance(&hull1, &hull2, 1.5)];
+/// ```
+pub fn within_distance<T: Float>(hull1: &[Vector3<T>], hull2: &[Vector3<T>], range: T) -> bool {
+	reaches(hull1, hull2, range).0
+}
+
+// `within_distance`, also counting the iterations of the descent
+fn reaches<T: Float>(hull1: &[Vector3<T>], hull2: &[Vector3<T>], range: T) -> (bool, u32) {
+	if range.is_nan() || range < T::ZERO || either_empty(hull1, hull2) {
+		return (false, 0);
+	}
+	let (mut beyond, mut iterations) = (false, 0);
+	let descent = descend_until(hull1, hull2, |simplex, closest, point| {
+		iterations += 1;
+		let distance = closest.length();
+		if distance <= range {
+			return true;
+		}
+		// Only an excess over the rounding of the descent settles it, closer
+		// calls are left to the full descent
+		let slack = tolerance::<T>() * simplex.max_length_squared().sqrt();
+		beyond = closest.dot(point) > (range + slack) * distance;
+		beyond
+	});
+	match descent {
+		Err(_) => (true, iterations),
+		Ok(_) if beyond => (false, iterations),
+		Ok((distance, _)) => (distance.sqrt() <= range, iterations),
+	}
+}
+
+/// The closest pair of points of two disjoint convex hulls
+///
+/// Returns `(on1, on2)` with `on1` in the first hull and `on2` in the second
+/// such that `on1.distance(on2)` is `gjk_distance(hull1, hull2)`. The points
+/// need not be vertices, e.g. for two parallel faces they can lie anywhere
+/// on the facing faces. Returns `None` when the hulls touch or intersect,
+/// where `gjk_distance` is `0.0` and there is no unique pair, and when
+/// either hull has no points to pair.
+///
+/// ```
+/// use bgjk::{closest_points, Vec3};
+///
+/// let segment = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0)];
+/// let point = [Vec3::new(1.0, 3.0, 0.0)];
+/// assert_eq![closest_points(&segment, &point), Some((Vec3::new(1.0, 0.0, 0.0), point[0]))];
+/// ```
+pub fn closest_points<T: Float>(hull1: &[Vector3<T>],
+                                hull2: &[Vector3<T>])
+                                -> Option<(Vector3<T>, Vector3<T>)> {
+	match gjk_query(hull1, hull2) {
+		GjkResult::Separated { witness_a, witness_b, .. } => Some((witness_a, witness_b)),
+		GjkResult::Intersecting { .. } | GjkResult::Empty => None,
+	}
+}
+
+/// The feature of the Minkowski difference of two disjoint hulls closest to
+/// the origin, see `closest_feature`
+#[derive(Clone, Copy, Debug)]
+pub struct ClosestFeature<T = f32> {
+	/// The corners of the feature in the Minkowski difference `hull1 -
+	/// hull2`, of which the first `count` are in use
+	pub points: [Vector3<T>; 4],
+	/// For each corner the indices of the points of the first and the second
+	/// hull it is the difference of, the first of equal points
+	pub vertices: [(usize, usize); 4],
+	/// The barycentric weights of the point of the feature closest to the
+	/// origin, none negative and summing to one, and zero past `count`
+	pub weights: [T; 4],
+	/// How many corners the feature has: one for a vertex, two for an edge
+	/// and three for a face
+	pub count: u8,
+}
+
+/// The vertex, edge or face of the Minkowski difference of two disjoint
+/// convex hulls closest to the origin, with the weights of the closest
+/// point, `None` where the hulls touch or intersect or either is empty
+///
+/// The simplex the descent of `closest_points` ends with, reduced to the
+/// feature the closest point is in. Blending anything attached to the hull
+/// vertices of the corners by the weights gives its value at the closest
+/// points, the witnesses of `closest_points` being the blend of the
+/// positions themselves.
+///
+/// ```
+/// use bgjk::{closest_feature, Vec3};
+///
+/// let segment = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(4.0, 0.0, 0.0)];
+/// let point = [Vec3::new(1.0, 2.0, 0.0)];
+/// let feature = closest_feature(&segment, &point).unwrap();
+/// assert_eq![feature.count, 2];
+/// let mut weights = [0.0; 2];
+/// for (&(i, _), &weight) in feature.vertices.iter().zip(&feature.weights).take(2) {
+///     weights[i] = weight;
+/// }
+/// assert_eq![weights, [0.75, 0.25]];
+/// ```
+pub fn closest_feature<T: Float>(hull1: &[Vector3<T>],
+                                 hull2: &[Vector3<T>]) -> Option<ClosestFeature<T>> {
+	if either_empty(hull1, hull2) {
+		return None;
+	}
+	let (_, simplex) = descend(hull1, hull2)?;
+	let mut feature = ClosestFeature {
+		points: [Vector3::ZERO; 4],
+		vertices: [(0, 0); 4],
+		weights: [T::ZERO; 4],
+		count: simplex.len as u8,
+	};
+	for corner in 0..simplex.len {
+		feature.points[corner] = simplex.points[corner];
+		feature.vertices[corner] = simplex.vertex(hull1, hull2, corner);
+		feature.weights[corner] = simplex.weights[corner].max(T::ZERO);
+	}
+	// Rounding can leave a weight a hair below zero or the sum a hair off
+	let sum = feature.weights.iter().fold(T::ZERO, |sum, &weight| sum + weight);
+	for weight in &mut feature.weights {
+		*weight = *weight / sum;
+	}
+	Some(feature)
+}
+
+/// The point of a convex hull closest to `point`, `point` itself where it
+/// is inside the hull or on it, and `None` for a hull with no points
+///
+/// Runs the descent of `closest_points` on the hull and the point alone,
+/// and weights the hull vertices of the final simplex like its closest
+/// point, so the answer is on the vertex, edge or face nearest `point`
+/// rather than only at a vertex. The foot of the perpendicular onto a
+/// face, the nearest point of an edge, or a corner, up to rounding.
+///
+/// ```
+/// use bgjk::{closest_point_on_hull, Vec3};
+///
+/// let triangle = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0), Vec3::new(0.0, 2.0, 0.0)];
+/// let (above, on) = (Vec3::new(0.5, 0.5, 3.0), Vec3::new(0.5, 0.5, 0.0));
+/// assert_eq![closest_point_on_hull(&triangle, above), Some(on)];
+/// assert_eq![closest_point_on_hull(&triangle, on), Some(on)];
+/// assert_eq![closest_point_on_hull(&[], above), None];
+/// ```
+pub fn closest_point_on_hull<T: Float>(hull: &[Vector3<T>],
+                                       point: Vector3<T>)
+                                       -> Option<Vector3<T>> {
+	if hull.is_empty() {
+		return None;
+	}
+	Some(descend(hull, &[point]).map_or(point, |(_, simplex)| simplex.witnesses().0))
+}
+
+/// A point inside or on both of two intersecting convex hulls, `None` where
+/// they are apart
+///
+/// The simplex `bgjk` ends with holds the origin, each of its corners the
+/// difference of a point of either hull. The weights that make the origin
+/// out of the corners make the same point out of the hull points on either
+/// side, which is therefore in both hulls. Rounding can leave the two a
+/// hair apart, so their midpoint is taken, and can put a point on the
+/// boundary of a hull a hair outside it, in which case the search is run
+/// again on the hulls scaled down slightly, like for `contact_normal`, for
+/// a point further in. Where the hulls only touch, the point is where they
+/// do, such as the shared corner of two cubes, up to rounding.
+///
+/// ```
+/// use bgjk::{common_point, point_in_hull, Vec3};
+///
+/// let hull1 = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0), Vec3::new(0.0, 2.0, 0.0)];
+/// let hull2 = [Vec3::new(1.0, 1.0, 0.0), Vec3::new(3.0, 1.0, 0.0), Vec3::new(1.0, 3.0, 0.0)];
+/// let point = common_point(&hull1, &hull2).unwrap();
+/// assert_eq![point, Vec3::new(1.0, 1.0, 0.0)];
+/// assert![point_in_hull(point, &hull1) && point_in_hull(point, &hull2)];
+/// let far: Vec<Vec3> = hull2.iter().map(|v| *v + Vec3::new(0.0, 0.0, 1.0)).collect();
+/// assert_eq![common_point(&hull1, &far), None];
+/// ```
+pub fn common_point<T: Float>(hull1: &[Vector3<T>], hull2: &[Vector3<T>]) -> Option<Vector3<T>> {
+	let point = common_from(hull1, hull2)?;
+	let inside = |point| point_in_hull(point, hull1) && point_in_hull(point, hull2);
+	if inside(point) {
+		return Some(point);
+	}
+	// Rounding took the point off the boundary of either hull. Where the
+	// hulls overlap by more than a sliver, the hulls scaled down like for
+	// `contact_normal` still intersect, at a point well inside both.
+	let (inner1, inner2) = shrunk(hull1, hull2);
+	match common_from(&inner1, &inner2) {
+		Some(inner) if inside(inner) => Some(inner),
+		_ => Some(point),
+	}
+}
+
+// The point of `common_point` mapped back from the simplex of `bgjk`
+fn common_from<T: Float>(hull1: &[Vector3<T>], hull2: &[Vector3<T>]) -> Option<Vector3<T>> {
+	let (hit, found) = bgjk_simplex(hull1, hull2);
+	if !hit {
+		return None;
+	}
+	let at = |hull: &[Vector3<T>], index: usize| hull.get(index).cloned().unwrap_or(Vector3::ZERO);
+	let (&(i, j), rest) = found.vertices().split_first()?;
+	let mut simplex = Simplex::new(at(hull1, i), at(hull2, j));
+	// The simplex of a search that ended early repeats corners, and the
+	// region tests of `reduce` take an edge between equal corners for a
+	// vertex, so each corner is kept once
+	for (k, &(i, j)) in rest.iter().enumerate() {
+		if !found.vertices()[..k + 1].contains(&(i, j)) {
+			simplex.push(at(hull1, i), at(hull2, j));
+		}
+	}
+	let (on1, on2) = if simplex.reduce().is_some() {
+		simplex.witnesses()
+	} else {
+		// The origin is strictly inside the tetrahedron: each weight is the
+		// share of the volume of the one made with the origin for its corner
+		let volume = |p: [Vector3<T>; 4]| Vector3::triple(p[1] - p[0], p[2] - p[0], p[3] - p[0]);
+		let whole = volume(simplex.points);
+		(0..4).fold((Vector3::ZERO, Vector3::ZERO), |(on1, on2), corner| {
+			let mut points = simplex.points;
+			points[corner] = Vector3::ZERO;
+			let weight = volume(points) / whole;
+			(on1 + simplex.on1[corner] * weight, on2 + simplex.on2[corner] * weight)
+		})
+	};
+	Some(if on1 == on2 { on1 } else { (on1 + on2) / T::from_f32(2.0) })
+}
+
+// How far `contact_normal` pulls the points of both hulls towards the
+// centers of their hulls, as a fraction of the extent of the smaller hull
+const SHRINK: f32 = 1e-3;
+
+// Both hulls scaled down about the averages of their points, each by as much
+// as moves its farthest point in by the same small distance. The distance
+// follows the smaller hull, so where a small hull touches a large one the
+// large one does not pull its features out from under the small one.
+fn shrunk<T: Float>(hull1: &[Vector3<T>],
+                    hull2: &[Vector3<T>])
+                    -> (Vec<Vector3<T>>, Vec<Vector3<T>>) {
+	if hull1.is_empty() || hull2.is_empty() {
+		return (hull1.to_vec(), hull2.to_vec());
+	}
+	let around = |hull: &[Vector3<T>]| {
+		let center = hull.iter().sum::<Vector3<T>>() / T::from_f32(hull.len() as f32);
+		(center, hull.iter().fold(T::ZERO, |max, point| max.max(point.distance(center))))
+	};
+	let (around1, around2) = (around(hull1), around(hull2));
+	// A point has no size to follow, the other hull then gives the gap alone
+	let smaller = if around1.1 > T::ZERO && around2.1 > T::ZERO {
+		around1.1.min(around2.1)
+	} else {
+		around1.1.max(around2.1)
+	};
+	let inset = smaller * T::from_f32(SHRINK);
+	let scale = |hull: &[Vector3<T>], (center, extent): (Vector3<T>, T)| {
+		let keep = if extent > T::ZERO { T::ONE - inset / extent } else { T::ONE };
+		hull.iter().map(|point| center + (*point - center) * keep).collect()
+	};
+	(scale(hull1, around1), scale(hull2, around2))
+}
+
+/// The unit normal of a touching or very shallow contact
+///
+/// Points from the first hull towards the second, like the normal of
+/// `penetration`, which degenerates for hulls that touch without
+/// overlapping. Instead both hulls are scaled down about their centers,
+/// each moving its farthest point in by a thousandth of the size of the
+/// smaller hull, which parts them by a small gap, and the normal is taken
+/// from the closest features of the final simplex across that gap. As the
+/// gap follows the smaller hull, a small hull on a large one keeps the
+/// features it touches however large the other is. Touching faces give the
+/// face normal, edges and vertices give a direction between them, such as
+/// the diagonal for two cubes meeting at a corner.
+///
+/// Returns `None` when the hulls are apart, when they overlap by more than
+/// the scaling removes (use `penetration` there), or when the contact has no
+/// direction, as for two equal points.
+///
+/// ```
+/// use bgjk::{contact_normal, Vec3};
+///
+/// let square = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0),
+///               Vec3::new(1.0, 1.0, 0.0)];
+/// let beside: Vec<Vec3> = square.iter().map(|v| *v + Vec3::X).collect();
+/// assert![contact_normal(&square, &beside).unwrap().approx_eq(Vec3::X, 1e-6)];
+/// ```
+pub fn contact_normal<T: Float>(hull1: &[Vector3<T>], hull2: &[Vector3<T>]) -> Option<Vector3<T>> {
+	if gjk_distance_squared(hull1, hull2) > T::ZERO {
+		return None;
+	}
+	let (inner1, inner2) = shrunk(hull1, hull2);
+	let (_, simplex) = descend(&inner1, &inner2)?;
+	simplex.normal()
+}
+
+#[cfg(test)]
+mod tests {
+
+	use std::f32;
+	use std::f32::consts::PI;
+	use super::super::{DVec3, GjkResult, Vec3, bgjk, closest_feature, closest_point_on_hull,
+	                   closest_points, common_point, contact_normal, gjk_distance,
+	                   gjk_distance_squared, gjk_query, penetration, point_in_hull, signed_distance,
+	                   within_distance};
+	use super::{descend_until, reaches};
+	use super::super::test_util::{cases, cube_at, shifted, unit_cube};
+	static EPS: f32 = f32::EPSILON;
+
+	fn circles(offset: f32) -> (Vec<Vec3>, Vec<Vec3>) {
+		let units = 100;
+		(0..units)
+			.map(|i| {
+				let radian = i as f32 / units as f32 * 2.0 * PI;
+				(Vec3(radian.cos(), radian.sin(), 0.0),
+				 Vec3(radian.cos() + offset, radian.sin(), 0.0))
+			})
+			.unzip()
+	}
+
+	#[test]
+	fn point_point() {
+		let a = pts![(1.0, 2.0, 3.0)];
+		let b = pts![(4.0, 6.0, 3.0)];
+		assert_eq![gjk_distance(&a, &b), 5.0];
+		assert_eq![gjk_distance_squared(&a, &b), 25.0];
+		assert_eq![gjk_distance(&a, &a), 0.0];
+	}
+
+	#[test]
+	fn point_segment() {
+		let segment = pts![(0.0, 0.0, 0.0), (2.0, 0.0, 0.0)];
+		assert_eq![gjk_distance(&segment, &pts![(1.0, 3.0, 0.0)]), 3.0];
+		assert_eq![gjk_distance(&segment, &pts![(5.0, 0.0, 4.0)]), 5.0];
+	}
+
+	#[test]
+	fn touching_squares() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let shape2 = pts![(1.0, 0.0, 0.0), (2.0, 0.0, 0.0), (1.0, 1.0, 0.0), (2.0, 1.0, 0.0)];
+		assert_eq![gjk_distance(&shape1, &shape2), 0.0];
+		let shape2 = pts![(1.0, 1.0, 0.0), (2.0, 1.0, 0.0), (1.0, 2.0, 0.0), (2.0, 2.0, 0.0)];
+		assert_eq![gjk_distance(&shape1, &shape2), 0.0];
+	}
+
+	#[test]
+	fn cubes() {
+		let cube = unit_cube();
+		assert_eq![gjk_distance(&cube, &shifted(&cube, Vec3(3.0, 0.0, 0.0))), 2.0];
+		assert_eq![gjk_distance(&cube, &shifted(&cube, Vec3(0.0, -1.5, 0.0))), 0.5];
+		let diagonal = gjk_distance(&cube, &shifted(&cube, Vec3(2.0, 2.0, 2.0)));
+		assert![(diagonal - 3f32.sqrt()).abs() <= 4.0 * EPS];
+		assert_eq![gjk_distance(&cube, &shifted(&cube, Vec3(0.5, 0.5, 0.5))), 0.0];
+		assert_eq![gjk_distance(&cube, &shifted(&cube, Vec3(1.0, 0.0, 0.0))), 0.0];
+	}
+
+	#[test]
+	fn matches_bgjk() {
+		let cube = unit_cube();
+		for x in -8..9 {
+			for y in -8..9 {
+				let by = Vec3(x as f32 * 0.25, y as f32 * 0.25, 0.3);
+				let other: Vec<Vec3> = cube.iter().map(|v| *v * 0.5 + by).collect();
+				assert_eq![gjk_distance(&cube, &other) == 0.0, bgjk(&cube, &other)];
+				assert_eq![within_distance(&cube, &other, 0.0), bgjk(&cube, &other)];
+			}
+		}
+	}
+
+	#[test]
+	fn closest_cube_faces() {
+		let cube = unit_cube();
+		let beside: Vec<Vec3> = cube.iter().map(|v| *v + Vec3(2.5, 0.25, -0.5)).collect();
+		let (on1, on2) = closest_points(&cube, &beside).unwrap();
+		assert![(on1.distance(on2) - 1.5).abs() <= 4.0 * EPS];
+		// Any pair on the facing faces x = 1 and x = 2.5 is a valid answer
+		assert_eq![(on1.0, on2.0), (1.0, 2.5)];
+		assert![on1.approx_eq(on2 - Vec3(1.5, 0.0, 0.0), 4.0 * EPS)];
+		assert![on1.1 >= 0.25 && on1.1 <= 1.0 && on1.2 >= 0.0 && on1.2 <= 0.5];
+		assert_eq![closest_points(&cube, &cube), None];
+	}
+
+	#[test]
+	fn closest_point_segment() {
+		let segment = pts![(0.0, 0.0, 0.0), (2.0, 0.0, 0.0)];
+		let point = pts![(0.5, 1.0, 1.0)];
+		assert_eq![closest_points(&segment, &point), Some((Vec3(0.5, 0.0, 0.0), point[0]))];
+		assert_eq![closest_points(&point, &segment), Some((point[0], Vec3(0.5, 0.0, 0.0)))];
+		let beyond = pts![(3.0, 1.0, 0.0)];
+		assert_eq![closest_points(&segment, &beyond), Some((segment[1], beyond[0]))];
+	}
+
+	#[test]
+	fn closest_skew_segments() {
+		let along_x = pts![(-1.0, 0.0, 0.0), (3.0, 0.0, 0.0)];
+		let along_y = pts![(1.0, -2.0, 2.0), (1.0, 2.0, 2.0)];
+		let (on1, on2) = closest_points(&along_x, &along_y).unwrap();
+		assert![on1.approx_eq(Vec3(1.0, 0.0, 0.0), 4.0 * EPS)];
+		assert![on2.approx_eq(Vec3(1.0, 0.0, 2.0), 4.0 * EPS)];
+		// Skewed in every axis, the witnesses span the common perpendicular
+		let a = pts![(0.0, 0.0, 0.0), (1.0, 2.0, 0.5)];
+		let b = pts![(2.0, 0.0, 1.0), (-1.0, 1.5, 2.0)];
+		let (on1, on2) = closest_points(&a, &b).unwrap();
+		let gap = on2 - on1;
+		assert![gap.dot(a[1] - a[0]).abs() <= 1e-5 && gap.dot(b[1] - b[0]).abs() <= 1e-5];
+		assert![(gap.length() - gjk_distance(&a, &b)).abs() <= 4.0 * EPS];
+	}
+
+	#[test]
+	fn circles_away() {
+		let (shape1, shape2) = circles(2.0 + 2.0 * EPS);
+		let distance = gjk_distance(&shape1, &shape2);
+		assert![distance > 0.0 && distance <= 4.0 * EPS];
+	}
+
+	#[test]
+	fn circles_overlap() {
+		let (shape1, shape2) = circles(0.5);
+		assert_eq![gjk_distance(&shape1, &shape2), 0.0];
+		let (shape1, shape2) = circles(3.0);
+		assert![(gjk_distance(&shape1, &shape2) - 1.0).abs() <= 4.0 * EPS];
+	}
+
+	#[test]
+	fn double_precision() {
+		let (shape1, shape2): (Vec<DVec3>, Vec<DVec3>) = (0..100)
+			.map(|i| {
+				let radian = i as f64 / 100.0 * 2.0 * ::std::f64::consts::PI;
+				(DVec3::new(radian.cos(), radian.sin(), 0.0),
+				 DVec3::new(radian.cos() + 2.0 + 1e-9, radian.sin(), 0.0))
+			})
+			.unzip();
+		assert![(gjk_distance(&shape1, &shape2) - 1e-9).abs() <= 1e-12];
+	}
+
+	#[test]
+	fn side_by_side_squares() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let shape2 = pts![(1.0, 0.0, 0.0), (2.0, 0.0, 0.0), (1.0, 1.0, 0.0), (2.0, 1.0, 0.0)];
+		assert![contact_normal(&shape1, &shape2).unwrap().approx_eq(Vec3::X, 1e-5)];
+		assert![contact_normal(&shape2, &shape1).unwrap().approx_eq(-Vec3::X, 1e-5)];
+	}
+
+	#[test]
+	fn corner_touching_cubes() {
+		let cube = unit_cube();
+		let diagonal = Vec3::ONE.normalized();
+		let normal = contact_normal(&cube, &shifted(&cube, Vec3::ONE)).unwrap();
+		assert![normal.approx_eq(diagonal, 1e-5)];
+	}
+
+	#[test]
+	fn stacked_boxes() {
+		let cube = unit_cube();
+		let normal = contact_normal(&cube, &shifted(&cube, Vec3(0.0, 0.0, 1.0))).unwrap();
+		assert![normal.approx_eq(Vec3::Z, 1e-5)];
+		let normal = contact_normal(&cube, &shifted(&cube, Vec3(0.25, -1.0, 0.5))).unwrap();
+		assert![normal.approx_eq(-Vec3::Y, 1e-5)];
+		// Far off center on a large ground slab
+		let slab: Vec<Vec3> = cube.iter().map(|v| v.mul_component(Vec3(20.0, 20.0, 1.0))).collect();
+		let ground = shifted(&slab, Vec3(-10.0, -10.0, -1.0));
+		let normal = contact_normal(&ground, &shifted(&cube, Vec3(6.0, 8.5, 0.0))).unwrap();
+		assert![normal.approx_eq(Vec3::Z, 1e-5)];
+	}
+
+	#[test]
+	fn small_box_by_the_edge_of_a_large_one() {
+		// Half over the edge of the top face, which scaling the large box by
+		// a fraction of its size would pull in from under the small one
+		let large: Vec<Vec3> = unit_cube().iter().map(|v| *v * 1000.0).collect();
+		let small = shifted(&unit_cube(), Vec3(999.5, 500.0, 1000.0));
+		assert![contact_normal(&large, &small).unwrap().approx_eq(Vec3::Z, 1e-5)];
+		assert![contact_normal(&small, &large).unwrap().approx_eq(-Vec3::Z, 1e-5)];
+		let point = pts![(0.25, 0.5, 1.0)];
+		assert![contact_normal(&unit_cube(), &point).unwrap().approx_eq(Vec3::Z, 1e-5)];
+	}
+
+	#[test]
+	fn shallow_overlap() {
+		let cube = unit_cube();
+		let normal = contact_normal(&cube, &shifted(&cube, Vec3(0.5, 0.25, 1.0 - 1e-5))).unwrap();
+		assert![normal.approx_eq(Vec3::Z, 1e-5)];
+		assert_eq![contact_normal(&cube, &shifted(&cube, Vec3(0.5, 0.25, 0.5))), None];
+	}
+
+	#[test]
+	fn apart() {
+		let cube = unit_cube();
+		assert_eq![contact_normal(&cube, &shifted(&cube, Vec3(0.0, 0.0, 1.01))), None];
+		assert_eq![contact_normal(&pts![(1.0, 2.0, 3.0)], &pts![(1.0, 2.0, 3.0)]), None];
+	}
+
+
+	// Hull pairs apart, touching and overlapping, flat and solid
+	fn pairs() -> Vec<(Vec<Vec3>, Vec<Vec3>)> {
+		let cube = unit_cube();
+		let mut pairs = Vec::new();
+		for &by in &[Vec3(3.0, 0.0, 0.0),
+		             Vec3(0.0, -1.5, 0.0),
+		             Vec3(2.0, 2.0, 2.0),
+		             Vec3(0.5, 0.5, 0.5),
+		             Vec3(1.0, 0.0, 0.0),
+		             Vec3(2.5, 0.25, -0.5),
+		             Vec3(-4.0, 7.0, 1.0)] {
+			pairs.push((cube.to_vec(), shifted(&cube, by)));
+		}
+		for &offset in &[0.5, 2.0, 2.0 + 2.0 * EPS, 3.0, 10.0] {
+			pairs.push(circles(offset));
+		}
+		pairs.push((pts![(-1.0, 0.0, 0.0), (3.0, 0.0, 0.0)].to_vec(),
+		            pts![(1.0, -2.0, 2.0), (1.0, 2.0, 2.0)].to_vec()));
+		pairs.push((pts![(0.0, 0.0, 0.0), (2.0, 0.0, 0.0)].to_vec(),
+		            pts![(0.5, 1.0, 1.0)].to_vec()));
+		pairs
+	}
+
+	#[test]
+	fn within_matches_distance() {
+		for (hull1, hull2) in pairs() {
+			let distance = gjk_distance(&hull1, &hull2);
+			let mut ranges = vec![0.0, 1e-6, 0.5, 1.0, 2.0, 5.0, 100.0, -1.0, f32::NAN];
+			ranges.push(f32::INFINITY);
+			for &scale in &[0.5, 0.9, 0.999, 1.0, 1.001, 1.1, 2.0] {
+				ranges.push(distance * scale);
+			}
+			ranges.push(distance - distance * EPS);
+			ranges.push(distance + distance * EPS);
+			let reversed = gjk_distance(&hull2, &hull1);
+			for &range in &ranges {
+				assert_eq![within_distance(&hull1, &hull2, range), distance <= range];
+				assert_eq![within_distance(&hull2, &hull1, range), reversed <= range];
+			}
+		}
+	}
+
+	#[test]
+	fn within_stops_early() {
+		let full = |hull1: &[Vec3], hull2: &[Vec3]| {
+			let mut iterations = 0;
+			let _ = descend_until(hull1, hull2, |_, _, _| {
+				iterations += 1;
+				false
+			});
+			iterations
+		};
+		// Far apart or far within range, nothing but the first step counts
+		let (shape1, shape2) = circles(10.0);
+		assert_eq![reaches(&shape1, &shape2, 1.0), (false, 1)];
+		assert_eq![reaches(&shape1, &shape2, 100.0), (true, 1)];
+		assert![full(&shape1, &shape2) > 1];
+		let cube = unit_cube();
+		for &by in &[Vec3(3.0, 0.0, 0.0), Vec3(-4.0, 7.0, 1.0), Vec3(2.5, 0.25, -0.5)] {
+			let other = shifted(&cube, by);
+			assert_eq![reaches(&cube, &other, 0.1), (false, 1)];
+			assert_eq![reaches(&cube, &other, 100.0), (true, 1)];
+			assert![full(&cube, &other) > 1];
+		}
+		// Close calls take the full descent
+		let (_, iterations) = reaches(&shape1, &shape2, gjk_distance(&shape1, &shape2));
+		assert![iterations <= full(&shape1, &shape2)];
+	}
+
+	#[test]
+	fn closest_point_of_cube() {
+		let cube = unit_cube();
+		let closest = |point: Vec3| closest_point_on_hull(&cube, point).unwrap();
+		// The foot of the perpendicular onto a face
+		assert![closest(Vec3(0.3, 0.6, 2.5)).approx_eq(Vec3(0.3, 0.6, 1.0), 1e-6)];
+		assert![closest(Vec3(-4.0, 0.25, 0.75)).approx_eq(Vec3(0.0, 0.25, 0.75), 1e-6)];
+		// The nearest point of an edge
+		assert![closest(Vec3(2.0, 0.4, -1.0)).approx_eq(Vec3(1.0, 0.4, 0.0), 1e-6)];
+		assert![closest(Vec3(-0.5, 1.5, 0.7)).approx_eq(Vec3(0.0, 1.0, 0.7), 1e-6)];
+		// A corner
+		assert![closest(Vec3(3.0, 2.0, 4.0)).approx_eq(Vec3::ONE, 1e-6)];
+		assert![closest(Vec3(-1.0, -0.1, -2.0)).approx_eq(Vec3::ZERO, 1e-6)];
+		// Inside and on the surface, the point itself
+		for &inside in &[Vec3(0.5, 0.5, 0.5), Vec3(0.1, 0.9, 0.2), Vec3(1.0, 0.5, 0.5), Vec3::ONE] {
+			assert_eq![closest(inside), inside];
+		}
+		for i in 0..100 {
+			let t = i as f32;
+			let point = Vec3((t * 0.37).sin(), (t * 1.91).cos(), (t * 0.73).sin()) * 3.0;
+			let found = closest(point);
+			assert![(found.distance(point) - gjk_distance(&cube, &[point])).abs() <= 1e-5];
+			let clamped = point.max(Vec3::ZERO).min(Vec3::ONE);
+			assert![found.approx_eq(clamped, 1e-5)];
+		}
+	}
+
+	#[test]
+	fn closest_point_of_segment() {
+		let segment = pts![(0.0, 0.0, 0.0), (2.0, 2.0, 0.0)];
+		let closest = |point: Vec3| closest_point_on_hull(&segment, point).unwrap();
+		assert![closest(Vec3(2.0, 0.0, 1.0)).approx_eq(Vec3(1.0, 1.0, 0.0), 1e-6)];
+		assert![closest(Vec3(-1.0, -3.0, 0.0)).approx_eq(Vec3::ZERO, 1e-6)];
+		assert![closest(Vec3(5.0, 4.0, -2.0)).approx_eq(Vec3(2.0, 2.0, 0.0), 1e-6)];
+		assert_eq![closest(Vec3(0.5, 0.5, 0.0)), Vec3(0.5, 0.5, 0.0)];
+		let point = pts![(1.0, -2.0, 3.0)];
+		assert_eq![closest_point_on_hull(&point, Vec3::ZERO), Some(point[0])];
+	}
+
+	#[test]
+	fn signed_through_contact() {
+		// Sliding one cube along x through another, offset in y and z so it
+		// is deepest 0.7 in, along y
+		let cube = unit_cube();
+		let at = |x: f32| signed_distance(&cube, &shifted(&cube, Vec3(x, 0.3, 0.2)));
+		let mut last = f32::NEG_INFINITY;
+		for i in 0..=400 {
+			let x = i as f32 * 0.005;
+			let value = at(x);
+			assert![value >= last - 1e-5];
+			let expected = if x > 1.0 { x - 1.0 } else { -(1.0 - x).min(0.7) };
+			assert![(value - expected).abs() <= 1e-5];
+			last = value;
+		}
+		assert![at(1.0).abs() <= 1e-6];
+		assert![at(0.999) < 0.0 && at(1.001) > 0.0];
+		assert![(at(0.0) + 0.7).abs() <= 1e-6];
+		assert![(at(0.5) + 0.5).abs() <= 1e-6];
+		assert![(at(1.5) - 0.5).abs() <= 1e-6];
+		assert![(at(-3.0) - 2.0).abs() <= 1e-6];
+		for i in 0..100 {
+			let t = i as f32;
+			let by = Vec3((t * 0.37).sin(), (t * 1.91).cos(), (t * 0.73).sin()) * 1.5;
+			let other = shifted(&cube, by);
+			let value = signed_distance(&cube, &other);
+			match penetration(&cube, &other) {
+				Some(contact) => assert![(value + contact.depth).abs() <= 1e-5],
+				None => assert_eq![value, gjk_distance(&cube, &other)],
+			}
+		}
+	}
+
+	// `gjk_query` agreeing with the functions answering each part of it
+	fn consistent(hull1: &[Vec3], hull2: &[Vec3]) {
+		match gjk_query(hull1, hull2) {
+			GjkResult::Separated { distance, witness_a, witness_b, axis } => {
+				assert![!bgjk(hull1, hull2)];
+				assert_eq![distance, gjk_distance(hull1, hull2)];
+				assert_eq![Some((witness_a, witness_b)), closest_points(hull1, hull2)];
+				let across = witness_b - witness_a;
+				let scale = 1.0 + distance;
+				assert![(across.length() - distance).abs() <= 1e-5 * scale];
+				assert![(axis.length() - 1.0).abs() <= 1e-5];
+				assert![across.approx_eq(axis * distance, 1e-5 * scale)];
+			}
+			GjkResult::Intersecting { simplex } => {
+				assert![bgjk(hull1, hull2)];
+				assert_eq![gjk_distance(hull1, hull2), 0.0];
+				assert_eq![closest_points(hull1, hull2), None];
+				assert![!simplex.points().is_empty()];
+				for (point, &(i, j)) in simplex.points().iter().zip(simplex.vertices()) {
+					assert_eq![*point, hull1[i] - hull2[j]];
+				}
+			}
+			GjkResult::Empty => {
+				assert![hull1.is_empty() || hull2.is_empty()];
+				assert![!bgjk(hull1, hull2)];
+				assert_eq![gjk_distance(hull1, hull2), f32::INFINITY];
+				assert_eq![closest_points(hull1, hull2), None];
+			}
+		}
+	}
+
+	#[test]
+	fn query_consistent() {
+		let cube = unit_cube();
+		let square = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let segment = pts![(-1.0, 0.5, 0.5), (2.0, 0.5, 0.5)];
+		let point = pts![(0.25, 0.5, 0.75)];
+		let (circle1, circle2) = circles(2.5);
+		consistent(&circle1, &circle2);
+		let (circle1, circle2) = circles(1.0);
+		consistent(&circle1, &circle2);
+		let shapes: [&[Vec3]; 4] = [&cube, &square, &segment, &point];
+		let mut hits = 0;
+		for i in 0..200 {
+			let t = i as f32;
+			let by = Vec3((t * 0.37).sin(), (t * 1.91).cos(), (t * 0.73).sin()) * (i % 4) as f32;
+			for first in &shapes {
+				for second in &shapes {
+					consistent(first, &shifted(second, by));
+					hits += bgjk(first, &shifted(second, by)) as usize;
+				}
+			}
+		}
+		assert![hits > 0 && hits < 200 * 16];
+		// Touching along a face, an edge and at a corner
+		for &by in &[Vec3::X, Vec3(1.0, 1.0, 0.0), Vec3::ONE] {
+			consistent(&cube, &shifted(&cube, by));
+		}
+	}
+
+	#[test]
+	fn feature_of_point_and_segment() {
+		let segment = pts![(0.0, 0.0, 0.0), (4.0, 0.0, 0.0)];
+		let point = pts![(3.0, -1.0, 2.0)];
+		let feature = closest_feature(&point, &segment).unwrap();
+		assert_eq![feature.count, 2];
+		assert_eq![(feature.weights[2], feature.weights[3]), (0.0, 0.0)];
+		assert![(feature.weights[0] + feature.weights[1] - 1.0).abs() <= EPS];
+		for corner in 0..2 {
+			let (i, j) = feature.vertices[corner];
+			assert_eq![(i, feature.points[corner]), (0, point[0] - segment[j])];
+			let along = if j == 0 { 0.25 } else { 0.75 };
+			assert![(feature.weights[corner] - along).abs() <= 1e-6];
+		}
+	}
+
+	#[test]
+	fn feature_of_facing_cubes() {
+		let cube = unit_cube();
+		let above = shifted(&cube, Vec3(0.2, 0.3, 2.0));
+		let feature = closest_feature(&cube, &above).unwrap();
+		assert_eq![feature.count, 3];
+		assert![feature.weights[..3].iter().all(|&weight| weight > 0.0)];
+		let sum: f32 = feature.weights.iter().sum();
+		assert![(sum - 1.0).abs() <= 2.0 * EPS];
+		let blend = |points: &dyn Fn(usize) -> Vec3| {
+			(0..3).fold(Vec3::ZERO, |sum, corner| sum + points(corner) * feature.weights[corner])
+		};
+		assert![blend(&|corner| feature.points[corner]).approx_eq(-Vec3::Z, 1e-6)];
+		let on1 = blend(&|corner| cube[feature.vertices[corner].0]);
+		let on2 = blend(&|corner| above[feature.vertices[corner].1]);
+		let (closest1, closest2) = closest_points(&cube, &above).unwrap();
+		assert![on1.approx_eq(closest1, 1e-6) && on2.approx_eq(closest2, 1e-6)];
+		assert![(on1.2 - 1.0).abs() <= 1e-6 && (on2.2 - 2.0).abs() <= 1e-6];
+	}
+
+	#[test]
+	fn feature_of_corners() {
+		let cube = unit_cube();
+		let beyond = shifted(&cube, Vec3(1.5, 2.0, 3.0));
+		let feature = closest_feature(&cube, &beyond).unwrap();
+		assert_eq![feature.count, 1];
+		assert_eq![feature.weights, [1.0, 0.0, 0.0, 0.0]];
+		assert_eq![feature.vertices[0], (7, 0)];
+		assert![closest_feature(&cube, &shifted(&cube, Vec3::ONE)).is_none()];
+		assert![closest_feature(&cube, &shifted(&cube, Vec3::ONE * 0.5)).is_none()];
+	}
+
+	#[test]
+	fn common_point_on_suite() {
+		for (name, hull1, hull2, hit) in cases() {
+			for &(a, b) in &[(&hull1, &hull2), (&hull2, &hull1)] {
+				match common_point(a, b) {
+					Some(point) => {
+						assert![hit, "{}", name];
+						assert![point_in_hull(point, a) && point_in_hull(point, b), "{}", name];
+					}
+					None => assert![!hit, "{}", name],
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn common_point_in_both() {
+		let in_both = |hull1: &[Vec3], hull2: &[Vec3]| {
+			common_point(hull1, hull2).is_some_and(|point| {
+				point_in_hull(point, hull1) && point_in_hull(point, hull2)
+			})
+		};
+		for (hull1, hull2) in pairs() {
+			let expected = bgjk(&hull1, &hull2);
+			assert_eq![common_point(&hull1, &hull2).is_some(), expected];
+			assert_eq![in_both(&hull1, &hull2), expected];
+			assert_eq![in_both(&hull2, &hull1), expected];
+		}
+		let cube = unit_cube();
+		let tilted = pts![(0.1, -0.3, 0.2), (1.3, 0.4, -0.2), (0.2, 1.1, 0.6), (0.6, 0.3, 1.4)];
+		let mut hits = 0;
+		for i in 0..343 {
+			let at = |step: usize| (step % 7) as f32 * 0.35 - 1.05;
+			let by = Vec3(at(i), at(i / 7), at(i / 49));
+			for other in &[shifted(&cube, by), shifted(&tilted, by)] {
+				let expected = bgjk(&cube, other);
+				// Hulls that only just touch, a rounding error into each other
+				let touching = penetration(&cube, other).is_some_and(|contact| contact.depth < 1e-6);
+				assert_eq![common_point(&cube, other).is_some(), expected];
+				assert![in_both(&cube, other) == expected || touching];
+				hits += expected as usize;
+			}
+		}
+		assert![hits > 100 && hits < 686];
+		// Exactly the same hulls, one inside the other, and touching corners
+		assert![in_both(&cube, &cube) && in_both(&tilted, &tilted)];
+		let inner = pts![(0.4, 0.4, 0.4), (0.6, 0.4, 0.4), (0.4, 0.6, 0.4), (0.4, 0.4, 0.6)];
+		assert![in_both(&cube, &inner) && in_both(&inner, &cube)];
+		assert_eq![common_point(&cube, &shifted(&cube, Vec3::ONE)), Some(Vec3::ONE)];
+		assert_eq![common_point(&cube, &shifted(&cube, Vec3(3.0, 0.0, 0.0))), None];
+	}
+
+	#[test]
+	fn empty_hulls() {
+		// Around the origin, where an empty hull taken as a point would be
+		let cube = cube_at(Vec3(-0.5, -0.5, -0.5));
+		let empty: [Vec3; 0] = [];
+		let pairs = [(&empty[..], &cube[..]), (&cube[..], &empty[..]), (&empty[..], &empty[..])];
+		for &(hull1, hull2) in &pairs {
+			assert_eq![gjk_distance(hull1, hull2), f32::INFINITY];
+			assert_eq![gjk_distance_squared(hull1, hull2), f32::INFINITY];
+			assert![matches![gjk_query(hull1, hull2), GjkResult::Empty]];
+			assert_eq![signed_distance(hull1, hull2), f32::INFINITY];
+			assert![!within_distance(hull1, hull2, 1e30)];
+			assert_eq![closest_points(hull1, hull2), None];
+			assert![closest_feature(hull1, hull2).is_none()];
+			assert_eq![common_point(hull1, hull2), None];
+			assert_eq![contact_normal(hull1, hull2), None];
+			consistent(hull1, hull2);
+		}
+		assert_eq![closest_point_on_hull(&empty, Vec3::ZERO), None];
+	}
+}
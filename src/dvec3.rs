@@ -0,0 +1,339 @@
+//! Double precision instantiation of `Vector3` and its `bgjk` entry point.
+use super::{Vec3, Vector3, bgjk};
+
+/// Double precision vector for use in the `bgjk_f64` function
+///
+/// Like `Vec3`, but for hulls whose features are too fine for `f32`, such
+/// as sub-micron details on parts measured in meters. Build values with
+/// `DVec3::new` or `Vector3(..)`.
+pub type DVec3 = Vector3<f64>;
+
+/// Widens every component, which is lossless
+impl From<Vec3> for DVec3 {
+	fn from(vector: Vec3) -> DVec3 {
+		Vector3(vector.0 as f64, vector.1 as f64, vector.2 as f64)
+	}
+}
+
+impl DVec3 {
+	/// Narrow every component to `f32`, rounding to the nearest value
+	pub fn to_f32(self) -> Vec3 {
+		Vec3(self.0 as f32, self.1 as f32, self.2 as f32)
+	}
+}
+
+/// The BGJK algorithm in double precision, same as `bgjk::<f64>`
+pub fn bgjk_f64(hull1: &[DVec3], hull2: &[DVec3]) -> bool {
+	bgjk(hull1, hull2)
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+
+	use std::f64;
+	use std::f64::consts::PI;
+	use super::super::{Axis, DVec3, Vec3, bgjk, bgjk_f64};
+	static EPS: f64 = f64::EPSILON;
+
+	macro_rules! pts {
+		($($e:expr),*) => {
+			[$(
+				DVec3::new($e.0, $e.1, $e.2)
+			),*]
+		};
+	}
+
+	#[test]
+	fn square1() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let shape2 = pts![(-2.0, 0.0, 0.0), (-3.0, 0.0, 0.0), (-2.0, 1.0, 0.0), (-3.0, 1.0, 0.0)];
+		assert_eq![bgjk_f64(&shape1, &shape2), false];
+	}
+
+	#[test]
+	fn exact_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let shape2 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		assert_eq![bgjk_f64(&shape1, &shape2), true];
+	}
+
+	#[test]
+	fn line_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0)];
+		let shape2 = pts![(0.5, 1.0, 0.0), (0.5, -1.0, 0.0)];
+		assert_eq![bgjk_f64(&shape1, &shape2), true];
+	}
+
+	#[test]
+	fn line_non_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0)];
+		let shape2 = pts![(1.5, 1.0, 0.0), (1.5, -1.0, 0.0)];
+		assert_eq![bgjk_f64(&shape1, &shape2), false];
+	}
+
+	#[test]
+	fn small_line_point_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (0.01, 0.0, 0.0)];
+		let shape2 = pts![(0.005, 0.0, 0.1)];
+		assert_eq![bgjk_f64(&shape1, &shape2), false];
+	}
+
+	#[test]
+	fn line_point_non_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0)];
+		let shape2 = pts![(0.5, 0.0, 0.1)];
+		assert_eq![bgjk_f64(&shape1, &shape2), false];
+	}
+
+	#[test]
+	fn point_overlap() {
+		let shape1 = pts![(0.5, 1.0, 0.0)];
+		let shape2 = pts![(0.5, 1.0, 0.0)];
+		assert_eq![bgjk_f64(&shape1, &shape2), true];
+	}
+
+	#[test]
+	fn point_no_overlap() {
+		let shape1 = pts![(0.5, 1.0, 0.0)];
+		let shape2 = pts![(1.0, 1.0, 0.0)];
+		assert_eq![bgjk_f64(&shape1, &shape2), false];
+	}
+
+	#[test]
+	fn empty_no_overlap() {
+		// An empty set intersects nothing, not even around the origin
+		let shape1: [DVec3; 0] = pts![];
+		let shape2 = pts![(1.0, 1.0, 1.0)];
+		assert_eq![bgjk_f64(&shape1, &shape2), false];
+		let around = pts![(-1.0, -1.0, -1.0), (1.0, -1.0, -1.0), (0.0, 1.0, -1.0), (0.0, 0.0, 1.0)];
+		assert_eq![bgjk_f64(&shape1, &around), false];
+	}
+
+	#[test]
+	fn side_by_side_squares() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let shape2 = pts![(1.0, 0.0, 0.0), (2.0, 0.0, 0.0), (1.0, 1.0, 0.0), (2.0, 1.0, 0.0)];
+		assert_eq![bgjk_f64(&shape1, &shape2), true];
+	}
+
+	#[test]
+	fn side_by_side_squares_offset() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let shape2 =
+			pts![(1.0 + EPS, 0.0, 0.0), (2.0, 0.0, 0.0), (1.0 + EPS, 1.0, 0.0), (2.0, 1.0, 0.0)];
+		assert_eq![bgjk_f64(&shape1, &shape2), false];
+	}
+
+	#[test]
+	fn single_point_square_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let shape2 = pts![(1.0, 1.0, 0.0), (2.0, 1.0, 0.0), (1.0, 2.0, 0.0), (2.0, 2.0, 0.0)];
+		assert_eq![bgjk_f64(&shape1, &shape2), true];
+	}
+
+	#[test]
+	fn single_point_shape_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0),
+		                 (1.0, 0.0, 0.0),
+		                 (0.0, 1.0, 0.0),
+		                 (1.0, 1.0, 0.0),
+		                 (0.0, 0.0, 1.0),
+		                 (1.0, 0.0, 1.0),
+		                 (0.0, 1.0, 1.0),
+		                 (1.0, 1.0, 1.0)];
+		let shape2 = pts![(1.0, 1.0, 1.0),
+		                 (2.0, 1.0, 1.0),
+		                 (1.0, 2.0, 1.0),
+		                 (2.0, 2.0, 1.0),
+		                 (1.0, 1.0, 2.0),
+		                 (2.0, 1.0, 2.0),
+		                 (1.0, 2.0, 2.0),
+		                 (2.0, 2.0, 2.0)];
+		assert_eq![bgjk_f64(&shape1, &shape2), true];
+	}
+
+	#[test]
+	fn single_point_shape_non_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0),
+		                 (1.0, 0.0, 0.0),
+		                 (0.0, 1.0, 0.0),
+		                 (1.0, 1.0, 0.0),
+		                 (0.0, 0.0, 1.0),
+		                 (1.0, 0.0, 1.0),
+		                 (0.0, 1.0, 1.0),
+		                 (1.0, 1.0, 1.0)];
+		let shape2 = pts![(1.0, 1.0, 1.0 + EPS),
+		                 (2.0, 1.0, 1.0 + EPS),
+		                 (1.0, 2.0, 1.0 + EPS),
+		                 (2.0, 2.0, 1.0 + EPS),
+		                 (1.0, 1.0, 2.0),
+		                 (2.0, 1.0, 2.0),
+		                 (1.0, 2.0, 2.0),
+		                 (2.0, 2.0, 2.0)];
+		assert_eq![bgjk_f64(&shape1, &shape2), false];
+	}
+
+	#[test]
+	fn single_line_shape_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0),
+		                 (1.0, 0.0, 0.0),
+		                 (0.0, 1.0, 0.0),
+		                 (1.0, 1.0, 0.0),
+		                 (0.0, 0.0, 1.0),
+		                 (1.0, 0.0, 1.0),
+		                 (0.0, 1.0, 1.0),
+		                 (1.0, 1.0, 1.0)];
+		let shape2 = pts![(1.0, 1.0, 0.0),
+		                 (2.0, 1.0, 0.0),
+		                 (1.0, 2.0, 0.0),
+		                 (2.0, 2.0, 0.0),
+		                 (1.0, 1.0, 1.0),
+		                 (2.0, 1.0, 1.0),
+		                 (1.0, 2.0, 1.0),
+		                 (2.0, 2.0, 1.0)];
+		assert_eq![bgjk_f64(&shape1, &shape2), true];
+	}
+
+	#[test]
+	fn shape_projective_non_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0),
+		                 (1.0, 0.0, 0.0),
+		                 (0.0, 1.0, 0.0),
+		                 (1.0, 1.0, 0.0),
+		                 (1.0, 0.0, 1.0),
+		                 (2.0, 0.0, 1.0),
+		                 (1.0, 1.0, 1.0),
+		                 (2.0, 1.0, 1.0)];
+		let shape2 = pts![(1.1, 1.0, 0.0),
+		                 (2.1, 1.0, 0.0),
+		                 (1.1, 2.0, 0.0),
+		                 (2.1, 2.0, 0.0),
+		                 (2.1, 1.0, 1.0),
+		                 (3.1, 1.0, 1.0),
+		                 (2.1, 2.0, 1.0),
+		                 (3.1, 2.0, 1.0)];
+		assert_eq![bgjk_f64(&shape1, &shape2), false];
+	}
+
+	#[test]
+	fn shape_projective_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0),
+		                 (1.0, 0.0, 0.0),
+		                 (0.0, 1.0, 0.0),
+		                 (1.0, 1.0, 0.0),
+		                 (1.0, 0.0, 1.0),
+		                 (2.0, 0.0, 1.0),
+		                 (1.0, 1.0, 1.0),
+		                 (2.0, 1.0, 1.0)];
+		let shape2 = pts![(1.1, 1.0, 0.0),
+		                 (2.1, 1.0, 0.0),
+		                 (1.1, 2.0, 0.0),
+		                 (2.1, 2.0, 0.0),
+		                 (2.0, 1.0, 1.0),
+		                 (3.1, 1.0, 1.0),
+		                 (2.0, 2.0, 1.0),
+		                 (3.1, 2.0, 1.0)];
+		assert_eq![bgjk_f64(&shape1, &shape2), true];
+	}
+
+	#[test]
+	fn shape_non_overlap() {
+		let (mut shape1, mut shape2) = (vec![], vec![]);
+		let units = 100;
+		shape1.reserve(units);
+		shape2.reserve(units);
+		for i in 0..units {
+			let radian = i as f64 / units as f64 * 2.0 * PI;
+			shape1.push(DVec3::new(radian.cos(), radian.sin(), 0.0));
+			shape2.push(DVec3::new(radian.cos(), radian.sin(), EPS));
+		}
+		assert_eq![bgjk_f64(&shape1, &shape2), false];
+	}
+
+	#[test]
+	fn shape_overlap() {
+		let (mut shape1, mut shape2) = (vec![], vec![]);
+		let units = 100;
+		shape1.reserve(units);
+		shape2.reserve(units);
+		for i in 0..units {
+			let radian = i as f64 / units as f64 * 2.0 * PI;
+			shape1.push(DVec3::new(radian.cos(), radian.sin(), 0.0));
+			shape2.push(DVec3::new(radian.cos(), radian.sin(), 0.0));
+		}
+		assert_eq![bgjk_f64(&shape1, &shape2), true];
+	}
+
+	#[test]
+	fn shape_section() {
+		let (mut shape1, mut shape2) = (vec![], vec![]);
+		let units = 100;
+		shape1.reserve(units);
+		shape2.reserve(units);
+		for i in 0..units {
+			let radian = i as f64 / units as f64 * 2.0 * PI;
+			shape1.push(DVec3::new(radian.cos(), radian.sin(), 0.0));
+			shape2.push(DVec3::new(radian.cos() + 0.5, radian.sin(), 0.0));
+		}
+		assert_eq![bgjk_f64(&shape1, &shape2), true];
+	}
+
+	#[test]
+	fn shape_away() {
+		let (mut shape1, mut shape2) = (vec![], vec![]);
+		let units = 100;
+		shape1.reserve(units);
+		shape2.reserve(units);
+		for i in 0..units {
+			let radian = i as f64 / units as f64 * 2.0 * PI;
+			shape1.push(DVec3::new(radian.cos(), radian.sin(), 0.0));
+			shape2.push(DVec3::new(radian.cos() + 2.0 + 2.0 * EPS, radian.sin(), 0.0));
+		}
+		assert_eq![bgjk_f64(&shape1, &shape2), false];
+	}
+
+	#[test]
+	fn operators() {
+		let v = DVec3::new(1.0, -2.0, 0.5);
+		assert_eq![v + DVec3::ONE, DVec3::new(2.0, -1.0, 1.5)];
+		assert_eq![v - DVec3::ONE, DVec3::new(0.0, -3.0, -0.5)];
+		assert_eq![v * 2.0, DVec3::new(2.0, -4.0, 1.0)];
+		assert_eq![2.0 * v, v * 2.0];
+		assert_eq![v / 2.0, DVec3::new(0.5, -1.0, 0.25)];
+		assert_eq![-v, DVec3::new(-1.0, 2.0, -0.5)];
+		let mut w = v;
+		w += DVec3::ONE;
+		w -= DVec3::X;
+		w *= 4.0;
+		w /= 2.0;
+		assert_eq![w, DVec3::new(2.0, -2.0, 3.0)];
+		assert_eq![(w[0], w[1], w[2]), (w[Axis::X], w[Axis::Y], w[Axis::Z])];
+		assert_eq![DVec3::X.cross(DVec3::Y), DVec3::Z];
+		assert_eq![DVec3::new(1.0, 2.0, 2.0).length(), 3.0];
+		assert_eq![DVec3::from(Vec3(1.0, 0.1, -3.0)).to_f32(), Vec3(1.0, 0.1, -3.0)];
+		assert_eq![DVec3::from([1.0, 2.0, 3.0]), DVec3::from((1.0, 2.0, 3.0))];
+	}
+
+	#[test]
+	fn sub_micron_gap() {
+		// Two 10 m blocks 0.1 µm apart, a gap that rounds away in `f32`
+		let block = pts![(0.0, 0.0, 0.0),
+		                 (10.0, 0.0, 0.0),
+		                 (0.0, 10.0, 0.0),
+		                 (10.0, 10.0, 0.0),
+		                 (0.0, 0.0, 10.0),
+		                 (10.0, 0.0, 10.0),
+		                 (0.0, 10.0, 10.0),
+		                 (10.0, 10.0, 10.0)];
+		let gap = DVec3::new(10.0 + 1e-7, 0.0, 0.0);
+		let beside: Vec<DVec3> = block.iter().map(|v| *v + gap).collect();
+		assert_eq![bgjk_f64(&block, &beside), false];
+		let block32: Vec<Vec3> = block.iter().map(|v| v.to_f32()).collect();
+		let beside32: Vec<Vec3> = beside.iter().map(|v| v.to_f32()).collect();
+		assert_eq![bgjk(&block32, &beside32), true];
+		let touching: Vec<DVec3> = block.iter().map(|v| *v + DVec3::new(10.0, 0.0, 0.0)).collect();
+		assert_eq![bgjk_f64(&block, &touching), true];
+	}
+
+}
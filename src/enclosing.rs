@@ -0,0 +1,187 @@
+//! The smallest sphere around a hull, for rejecting pairs before `bgjk`.
+use super::{Float, Sphere, Vector3};
+
+// The smallest sphere through two points
+fn diametral<T: Float>(a: Vector3<T>, b: Vector3<T>) -> Sphere<T> {
+	let center = (a + b) / T::from_f32(2.0);
+	Sphere { center, radius: center.distance(a) }
+}
+
+// The smallest sphere through three points, centered in their plane, or
+// through the farthest two of them where they are on a line
+fn circumscribed<T: Float>(a: Vector3<T>, b: Vector3<T>, c: Vector3<T>) -> Sphere<T> {
+	let (u, v) = (b - a, c - a);
+	let w = u.cross(v);
+	let divisor = w.length_squared() * T::from_f32(2.0);
+	let offset = (v.cross(w) * u.length_squared() + w.cross(u) * v.length_squared()) / divisor;
+	if !offset.is_finite() {
+		let pairs = [diametral(a, b), diametral(a, c), diametral(b, c)];
+		return pairs.iter().fold(pairs[0], |widest, &pair| {
+			if pair.radius > widest.radius { pair } else { widest }
+		});
+	}
+	let center = a + offset;
+	Sphere { center, radius: center.distance(a) }
+}
+
+// The sphere through four points, `None` where they are on a plane
+fn through<T: Float>(a: Vector3<T>,
+                     b: Vector3<T>,
+                     c: Vector3<T>,
+                     d: Vector3<T>) -> Option<Sphere<T>> {
+	let (u, v, t) = (b - a, c - a, d - a);
+	let divisor = u.dot(v.cross(t)) * T::from_f32(2.0);
+	let offset = (v.cross(t) * u.length_squared() + t.cross(u) * v.length_squared() +
+	              u.cross(v) * t.length_squared()) / divisor;
+	if !offset.is_finite() {
+		return None;
+	}
+	let center = a + offset;
+	Some(Sphere { center, radius: center.distance(a) })
+}
+
+/// The smallest sphere containing all `points`, `None` for no points
+///
+/// Runs Welzl's algorithm in its incremental form, taking expected linear
+/// time over the points visited in a shuffled order. The sphere found is
+/// the smallest up to rounding, rounding that can only make it larger: its
+/// radius is finally widened to the farthest point, so every point is
+/// inside as `distance` measures it. A single point gives a radius of zero.
+///
+/// Spheres are far cheaper to test against each other than hulls, so pairs
+/// whose spheres are apart need no `bgjk` at all. The sphere around the
+/// `centroid` reaching the farthest point is quicker to find, but can have
+/// almost twice the radius.
+///
+/// ```
+/// use bgjk::{bounding_sphere, Vec3};
+///
+/// let segment = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.5, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0)];
+/// let sphere = bounding_sphere(&segment).unwrap();
+/// assert_eq![(sphere.center, sphere.radius), (Vec3::new(1.0, 0.0, 0.0), 1.0)];
+/// ```
+pub fn bounding_sphere<T: Float>(points: &[Vector3<T>]) -> Option<Sphere<T>> {
+	debug_assert![points.iter().all(Vector3::is_finite),
+	              "bounding_sphere called with a non-finite point"];
+	let first = *points.first()?;
+	// A fixed shuffle, the same sphere for the same points every time
+	let mut order: Vec<Vector3<T>> = points.to_vec();
+	let mut state = 0x853c_49e6_748f_ea9bu64;
+	for i in (1..order.len()).rev() {
+		state = state.wrapping_mul(6_364_136_223_846_793_005)
+		             .wrapping_add(1_442_695_040_888_963_407);
+		order.swap(i, (state >> 33) as usize % (i + 1));
+	}
+	// Points this close outside count as inside, so rounding does not keep
+	// restarting the inner loops
+	let scale = points.iter().fold(T::ZERO, |max, point| {
+		let size = point.abs();
+		max.max(size.0).max(size.1).max(size.2)
+	});
+	let slack = scale * T::EPSILON * T::from_f32(16.0);
+	let outside = |sphere: &Sphere<T>, point: Vector3<T>| {
+		sphere.center.distance(point) > sphere.radius + slack
+	};
+	let mut sphere = Sphere { center: first, radius: T::ZERO };
+	for i in 0..order.len() {
+		if !outside(&sphere, order[i]) {
+			continue;
+		}
+		sphere = Sphere { center: order[i], radius: T::ZERO };
+		for j in 0..i {
+			if !outside(&sphere, order[j]) {
+				continue;
+			}
+			sphere = diametral(order[i], order[j]);
+			for k in 0..j {
+				if !outside(&sphere, order[k]) {
+					continue;
+				}
+				sphere = circumscribed(order[i], order[j], order[k]);
+				for l in 0..k {
+					if outside(&sphere, order[l]) {
+						sphere = through(order[i], order[j], order[k], order[l]).unwrap_or(Sphere {
+							center: sphere.center,
+							radius: sphere.center.distance(order[l]),
+						});
+					}
+				}
+			}
+		}
+	}
+	sphere.radius = points.iter().fold(sphere.radius, |radius, point| {
+		radius.max(sphere.center.distance(*point))
+	});
+	Some(sphere)
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::super::{Vec3, bounding_sphere, centroid};
+	use super::super::test_util::cube_at;
+
+	#[test]
+	fn cube() {
+		let sphere = bounding_sphere(&cube_at(Vec3::ZERO)).unwrap();
+		assert![sphere.center.approx_eq(Vec3::ONE * 0.5, 1e-6)];
+		assert![(sphere.radius - 0.75f32.sqrt()).abs() <= 1e-6];
+		let sphere = bounding_sphere(&cube_at(Vec3(100.0, -50.0, 20.0))).unwrap();
+		assert![sphere.center.approx_eq(Vec3(100.5, -49.5, 20.5), 1e-4)];
+		assert![(sphere.radius - 0.75f32.sqrt()).abs() <= 1e-4];
+	}
+
+	#[test]
+	fn degenerate() {
+		assert![bounding_sphere::<f32>(&[]).is_none()];
+		let point = bounding_sphere(&pts![(1.0, 2.0, 3.0)]).unwrap();
+		assert_eq![(point.center, point.radius), (Vec3(1.0, 2.0, 3.0), 0.0)];
+		let line: Vec<Vec3> = (0..20).map(|i| Vec3(1.0, 2.0, 2.0) * (i * 7 % 20) as f32).collect();
+		let sphere = bounding_sphere(&line).unwrap();
+		assert![sphere.center.approx_eq(Vec3(1.0, 2.0, 2.0) * 9.5, 1e-4)];
+		assert![(sphere.radius - 3.0 * 9.5).abs() <= 1e-4];
+		// A square, all corners on the circle in its plane
+		let square = pts![(0.0, 0.0, 1.0), (2.0, 0.0, 1.0), (0.0, 2.0, 1.0), (2.0, 2.0, 1.0)];
+		let sphere = bounding_sphere(&square).unwrap();
+		assert![sphere.center.approx_eq(Vec3(1.0, 1.0, 1.0), 1e-6)];
+		assert![(sphere.radius - 2.0f32.sqrt()).abs() <= 1e-6];
+	}
+
+	#[test]
+	fn tighter_than_centroid() {
+		// Most points bunched up at one end, which drags the centroid there
+		let mut cloud: Vec<Vec3> = (0..100)
+			.map(|i| {
+				let t = i as f32;
+				Vec3((t * 0.37).sin(), (t * 1.91).sin(), t.cos()) * 0.1
+			})
+			.collect();
+		cloud.push(Vec3::X * 10.0);
+		let center = centroid(&cloud).unwrap();
+		let loose = cloud.iter().fold(0.0f32, |radius, point| radius.max(center.distance(*point)));
+		let sphere = bounding_sphere(&cloud).unwrap();
+		assert![sphere.radius < loose * 0.6];
+		assert![(sphere.radius - 5.05).abs() <= 0.05];
+	}
+
+	#[test]
+	fn contains_every_point() {
+		for n in 1..60 {
+			let cloud: Vec<Vec3> = (0..n * 5)
+				.map(|i| {
+					let t = (i * n) as f32;
+					Vec3((t * 0.37).sin() * 3.0, (t * 1.91).sin(), (t * 0.73).cos() * n as f32 * 0.1)
+				})
+				.collect();
+			let sphere = bounding_sphere(&cloud).unwrap();
+			let reach = |far: f32, point: &Vec3| far.max(sphere.center.distance(*point));
+			let farthest = cloud.iter().fold(0.0f32, reach);
+			assert![farthest <= sphere.radius];
+			// At least two points on the sphere, or it could shrink
+			let touching = cloud.iter()
+				.filter(|point| sphere.center.distance(**point) >= sphere.radius * (1.0 - 1e-5))
+				.count();
+			assert![touching >= 2 || sphere.radius == 0.0];
+		}
+	}
+}
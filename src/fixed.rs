@@ -0,0 +1,510 @@
+//! Fixed-point hulls and their `bgjk` entry point, for deterministic results.
+use std::ops::{Add, Neg, Sub};
+use super::{DVec3, Region, either_empty};
+
+// Upper bound on the iterations, the default of `GjkConfig`, running out
+// counts as an intersection like it does for `bgjk`
+const MAX_ITERATIONS: usize = 64;
+
+/// Fixed-point vector for use in the `bgjk_fixed` function
+///
+/// Each component is the raw `i64` of a fixed-point number with `FRAC`
+/// fractional bits, 32.32 by default, so `FixVec3(1 << 32, 0, 0)` is the
+/// unit vector along x. Lockstep simulations need every peer to get the
+/// same answers bit for bit, which floating point does not promise across
+/// compilers and platforms, but integer arithmetic does.
+///
+/// Components must stay within `FixVec3::MAX` in magnitude, see
+/// `bgjk_fixed`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FixVec3<const FRAC: u32 = 32>(pub i64, pub i64, pub i64);
+
+impl<const FRAC: u32> FixVec3<FRAC> {
+	/// The zero vector
+	pub const ZERO: FixVec3<FRAC> = FixVec3(0, 0, 0);
+	/// The largest raw component magnitude `bgjk_fixed` handles, `2^60`,
+	/// which is `2^28` or about 268 million units with 32 fractional bits
+	pub const MAX: i64 = 1 << 60;
+
+	/// Create a vector from its components, rounding each to the nearest
+	/// fixed-point value
+	///
+	/// Rounding is the same on every platform, so hulls converted from the
+	/// same `f64` values are the same everywhere. Components outside the
+	/// range of `i64` saturate and NaN becomes zero.
+	pub fn from_f64(x: f64, y: f64, z: f64) -> FixVec3<FRAC> {
+		let scale = 2f64.powi(FRAC as i32);
+		FixVec3((x * scale).round() as i64, (y * scale).round() as i64, (z * scale).round() as i64)
+	}
+
+	/// The nearest `f64` of every component
+	pub fn to_f64(self) -> DVec3 {
+		let scale = 2f64.powi(-(FRAC as i32));
+		DVec3::new(self.0 as f64 * scale, self.1 as f64 * scale, self.2 as f64 * scale)
+	}
+}
+
+impl<const FRAC: u32> Add for FixVec3<FRAC> {
+	type Output = FixVec3<FRAC>;
+	fn add(self, right: FixVec3<FRAC>) -> Self::Output {
+		FixVec3(self.0 + right.0, self.1 + right.1, self.2 + right.2)
+	}
+}
+
+impl<const FRAC: u32> Sub for FixVec3<FRAC> {
+	type Output = FixVec3<FRAC>;
+	fn sub(self, right: FixVec3<FRAC>) -> Self::Output {
+		FixVec3(self.0 - right.0, self.1 - right.1, self.2 - right.2)
+	}
+}
+
+impl<const FRAC: u32> Neg for FixVec3<FRAC> {
+	type Output = FixVec3<FRAC>;
+	fn neg(self) -> Self::Output {
+		FixVec3(-self.0, -self.1, -self.2)
+	}
+}
+
+// A vector of the search in raw units. Points of the Minkowski difference
+// and the edges between them fit in 63 bits, directions are scaled down to
+// fit in 62, so the products of either with a direction fit in `i128`.
+#[derive(Clone, Copy, PartialEq)]
+struct Wide(i128, i128, i128);
+
+impl Wide {
+	const ZERO: Wide = Wide(0, 0, 0);
+	const ONE: Wide = Wide(1, 1, 1);
+
+	fn dot(self, right: Wide) -> i128 {
+		self.0 * right.0 + self.1 * right.1 + self.2 * right.2
+	}
+
+	// The cross product, halved until every component fits in 62 bits.
+	// Halving keeps the direction up to rounding, and only directions are
+	// crossed, never compared for length.
+	fn cross(self, right: Wide) -> Wide {
+		let mut cross = Wide(self.1 * right.2 - self.2 * right.1,
+		                     self.2 * right.0 - self.0 * right.2,
+		                     self.0 * right.1 - self.1 * right.0);
+		let limit = 1 << 62;
+		while cross.0.abs() >= limit || cross.1.abs() >= limit || cross.2.abs() >= limit {
+			cross = Wide(cross.0 >> 1, cross.1 >> 1, cross.2 >> 1);
+		}
+		cross
+	}
+}
+
+impl Add for Wide {
+	type Output = Wide;
+	fn add(self, right: Wide) -> Wide {
+		Wide(self.0 + right.0, self.1 + right.1, self.2 + right.2)
+	}
+}
+
+impl Sub for Wide {
+	type Output = Wide;
+	fn sub(self, right: Wide) -> Wide {
+		Wide(self.0 - right.0, self.1 - right.1, self.2 - right.2)
+	}
+}
+
+impl Neg for Wide {
+	type Output = Wide;
+	fn neg(self) -> Wide {
+		Wide(-self.0, -self.1, -self.2)
+	}
+}
+
+impl<const FRAC: u32> From<FixVec3<FRAC>> for Wide {
+	fn from(vector: FixVec3<FRAC>) -> Wide {
+		Wide(vector.0 as i128, vector.1 as i128, vector.2 as i128)
+	}
+}
+
+/// The BGJK algorithm on fixed-point hulls, see `bgjk`
+///
+/// Runs the same search as `bgjk` in integer arithmetic, with products
+/// taken in `i128`, so the answer depends on nothing but the input: every
+/// platform, compiler and optimization level gives the same one. The
+/// fractional bits only matter for converting to and from floats, the
+/// search works on the raw integers.
+///
+/// Every raw component of both hulls must be within `FixVec3::MAX`, that
+/// is `2^60`, in magnitude. The arithmetic cannot overflow within that
+/// bound. Directions computed from cross products are scaled down to stay
+/// within it, so like `bgjk` the answer for hulls that only just touch or
+/// miss can go either way, but always the same way. Debug builds panic on
+/// components out of bounds.
+///
+/// ```
+/// use bgjk::{bgjk_fixed, FixVec3};
+///
+/// let triangle: [FixVec3; 3] = [FixVec3::from_f64(0.0, 0.0, 0.0),
+///                               FixVec3::from_f64(1.0, 0.0, 0.0),
+///                               FixVec3::from_f64(0.0, 1.0, 0.0)];
+/// let point = FixVec3::from_f64(0.25, 0.25, 0.0);
+/// assert![bgjk_fixed(&triangle, &[point])];
+/// assert![!bgjk_fixed(&triangle, &[point + FixVec3(0, 0, 1)])];
+/// ```
+pub fn bgjk_fixed<const FRAC: u32>(hull1: &[FixVec3<FRAC>], hull2: &[FixVec3<FRAC>]) -> bool {
+	debug_assert![hull1.iter()
+	                   .chain(hull2)
+	                   .all(|v| [v.0, v.1, v.2].iter().all(|c| c.abs() <= FixVec3::<FRAC>::MAX)),
+	              "bgjk_fixed called with a component beyond FixVec3::MAX"];
+	if either_empty(hull1, hull2) {
+		return false;
+	}
+	let support = |direction| farthest(hull1, direction) - farthest(hull2, -direction);
+	let (mut ap, mut bp, mut cp, mut dp);
+
+	cp = support(Wide::ONE);
+	dp = cp;
+	let mut sp = -cp;
+	bp = support(sp);
+	if bp.dot(sp) < 0 {
+		return false;
+	}
+	sp = (cp - bp).cross(-bp).cross(cp - bp);
+	let mut w = 2;
+
+	for _ in 0..MAX_ITERATIONS {
+		ap = support(sp);
+		if ap.dot(sp) < 0 {
+			return false;
+		} else if simplex(&mut ap, &mut bp, &mut cp, &mut dp, &mut sp, &mut w) {
+			return true;
+		}
+	}
+	true
+}
+
+// The raw point of `vertices` farthest along `direction`, the first where
+// several tie and zero for no points
+fn farthest<const FRAC: u32>(vertices: &[FixVec3<FRAC>], direction: Wide) -> Wide {
+	let mut max: Option<i128> = None;
+	let mut farthest = Wide::ZERO;
+	for &vertex in vertices {
+		let current = Wide::from(vertex).dot(direction);
+		if max.is_none_or(|value| current > value) {
+			max = Some(current);
+			farthest = Wide::from(vertex);
+		}
+	}
+	farthest
+}
+
+// The simplex step of `bgjk`, in integers
+fn simplex(ap: &mut Wide,
+           bp: &mut Wide,
+           cp: &mut Wide,
+           dp: &mut Wide,
+           sp: &mut Wide,
+           w: &mut i32)
+           -> bool {
+	let ao = -*ap;
+	let ab = *bp - *ap;
+	let ac = *cp - *ap;
+	let abc = ab.cross(ac);
+	match *w {
+		2 => {
+			if ab.cross(abc).dot(ao) > 0 {
+				*cp = *bp;
+				*bp = *ap;
+				*sp = ab.cross(ao).cross(ab);
+			} else if abc.cross(ac).dot(ao) > 0 {
+				*bp = *ap;
+				*sp = ac.cross(ao).cross(ac);
+			} else {
+				if abc.dot(ao) > 0 {
+					*dp = *cp;
+					*cp = *bp;
+					*bp = *ap;
+					*sp = abc;
+				} else {
+					*dp = *bp;
+					*bp = *ap;
+					*sp = -abc;
+				}
+				*w = 3;
+			}
+			false
+		}
+		3 => {
+			// The face loop of the float `simplex` in lib.rs, in integers, keep
+			// the two alike
+			let ad = *dp - *ap;
+			let faces = [(abc, *bp, *cp, ab, ac),
+			             (ac.cross(ad), *cp, *dp, ac, ad),
+			             (ad.cross(ab), *dp, *bp, ad, ab)];
+			let mut chosen = None;
+			for &(normal, second, third, first_edge, second_edge) in &faces {
+				if normal.dot(ao) > 0 {
+					let region = if first_edge.cross(normal).dot(ao) > 0 {
+						Region::FirstEdge
+					} else if normal.cross(second_edge).dot(ao) > 0 {
+						Region::SecondEdge
+					} else {
+						Region::Face
+					};
+					if chosen.is_none() || region == Region::Face {
+						chosen = Some((region, normal, second, third, first_edge, second_edge));
+					}
+					if region == Region::Face {
+						break;
+					}
+				}
+			}
+			match chosen {
+				Some((Region::FirstEdge, _, second, _, edge, _)) => {
+					*cp = second;
+					*bp = *ap;
+					*sp = edge.cross(ao).cross(edge);
+					*w = 2;
+				}
+				Some((Region::SecondEdge, _, _, third, _, edge)) => {
+					*cp = third;
+					*bp = *ap;
+					*sp = edge.cross(ao).cross(edge);
+					*w = 2;
+				}
+				Some((Region::Face, normal, second, third, _, _)) => {
+					*dp = third;
+					*cp = second;
+					*bp = *ap;
+					*sp = normal;
+				}
+				None => return true,
+			}
+			false
+		}
+		_ => false,
+	}
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+
+	use std::f64::consts::PI;
+	use super::super::{DVec3, FixVec3, bgjk_f64, bgjk_fixed};
+	// One raw unit of the default 32 fractional bits
+	static EPS: f64 = 1.0 / 4294967296.0;
+
+	macro_rules! pts {
+		($($e:expr),*) => {
+			[$(
+				FixVec3::<32>::from_f64($e.0, $e.1, $e.2)
+			),*]
+		};
+	}
+
+	fn cube_at(x: f64, y: f64, z: f64) -> [FixVec3; 8] {
+		pts![(x, y, z),
+		     (x + 1.0, y, z),
+		     (x, y + 1.0, z),
+		     (x + 1.0, y + 1.0, z),
+		     (x, y, z + 1.0),
+		     (x + 1.0, y, z + 1.0),
+		     (x, y + 1.0, z + 1.0),
+		     (x + 1.0, y + 1.0, z + 1.0)]
+	}
+
+	fn circle(offset: FixVec3, units: usize) -> Vec<FixVec3> {
+		(0..units)
+			.map(|i| {
+				let radian = i as f64 / units as f64 * 2.0 * PI;
+				FixVec3::from_f64(radian.cos(), radian.sin(), 0.0) + offset
+			})
+			.collect()
+	}
+
+	#[test]
+	fn square1() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let shape2 = pts![(-2.0, 0.0, 0.0), (-3.0, 0.0, 0.0), (-2.0, 1.0, 0.0), (-3.0, 1.0, 0.0)];
+		assert_eq![bgjk_fixed(&shape1, &shape2), false];
+	}
+
+	#[test]
+	fn exact_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		assert_eq![bgjk_fixed(&shape1, &shape1), true];
+	}
+
+	#[test]
+	fn lines_and_points() {
+		let line = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0)];
+		assert_eq![bgjk_fixed(&line, &pts![(0.5, 1.0, 0.0), (0.5, -1.0, 0.0)]), true];
+		assert_eq![bgjk_fixed(&line, &pts![(1.5, 1.0, 0.0), (1.5, -1.0, 0.0)]), false];
+		assert_eq![bgjk_fixed(&line, &pts![(0.5, 0.0, 0.1)]), false];
+		assert_eq![bgjk_fixed(&line, &pts![(0.5, 0.0, EPS)]), false];
+		assert_eq![bgjk_fixed(&line, &pts![(0.5, 0.0, 0.0)]), true];
+		let short = pts![(0.0, 0.0, 0.0), (0.01, 0.0, 0.0)];
+		assert_eq![bgjk_fixed(&short, &pts![(0.005, 0.0, 0.1)]), false];
+		assert_eq![bgjk_fixed(&pts![(0.5, 1.0, 0.0)], &pts![(0.5, 1.0, 0.0)]), true];
+		assert_eq![bgjk_fixed(&pts![(0.5, 1.0, 0.0)], &pts![(1.0, 1.0, 0.0)]), false];
+	}
+
+	#[test]
+	fn empty_no_overlap() {
+		// An empty set intersects nothing, not even around the origin
+		let shape1: [FixVec3; 0] = pts![];
+		assert_eq![bgjk_fixed(&shape1, &pts![(1.0, 1.0, 1.0)]), false];
+		assert_eq![bgjk_fixed(&shape1, &cube_at(-0.5, -0.5, -0.5)), false];
+		assert_eq![bgjk_fixed(&cube_at(-0.5, -0.5, -0.5), &shape1), false];
+		assert_eq![bgjk_fixed(&shape1, &shape1), false];
+	}
+
+	#[test]
+	fn side_by_side_squares() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let shape2 = pts![(1.0, 0.0, 0.0), (2.0, 0.0, 0.0), (1.0, 1.0, 0.0), (2.0, 1.0, 0.0)];
+		assert_eq![bgjk_fixed(&shape1, &shape2), true];
+		let shape2 =
+			pts![(1.0 + EPS, 0.0, 0.0), (2.0, 0.0, 0.0), (1.0 + EPS, 1.0, 0.0), (2.0, 1.0, 0.0)];
+		assert_eq![bgjk_fixed(&shape1, &shape2), false];
+		let shape2 = pts![(1.0, 1.0, 0.0), (2.0, 1.0, 0.0), (1.0, 2.0, 0.0), (2.0, 2.0, 0.0)];
+		assert_eq![bgjk_fixed(&shape1, &shape2), true];
+	}
+
+	#[test]
+	fn cubes() {
+		let cube = cube_at(0.0, 0.0, 0.0);
+		assert_eq![bgjk_fixed(&cube, &cube_at(1.0, 1.0, 1.0)), true];
+		assert_eq![bgjk_fixed(&cube, &cube_at(1.0, 1.0, 1.0 + EPS)), false];
+		assert_eq![bgjk_fixed(&cube, &cube_at(1.0, 1.0, 0.0)), true];
+		assert_eq![bgjk_fixed(&cube, &cube_at(0.5, -0.5, 0.25)), true];
+		assert_eq![bgjk_fixed(&cube, &cube_at(0.2, 0.3, 0.4)), true];
+		assert_eq![bgjk_fixed(&cube, &cube_at(-1.0 - EPS, 0.0, 0.0)), false];
+	}
+
+	#[test]
+	fn shape_projective() {
+		let shape1 = pts![(0.0, 0.0, 0.0),
+		                 (1.0, 0.0, 0.0),
+		                 (0.0, 1.0, 0.0),
+		                 (1.0, 1.0, 0.0),
+		                 (1.0, 0.0, 1.0),
+		                 (2.0, 0.0, 1.0),
+		                 (1.0, 1.0, 1.0),
+		                 (2.0, 1.0, 1.0)];
+		let shape2 = pts![(1.1, 1.0, 0.0),
+		                 (2.1, 1.0, 0.0),
+		                 (1.1, 2.0, 0.0),
+		                 (2.1, 2.0, 0.0),
+		                 (2.1, 1.0, 1.0),
+		                 (3.1, 1.0, 1.0),
+		                 (2.1, 2.0, 1.0),
+		                 (3.1, 2.0, 1.0)];
+		assert_eq![bgjk_fixed(&shape1, &shape2), false];
+		let shape2 = pts![(1.1, 1.0, 0.0),
+		                 (2.1, 1.0, 0.0),
+		                 (1.1, 2.0, 0.0),
+		                 (2.1, 2.0, 0.0),
+		                 (2.0, 1.0, 1.0),
+		                 (3.1, 1.0, 1.0),
+		                 (2.0, 2.0, 1.0),
+		                 (3.1, 2.0, 1.0)];
+		assert_eq![bgjk_fixed(&shape1, &shape2), true];
+	}
+
+	#[test]
+	fn circles() {
+		let circle1 = circle(FixVec3::ZERO, 100);
+		assert_eq![bgjk_fixed(&circle1, &circle(FixVec3(0, 0, 1), 100)), false];
+		assert_eq![bgjk_fixed(&circle1, &circle1), true];
+		assert_eq![bgjk_fixed(&circle1, &circle(FixVec3::from_f64(0.5, 0.0, 0.0), 100)), true];
+		assert_eq![bgjk_fixed(&circle1, &circle(FixVec3::from_f64(2.0, 0.0, 0.0), 100)), true];
+		assert_eq![bgjk_fixed(&circle1, &circle(FixVec3::from_f64(2.0 + EPS, 0.0, 0.0), 100)),
+		           false];
+	}
+
+	#[test]
+	fn matches_f64() {
+		// Away from touching the fixed-point and float answers agree
+		let cube = cube_at(0.0, 0.0, 0.0);
+		let cube64: Vec<DVec3> = cube.iter().map(|v| v.to_f64()).collect();
+		let steps = [-1.3, -0.9, -0.4, 0.3, 0.7, 1.2];
+		for &x in &steps {
+			for &y in &steps {
+				for &z in &steps {
+					let other = cube_at(x * 0.7, y, z * 1.1);
+					let other64: Vec<DVec3> = other.iter().map(|v| v.to_f64()).collect();
+					assert_eq![bgjk_fixed(&cube, &other), bgjk_f64(&cube64, &other64)];
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn fractional_bits() {
+		let cube = |scale: f64| {
+			[FixVec3::<8>::from_f64(0.0, 0.0, 0.0),
+			 FixVec3::from_f64(scale, 0.0, 0.0),
+			 FixVec3::from_f64(0.0, scale, 0.0),
+			 FixVec3::from_f64(0.0, 0.0, scale)]
+		};
+		let moved: Vec<FixVec3<8>> = cube(1.0).iter().map(|v| *v + FixVec3(256, 0, 0)).collect();
+		assert_eq![bgjk_fixed(&cube(1.0), &moved), true];
+		let moved: Vec<FixVec3<8>> = moved.iter().map(|v| *v + FixVec3(1, 0, 0)).collect();
+		assert_eq![bgjk_fixed(&cube(1.0), &moved), false];
+		assert_eq![FixVec3::<8>::from_f64(1.5, -2.0, 0.001), FixVec3(384, -512, 0)];
+		assert_eq![FixVec3::<8>(384, -512, 1).to_f64(), DVec3::new(1.5, -2.0, 1.0 / 256.0)];
+	}
+
+	#[test]
+	fn largest_coordinates() {
+		// Overflow panics in tests, so these show the bound is safe
+		let max = FixVec3::<32>::MAX;
+		let corners: Vec<FixVec3> = (0..8)
+			.map(|i| {
+				let pick = |bit: i32| if i & bit == 0 { -max } else { max };
+				FixVec3(pick(1), pick(2), pick(4))
+			})
+			.collect();
+		let far: [FixVec3; 3] =
+			[FixVec3(max, max, max), FixVec3(max, -max, max), FixVec3(-max, max, -max)];
+		assert_eq![bgjk_fixed(&corners, &far), true];
+		assert_eq![bgjk_fixed(&corners, &[FixVec3(max, max, max)]), true];
+		assert_eq![bgjk_fixed(&corners[..4], &[FixVec3(0, 0, max)]), false];
+		let sliver: [FixVec3; 3] =
+			[FixVec3(-max, -max, -max), FixVec3(max, max, max - 1), FixVec3(max, -max, 0)];
+		assert_eq![bgjk_fixed(&sliver, &[FixVec3(max, max, max)]), false];
+		assert_eq![bgjk_fixed(&sliver, &[FixVec3(0, 0, -1)]), true];
+	}
+
+	// A step of a linear congruential generator, the top bits shifted down
+	// by `shift` so components span every magnitude up to `FixVec3::MAX`
+	fn random(state: &mut u64, shift: u32) -> i64 {
+		*state = state.wrapping_mul(6_364_136_223_846_793_005)
+			.wrapping_add(1_442_695_040_888_963_407);
+		(*state as i64) >> (shift + 4)
+	}
+
+	fn random_hull(state: &mut u64, shift: u32, len: u64) -> Vec<FixVec3> {
+		let mut next = || random(state, shift);
+		let mut point = || FixVec3(next(), next(), next());
+		let center = point();
+		(0..len).map(|_| center + point()).collect()
+	}
+
+	#[test]
+	fn golden_hash() {
+		// A fixed pseudo-random batch of hull pairs, the results hashed with
+		// FNV-1a. Any change to the answers on any platform changes the hash.
+		let mut state: u64 = 0x853c_49e6_748f_ea9b;
+		let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+		let mut hits = 0;
+		for round in 0..4000 {
+			let shift = round % 57;
+			let len = 1 + state % 8;
+			let hull1 = random_hull(&mut state, shift, len);
+			let hull2 = random_hull(&mut state, shift, 9 - len);
+			let hit = bgjk_fixed(&hull1, &hull2);
+			hits += hit as usize;
+			hash = (hash ^ hit as u64).wrapping_mul(0x0000_0100_0000_01b3);
+		}
+		assert![hits > 400 && hits < 3600];
+		assert_eq![hash, 0x2437_fe9c_c6e0_cd26];
+	}
+}
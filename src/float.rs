@@ -0,0 +1,113 @@
+//! The scalar types a `Vec3` can be built from.
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Floating point scalar of a `Vec3`
+///
+/// Implemented for `f32` and `f64`, which lets `bgjk` and the vector math run
+/// in either precision. The methods mirror the inherent methods of the
+/// primitive types of the same name.
+pub trait Float: Copy + PartialOrd + fmt::Debug + fmt::Display + fmt::LowerExp +
+	Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> +
+	Neg<Output = Self> {
+	/// Zero
+	const ZERO: Self;
+	/// One
+	const ONE: Self;
+	/// The difference between one and the next larger value
+	const EPSILON: Self;
+	/// Positive infinity
+	const INFINITY: Self;
+	/// Convert from `f32`, rounding to the nearest value if necessary
+	fn from_f32(value: f32) -> Self;
+	/// The square root
+	fn sqrt(self) -> Self;
+	/// The absolute value
+	fn abs(self) -> Self;
+	/// The minimum, ignoring NaN
+	fn min(self, other: Self) -> Self;
+	/// The maximum, ignoring NaN
+	fn max(self, other: Self) -> Self;
+	/// Restrict to the interval `[lo, hi]`
+	fn clamp(self, lo: Self, hi: Self) -> Self;
+	/// The sign, `1` or `-1`
+	fn signum(self) -> Self;
+	/// The magnitude of `self` with the sign of `sign`
+	fn copysign(self, sign: Self) -> Self;
+	/// The sine and cosine of an angle in radians
+	fn sin_cos(self) -> (Self, Self);
+	/// The arc cosine in radians
+	fn acos(self) -> Self;
+	/// Whether the value is neither infinite nor NaN
+	fn is_finite(self) -> bool;
+	/// Whether the value is NaN
+	fn is_nan(self) -> bool;
+	/// The largest integer less than or equal to the value
+	fn floor(self) -> Self;
+	/// Convert to `f64`, which holds every value exactly
+	fn to_f64(self) -> f64;
+	/// The bit pattern, widened to 64 bits
+	fn to_bits(self) -> u64;
+}
+
+macro_rules! impl_float {
+	($t:ident) => {
+		impl Float for $t {
+			const ZERO: $t = 0.0;
+			const ONE: $t = 1.0;
+			const EPSILON: $t = $t::EPSILON;
+			const INFINITY: $t = $t::INFINITY;
+			#[allow(clippy::unnecessary_cast)]
+			fn from_f32(value: f32) -> $t {
+				value as $t
+			}
+			fn sqrt(self) -> $t {
+				$t::sqrt(self)
+			}
+			fn abs(self) -> $t {
+				$t::abs(self)
+			}
+			fn min(self, other: $t) -> $t {
+				$t::min(self, other)
+			}
+			fn max(self, other: $t) -> $t {
+				$t::max(self, other)
+			}
+			fn clamp(self, lo: $t, hi: $t) -> $t {
+				$t::clamp(self, lo, hi)
+			}
+			fn signum(self) -> $t {
+				$t::signum(self)
+			}
+			fn copysign(self, sign: $t) -> $t {
+				$t::copysign(self, sign)
+			}
+			fn sin_cos(self) -> ($t, $t) {
+				$t::sin_cos(self)
+			}
+			fn acos(self) -> $t {
+				$t::acos(self)
+			}
+			fn is_finite(self) -> bool {
+				$t::is_finite(self)
+			}
+			fn is_nan(self) -> bool {
+				$t::is_nan(self)
+			}
+			fn floor(self) -> $t {
+				$t::floor(self)
+			}
+			#[allow(clippy::unnecessary_cast)]
+			fn to_f64(self) -> f64 {
+				self as f64
+			}
+			#[allow(clippy::unnecessary_cast)]
+			fn to_bits(self) -> u64 {
+				$t::to_bits(self) as u64
+			}
+		}
+	};
+}
+
+impl_float!(f32);
+impl_float!(f64);
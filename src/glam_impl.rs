@@ -0,0 +1,106 @@
+//! Conversions between `Vec3` and the `glam` vector types.
+use std::{mem, slice};
+use super::{Vec3, bgjk};
+
+// `bgjk_glam` reinterprets `glam::Vec3` slices, which needs identical layouts.
+// `glam::Vec3A` is padded to 16 bytes and must be converted per element.
+const _: () = assert![mem::size_of::<glam::Vec3>() == mem::size_of::<Vec3>()];
+const _: () = assert![mem::align_of::<glam::Vec3>() == mem::align_of::<Vec3>()];
+
+impl From<glam::Vec3> for Vec3 {
+	fn from(vector: glam::Vec3) -> Vec3 {
+		Vec3(vector.x, vector.y, vector.z)
+	}
+}
+
+impl From<Vec3> for glam::Vec3 {
+	fn from(vector: Vec3) -> glam::Vec3 {
+		glam::Vec3::new(vector.0, vector.1, vector.2)
+	}
+}
+
+impl From<glam::Vec3A> for Vec3 {
+	fn from(vector: glam::Vec3A) -> Vec3 {
+		Vec3(vector.x, vector.y, vector.z)
+	}
+}
+
+impl From<Vec3> for glam::Vec3A {
+	fn from(vector: Vec3) -> glam::Vec3A {
+		glam::Vec3A::new(vector.0, vector.1, vector.2)
+	}
+}
+
+fn as_hull(points: &[glam::Vec3]) -> &[Vec3] {
+	// Safety: both types are `repr(C)` structs of three `f32` in the order
+	// x, y, z, with equal size and alignment as asserted above
+	unsafe { slice::from_raw_parts(points.as_ptr() as *const Vec3, points.len()) }
+}
+
+/// The BGJK algorithm on `glam::Vec3` hulls, see `bgjk`
+///
+/// The hulls are reinterpreted in place, so no conversion or allocation
+/// takes place. There is no `glam::Vec3A` variant since its padding makes
+/// that impossible, convert those with `Vec3::from` instead.
+pub fn bgjk_glam(hull1: &[glam::Vec3], hull2: &[glam::Vec3]) -> bool {
+	bgjk(as_hull(hull1), as_hull(hull2))
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+
+	use std::f32;
+	use std::f32::consts::PI;
+	use glam;
+	use super::super::{Vec3, bgjk, bgjk_glam};
+
+	fn unit_cube() -> Vec<glam::Vec3> {
+		(0..8)
+			.map(|i| glam::Vec3::new((i & 1) as f32, (i >> 1 & 1) as f32, (i >> 2) as f32))
+			.collect()
+	}
+
+	fn circle(offset: glam::Vec3) -> Vec<glam::Vec3> {
+		(0..100)
+			.map(|i| {
+				let radian = i as f32 / 100.0 * 2.0 * PI;
+				glam::Vec3::new(radian.cos(), radian.sin(), 0.0) + offset
+			})
+			.collect()
+	}
+
+	#[test]
+	fn round_trip() {
+		let v = Vec3(1.0, -2.0, 3.5);
+		let g: glam::Vec3 = v.into();
+		assert_eq![(g.x, g.y, g.z), (1.0, -2.0, 3.5)];
+		assert_eq![Vec3::from(g), v];
+		let a: glam::Vec3A = v.into();
+		assert_eq![(a.x, a.y, a.z), (1.0, -2.0, 3.5)];
+		assert_eq![Vec3::from(a), v];
+	}
+
+	fn check(a: &[glam::Vec3], b: &[glam::Vec3], expected: bool) {
+		let native_a: Vec<Vec3> = a.iter().map(|v| Vec3::from(*v)).collect();
+		let native_b: Vec<Vec3> = b.iter().map(|v| Vec3::from(*v)).collect();
+		assert_eq![bgjk_glam(a, b), bgjk(&native_a, &native_b)];
+		assert_eq![bgjk_glam(a, b), expected];
+		let aligned: Vec<Vec3> = a.iter().map(|v| Vec3::from(glam::Vec3A::from(*v))).collect();
+		assert_eq![bgjk(&aligned, &native_b), expected];
+	}
+
+	#[test]
+	fn same_as_native() {
+		let cube = unit_cube();
+		let moved = |offset: glam::Vec3| -> Vec<glam::Vec3> { cube.iter().map(|v| *v + offset).collect() };
+		let eps = f32::EPSILON;
+		check(&cube, &moved(glam::Vec3::ONE), true);
+		check(&cube, &moved(glam::Vec3::new(1.0, 1.0, 1.0 + eps)), false);
+		check(&cube, &moved(glam::Vec3::splat(-0.5)), true);
+		check(&circle(glam::Vec3::ZERO), &circle(glam::Vec3::new(0.5, 0.0, 0.0)), true);
+		check(&circle(glam::Vec3::ZERO), &circle(glam::Vec3::new(2.0 + 2.0 * eps, 0.0, 0.0)), false);
+		check(&circle(glam::Vec3::ZERO), &circle(glam::Vec3::new(0.0, 0.0, eps)), false);
+	}
+
+}
@@ -0,0 +1,324 @@
+//! Hulls checked once when built, rather than trusted on every call.
+use std::error;
+use std::fmt;
+use std::ops::Deref;
+use super::{Float, SupportMap, Vector3, bgjk, farthest};
+
+/// Why `ConvexHull::new` refused a set of points
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HullError {
+	/// There were no points
+	Empty,
+	/// The point at this index has a NaN or infinite coordinate
+	NonFinite(usize),
+}
+
+impl fmt::Display for HullError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			HullError::Empty => write!(f, "a hull needs at least one point"),
+			HullError::NonFinite(index) => write!(f, "point {} of the hull is not finite", index),
+		}
+	}
+}
+
+impl error::Error for HullError {}
+
+/// Why `try_bgjk` refused a pair of hulls
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BgjkError {
+	/// The point at `index` of the hull has a NaN or infinite coordinate,
+	/// `hull` being 1 for the first hull and 2 for the second
+	NonFinite {
+		/// Which hull the point is in
+		hull: usize,
+		/// The index of the point in the hull
+		index: usize,
+	},
+}
+
+impl fmt::Display for BgjkError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			BgjkError::NonFinite { hull, index } => {
+				write!(f, "point {} of hull {} is not finite", index, hull)
+			}
+		}
+	}
+}
+
+impl error::Error for BgjkError {}
+
+/// The BGJK algorithm, refusing hulls with a point that is not finite, see
+/// `bgjk`
+///
+/// A NaN coordinate makes every comparison with it false, so `bgjk` quietly
+/// answers anything for such hulls, and only panics about them in debug
+/// builds. This checks every point once beforehand, stopping at the first
+/// NaN or infinite one, the first hull before the second, and otherwise
+/// gives the answer of `bgjk`.
+///
+/// ```
+/// use bgjk::{try_bgjk, BgjkError, Vec3};
+///
+/// let triangle = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// assert_eq![try_bgjk(&triangle, &[Vec3::new(0.2, 0.2, 0.0)]), Ok(true)];
+/// let corrupted = [Vec3::new(0.2, 0.2, 0.0), Vec3::new(f32::NAN, 0.0, 0.0)];
+/// assert_eq![try_bgjk(&triangle, &corrupted), Err(BgjkError::NonFinite { hull: 2, index: 1 })];
+/// ```
+pub fn try_bgjk<T: Float>(hull1: &[Vector3<T>], hull2: &[Vector3<T>]) -> Result<bool, BgjkError> {
+	for (hull, points) in [hull1, hull2].iter().enumerate() {
+		if let Some(index) = points.iter().position(|point| !point.is_finite()) {
+			return Err(BgjkError::NonFinite { hull: hull + 1, index });
+		}
+	}
+	Ok(bgjk(hull1, hull2))
+}
+
+/// The points of a convex hull, checked to be usable by `bgjk`
+///
+/// Dereferences to the slice of its points, so it goes wherever a hull
+/// does, `bgjk` included, and is a `SupportMap` for `bgjk_support`. Like
+/// for `bgjk`, the points need not all be corners of the hull, points
+/// inside it are only a waste of time.
+///
+/// ```
+/// use bgjk::{bgjk, ConvexHull, HullError, Vec3};
+///
+/// let points = vec![Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// let mut triangle = ConvexHull::new(points).unwrap();
+/// triangle.translate(Vec3::new(0.0, 0.0, 1.0));
+/// assert![!bgjk(&triangle, &[Vec3::new(0.2, 0.2, 0.0)])];
+/// assert_eq![ConvexHull::<f32>::new(vec![]).unwrap_err(), HullError::Empty];
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConvexHull<T = f32> {
+	points: Vec<Vector3<T>>,
+}
+
+impl<T: Float> ConvexHull<T> {
+	/// Take the points of a hull, refusing none at all or any that are not
+	/// finite, the first of which the error gives the index of
+	pub fn new(points: Vec<Vector3<T>>) -> Result<ConvexHull<T>, HullError> {
+		if points.is_empty() {
+			return Err(HullError::Empty);
+		}
+		if let Some(index) = points.iter().position(|point| !point.is_finite()) {
+			return Err(HullError::NonFinite(index));
+		}
+		Ok(ConvexHull { points })
+	}
+
+	/// Take the points of a hull as they are, for points already known to
+	/// be finite. Empty or non-finite points give the answers `bgjk` gives
+	/// for them.
+	pub fn from_points_unchecked(points: Vec<Vector3<T>>) -> ConvexHull<T> {
+		ConvexHull { points }
+	}
+
+	/// The points of the hull
+	pub fn points(&self) -> &[Vector3<T>] {
+		&self.points
+	}
+
+	/// Give back the points of the hull
+	pub fn into_points(self) -> Vec<Vector3<T>> {
+		self.points
+	}
+
+	/// How many points the hull has
+	pub fn len(&self) -> usize {
+		self.points.len()
+	}
+
+	/// Whether the hull has no points, only possible when built unchecked
+	pub fn is_empty(&self) -> bool {
+		self.points.is_empty()
+	}
+
+	/// Move every point by `by`
+	///
+	/// The points stay finite, as `new` checks, only while `by` is finite
+	/// and no coordinate overflows. Debug builds panic on a point that
+	/// turns non-finite here.
+	pub fn translate(&mut self, by: Vector3<T>) {
+		for point in &mut self.points {
+			let moved = *point + by;
+			debug_assert![moved.is_finite() || !point.is_finite(),
+			              "ConvexHull::translate made a point non-finite"];
+			*point = moved;
+		}
+	}
+
+	/// Turn every point by `rotation`, given as its columns, then move it by
+	/// `translation`, placing the hull like `bgjk_transformed` and
+	/// `Transformed` do
+	///
+	/// Like `translate`, this keeps the points finite only for a finite
+	/// rotation and translation that overflow no coordinate, and debug
+	/// builds panic otherwise.
+	pub fn transform(&mut self, rotation: &[Vector3<T>; 3], translation: Vector3<T>) {
+		for point in &mut self.points {
+			let local = *point;
+			let placed = rotation[0] * local.0 + rotation[1] * local.1 + rotation[2] * local.2 +
+			             translation;
+			debug_assert![placed.is_finite() || !local.is_finite(),
+			              "ConvexHull::transform made a point non-finite"];
+			*point = placed;
+		}
+	}
+}
+
+impl<T> Deref for ConvexHull<T> {
+	type Target = [Vector3<T>];
+
+	fn deref(&self) -> &[Vector3<T>] {
+		&self.points
+	}
+}
+
+impl<T> AsRef<[Vector3<T>]> for ConvexHull<T> {
+	fn as_ref(&self) -> &[Vector3<T>] {
+		&self.points
+	}
+}
+
+impl<T: Float> SupportMap<T> for ConvexHull<T> {
+	fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+		farthest(&self.points, direction).unwrap_or(Vector3::ZERO)
+	}
+
+	fn is_empty(&self) -> bool {
+		self.points.is_empty()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+
+	use std::f32::consts::PI;
+	use std::f32;
+	use super::super::{BgjkError, ConvexHull, HullError, Vec3, bgjk, bgjk_support, bgjk_transformed,
+	                   try_bgjk};
+	use super::super::test_util::{cube_at, rotation};
+
+	#[test]
+	fn refuses() {
+		assert_eq![ConvexHull::<f32>::new(vec![]).unwrap_err(), HullError::Empty];
+		let nan = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, f32::NAN, 0.0)].to_vec();
+		assert_eq![ConvexHull::new(nan).unwrap_err(), HullError::NonFinite(2)];
+		let infinite = pts![(f32::INFINITY, 0.0, 0.0), (0.0, 0.0, -f32::INFINITY)].to_vec();
+		assert_eq![ConvexHull::new(infinite).unwrap_err(), HullError::NonFinite(0)];
+		assert_eq![HullError::NonFinite(3).to_string(), "point 3 of the hull is not finite"];
+		let point = ConvexHull::new(vec![Vec3(1.0, 2.0, 3.0)]).unwrap();
+		assert_eq![point.points(), &[Vec3(1.0, 2.0, 3.0)]];
+		assert_eq![point.len(), 1];
+		let unchecked = ConvexHull::<f32>::from_points_unchecked(vec![]);
+		assert![unchecked.is_empty()];
+	}
+
+	#[test]
+	fn same_as_slices() {
+		let cube = cube_at(Vec3::ZERO);
+		let hull = ConvexHull::new(cube.clone()).unwrap();
+		let mut hits = 0;
+		for i in 0..100 {
+			let (sin, cos) = (i as f32 * 0.61).sin_cos();
+			let by = Vec3(cos, sin, (i % 7) as f32 * 0.3 - 1.0) * (i % 4) as f32 * 0.7;
+			let other = cube_at(by);
+			let expected = bgjk(&cube, &other);
+			let moved = ConvexHull::new(other.clone()).unwrap();
+			assert_eq![bgjk(&hull, &other), expected];
+			assert_eq![bgjk(&hull, &moved), expected];
+			assert_eq![bgjk(hull.as_ref(), moved.points()), expected];
+			assert_eq![bgjk_support(&hull, &moved), expected];
+			hits += expected as usize;
+		}
+		assert![hits > 0 && hits < 100];
+	}
+
+	#[test]
+	fn transforms_compose() {
+		let identity = [Vec3::X, Vec3::Y, Vec3::Z];
+		let mut stepped = ConvexHull::new(cube_at(Vec3::ZERO)).unwrap();
+		stepped.translate(Vec3(1.0, 0.0, 0.0));
+		stepped.translate(Vec3(0.0, -2.0, 0.5));
+		let mut once = ConvexHull::new(cube_at(Vec3::ZERO)).unwrap();
+		once.transform(&identity, Vec3(1.0, -2.0, 0.5));
+		assert_eq![stepped.points(), once.points()];
+		assert_eq![stepped.into_points(), cube_at(Vec3(1.0, -2.0, 0.5))];
+
+		let target = cube_at(Vec3(0.4, 0.3, 0.2));
+		for (i, axis) in [Vec3::Z, Vec3(1.0, 1.0, 0.0), Vec3(0.3, -0.8, 0.5)].iter().enumerate() {
+			let turn = rotation(*axis, PI / 3.0 + i as f32);
+			let by = Vec3(0.5, -0.25, 0.75);
+			let mut placed = ConvexHull::new(cube_at(Vec3::ZERO)).unwrap();
+			placed.transform(&turn, Vec3::ZERO);
+			placed.translate(by);
+			let mut direct = ConvexHull::new(cube_at(Vec3::ZERO)).unwrap();
+			direct.transform(&turn, by);
+			for (a, b) in placed.iter().zip(direct.iter()) {
+				assert![a.approx_eq(*b, 1e-6)];
+			}
+			let expected = bgjk_transformed(&cube_at(Vec3::ZERO), &turn, by,
+			                                &target, &identity, Vec3::ZERO);
+			assert_eq![bgjk(&direct, &target), expected];
+		}
+	}
+
+	#[test]
+	#[cfg(debug_assertions)]
+	#[should_panic(expected = "ConvexHull::translate made a point non-finite")]
+	fn translate_overflow() {
+		let mut hull = ConvexHull::new(cube_at(Vec3::ONE * f32::MAX)).unwrap();
+		hull.translate(Vec3::X * f32::MAX);
+	}
+
+	#[test]
+	#[cfg(debug_assertions)]
+	#[should_panic(expected = "ConvexHull::transform made a point non-finite")]
+	fn transform_non_finite() {
+		let mut hull = ConvexHull::new(cube_at(Vec3::ZERO)).unwrap();
+		hull.transform(&[Vec3::X, Vec3::Y, Vec3::Z], Vec3(f32::NAN, 0.0, 0.0));
+	}
+
+	#[test]
+	fn try_refuses() {
+		let cube = cube_at(Vec3::ZERO);
+		let error = |hull, index| Err(BgjkError::NonFinite { hull, index });
+		for &bad in &[f32::NAN, f32::INFINITY, -f32::INFINITY] {
+			for axis in 0..3 {
+				let mut corrupted = cube_at(Vec3::ONE * 0.5);
+				let mut point = [0.5; 3];
+				point[axis] = bad;
+				corrupted[5] = Vec3(point[0], point[1], point[2]);
+				assert_eq![try_bgjk(&corrupted, &cube), error(1, 5)];
+				assert_eq![try_bgjk(&cube, &corrupted), error(2, 5)];
+				assert_eq![try_bgjk(&corrupted, &corrupted), error(1, 5)];
+			}
+		}
+		// The first point that is not finite
+		let mut twice = cube.clone();
+		twice[6].2 = f32::NAN;
+		twice[3].0 = f32::INFINITY;
+		assert_eq![try_bgjk(&[], &twice), error(2, 3)];
+		assert_eq![error(2, 3).unwrap_err().to_string(), "point 3 of hull 2 is not finite"];
+	}
+
+	#[test]
+	fn try_same_as_bgjk() {
+		let cube = cube_at(Vec3::ZERO);
+		let mut hits = 0;
+		for i in 0..100 {
+			let (sin, cos) = (i as f32 * 0.61).sin_cos();
+			let by = Vec3(cos, sin, (i % 7) as f32 * 0.3 - 1.0) * (i % 4) as f32 * 0.7;
+			let other = cube_at(by);
+			let expected = bgjk(&cube, &other);
+			assert_eq![try_bgjk(&cube, &other), Ok(expected)];
+			assert_eq![try_bgjk(&other, &cube), Ok(expected)];
+			hits += expected as usize;
+		}
+		assert![hits > 0 && hits < 100];
+		assert_eq![try_bgjk(&[], &cube), Ok(bgjk(&[], &cube))];
+	}
+}
@@ -0,0 +1,245 @@
+//! Final simplices kept between frames, for searches that pick up where the
+//! last one ended.
+use super::{Corner, Float, GjkConfig, Seed, Vector3, dcross3, either_empty, gjk_resume,
+            hull_support};
+
+/// The vertex indices of the simplex a pair of hulls last ended with, for
+/// `bgjk_incremental`
+///
+/// Starts out empty, and is emptied again by `clear`, such as when either
+/// hull is swapped for another.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SimplexCache {
+	pairs: [(usize, usize); 4],
+	len: usize,
+}
+
+impl SimplexCache {
+	/// A cache holding no simplex
+	pub fn new() -> SimplexCache {
+		SimplexCache::default()
+	}
+
+	/// For each corner of the simplex kept, none to four of them, the
+	/// indices of the points of the first and the second hull it is the
+	/// difference of, like `SmallSimplexIds::pairs`
+	pub fn pairs(&self) -> &[(usize, usize)] {
+		&self.pairs[..self.len]
+	}
+
+	/// Forget the simplex, so the next search starts cold
+	pub fn clear(&mut self) {
+		self.len = 0;
+	}
+}
+
+// A seed of the search with the vertex indices of its corners
+type Start<T> = Seed<T, (usize, usize)>;
+
+// Where to start the search from the kept simplex, rebuilt from the
+// points the indices now give: `Ok(None)` where the tetrahedron still
+// holds the origin and the hulls still intersect, `Err` with a direction to
+// start along cold where no simplex is kept or it is out of range of the
+// hulls or flat
+fn seed<T: Float>(hull1: &[Vector3<T>],
+                  hull2: &[Vector3<T>],
+                  cache: &SimplexCache)
+                  -> Result<Option<Start<T>>, Vector3<T>> {
+	let mut corners = [Corner { point: Vector3::ZERO, from: (0, 0) }; 4];
+	for (corner, &(i, j)) in corners.iter_mut().zip(cache.pairs()) {
+		match (hull1.get(i), hull2.get(j)) {
+			(Some(&a), Some(&b)) => *corner = Corner { point: a - b, from: (i, j) },
+			_ => return Err(Vector3::ONE),
+		}
+	}
+	let triangle = |b: Corner<T, _>, c: Corner<T, _>, d: Corner<T, _>| {
+		let normal = (c.point - b.point).cross(d.point - b.point);
+		if normal.dot(-b.point) < T::ZERO {
+			Seed { corners: [b, d, c], len: 3, direction: -normal }
+		} else {
+			Seed { corners: [b, c, d], len: 3, direction: normal }
+		}
+	};
+	let points = corners.map(|corner| corner.point);
+	match cache.len {
+		2 => {
+			let direction = dcross3(points[1] - points[0], -points[0]);
+			if direction == Vector3::ZERO || !direction.is_finite() {
+				return Err(-points[0]);
+			}
+			let [b, c, d, _] = corners;
+			Ok(Some(Seed { corners: [b, c, d], len: 2, direction }))
+		}
+		3 => {
+			let seed = triangle(corners[0], corners[1], corners[2]);
+			if seed.direction == Vector3::ZERO || !seed.direction.is_finite() {
+				return Err(-points[0]);
+			}
+			Ok(Some(seed))
+		}
+		4 => {
+			// Each face with the corner opposite it
+			let faces = [(0, 1, 2, 3), (0, 1, 3, 2), (0, 2, 3, 1), (1, 2, 3, 0)];
+			let mut nearest: Option<(T, Start<T>)> = None;
+			for &(b, c, d, opposite) in &faces {
+				let normal = (points[c] - points[b]).cross(points[d] - points[b]);
+				let length = normal.length();
+				let inward = normal.dot(points[opposite] - points[b]);
+				if length == T::ZERO || !length.is_finite() || inward == T::ZERO {
+					return Err(-points[0]);
+				}
+				// How far the origin is in front of the face, outward
+				let outward = if inward > T::ZERO { -normal } else { normal };
+				let ahead = outward.dot(-points[b]) / length;
+				if ahead >= T::ZERO && nearest.as_ref().is_none_or(|&(most, _)| ahead > most) {
+					nearest = Some((ahead, triangle(corners[b], corners[c], corners[d])));
+				}
+			}
+			Ok(nearest.map(|(_, seed)| seed))
+		}
+		1 => Err(-points[0]),
+		_ => Err(Vector3::ONE),
+	}
+}
+
+// `bgjk_incremental`, also giving the number of support points computed
+fn search<T: Float>(hull1: &[Vector3<T>],
+                    hull2: &[Vector3<T>],
+                    cache: &mut SimplexCache) -> (bool, u32) {
+	if either_empty(hull1, hull2) {
+		cache.clear();
+		return (false, 0);
+	}
+	let (initial_direction, start) = match seed(hull1, hull2, cache) {
+		Ok(Some(start)) => (Vector3::ONE, Some(start)),
+		Ok(None) => return (true, 0),
+		Err(direction) => (direction, None),
+	};
+	let config = GjkConfig { initial_direction, ..GjkConfig::default() };
+	let search = gjk_resume(|direction| hull_support(hull1, hull2, direction),
+	                        &config,
+	                        start,
+	                        |_| ());
+	cache.pairs = search.corners.map(|corner| corner.from);
+	cache.len = search.len;
+	(search.hit(&config), search.support_calls)
+}
+
+/// The BGJK algorithm starting from the simplex the hulls last ended with,
+/// see `bgjk`
+///
+/// The indices in `cache` are looked up in the hulls as they are now, so
+/// hulls that moved between calls start from nearly the simplex they will
+/// end with. A tetrahedron still around the origin answers without any
+/// support point, and a face or edge still facing it mostly takes one to
+/// confirm the hulls are apart. An empty cache, indices out of range of the
+/// hulls and flat simplices start cold like `bgjk` does. The answer is that
+/// of `bgjk` either way, and the simplex it ends with is kept in `cache`,
+/// which an empty hull leaves empty.
+///
+/// ```
+/// use bgjk::{bgjk, bgjk_incremental, SimplexCache, Vec3};
+///
+/// let hull1 = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0),
+///              Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 0.0, 1.0)];
+/// let mut cache = SimplexCache::new();
+/// for frame in 0..10 {
+///     let by = Vec3::new(frame as f32 * 0.1 - 0.8, 0.1, 0.1);
+///     let hull2: Vec<Vec3> = hull1.iter().map(|v| *v + by).collect();
+///     assert_eq![bgjk_incremental(&hull1, &hull2, &mut cache), bgjk(&hull1, &hull2)];
+/// }
+/// assert![!cache.pairs().is_empty()];
+/// ```
+pub fn bgjk_incremental<T: Float>(hull1: &[Vector3<T>],
+                                  hull2: &[Vector3<T>],
+                                  cache: &mut SimplexCache)
+                                  -> bool {
+	debug_assert![hull1.iter().chain(hull2).all(Vector3::is_finite),
+	              "bgjk called with a non-finite point in a hull"];
+	search(hull1, hull2, cache).0
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::search;
+	use super::super::{SimplexCache, Vec3, bgjk, bgjk_incremental, bgjk_stats,
+	                   bgjk_witness_indices};
+	use super::super::test_util::cube_at;
+
+	// A cube and a jittered octahedron slowly orbiting through it, in frame
+	// `frame` of sequence `sequence`
+	fn hulls(sequence: usize, frame: usize) -> (Vec<Vec3>, Vec<Vec3>) {
+		let t = frame as f32 * 0.02 + sequence as f32 * 0.9;
+		let center = Vec3(t.cos(), t.sin(), (t * 0.3).sin()) * (0.9 + (sequence % 5) as f32 * 0.4);
+		let octahedron = [Vec3::X, -Vec3::X, Vec3::Y, -Vec3::Y, Vec3::Z, -Vec3::Z]
+			.iter()
+			.enumerate()
+			.map(|(i, corner)| {
+				let jitter = ((frame * 7 + i * 13) % 17) as f32 * 0.001;
+				center + *corner * (0.5 + jitter)
+			})
+			.collect();
+		(cube_at(Vec3::ONE * -0.5), octahedron)
+	}
+
+	#[test]
+	fn same_as_cold() {
+		let mut hits = 0;
+		for sequence in 0..20 {
+			let mut cache = SimplexCache::new();
+			for frame in 0..100 {
+				let (hull1, hull2) = hulls(sequence, frame);
+				let expected = bgjk(&hull1, &hull2);
+				assert_eq![bgjk_incremental(&hull1, &hull2, &mut cache), expected];
+				assert_eq![bgjk_incremental(&hull2, &hull1, &mut SimplexCache::new()), expected];
+				hits += expected as usize;
+			}
+		}
+		assert![hits > 0 && hits < 2000];
+	}
+
+	#[test]
+	fn fewer_support_points_warm() {
+		let (mut warm, mut cold) = (0, 0);
+		for sequence in 0..20 {
+			let mut cache = SimplexCache::new();
+			for frame in 0..100 {
+				let (hull1, hull2) = hulls(sequence, frame);
+				warm += search(&hull1, &hull2, &mut cache).1;
+				cold += bgjk_stats(&hull1, &hull2).1.support_calls;
+			}
+		}
+		assert![warm * 2 < cold];
+	}
+
+	#[test]
+	fn stale_indices() {
+		// Indices from a larger hull, out of range of the smaller one
+		let big = cube_at(Vec3::ZERO);
+		let (_, ids) = bgjk_witness_indices(&big, &cube_at(Vec3::ONE * 0.5));
+		assert![ids.pairs().iter().any(|&(i, _)| i >= 4)];
+		let mut cache = SimplexCache::new();
+		bgjk_incremental(&big, &cube_at(Vec3::ONE * 0.5), &mut cache);
+		assert_eq![cache.pairs(), ids.pairs()];
+		let small = &big[..4];
+		let other = cube_at(Vec3(0.5, 0.5, -0.5));
+		assert_eq![bgjk_incremental(small, &other, &mut cache), bgjk(small, &other)];
+		assert![cache.pairs().iter().all(|&(i, j)| i < 4 && j < 8)];
+
+		// A tetrahedron flattened into a square since it was kept
+		let mut cache = SimplexCache::new();
+		let tetrahedron = [Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::Z];
+		let point = [Vec3::ONE * 0.1];
+		assert![bgjk_incremental(&tetrahedron, &point, &mut cache)];
+		assert_eq![cache.pairs().len(), 4];
+		let flat = [Vec3::ZERO, Vec3::X, Vec3::Y, Vec3(1.0, 1.0, 0.0)];
+		assert![!bgjk_incremental(&flat, &point, &mut cache)];
+		assert![bgjk_incremental(&flat, &[Vec3(0.5, 0.5, 0.0)], &mut cache)];
+
+		let mut empty = SimplexCache::new();
+		assert_eq![bgjk_incremental(&[], &point, &mut empty), bgjk(&[], &point)];
+		empty.clear();
+		assert![empty.pairs().is_empty()];
+	}
+}
@@ -4,401 +4,1905 @@
 #![deny(missing_docs)]
 //! Defines 3-space and implements the boolean GJK (BGJK) algorithm
 //! for intersection testing.
-use std::ops::{Neg, Sub};
+//!
+//! # Features
+//!
+//! * `serde`: `Serialize` and `Deserialize` for `Vec3`, as `[x, y, z]`.
+//! * `bytemuck`: `Pod` and `Zeroable` for `Vec3`, for casting vertex buffers.
+//! * `mint`: conversions between `Vec3` and `mint::Point3<f32>` or
+//!   `mint::Vector3<f32>`.
+//! * `glam`: conversions between `Vec3` and `glam::Vec3` or `glam::Vec3A`,
+//!   and `bgjk_glam` for testing `glam::Vec3` hulls without copying.
+//! * `nalgebra`: conversions between `Vec3` and `nalgebra::Point3<f32>` or
+//!   `nalgebra::Vector3<f32>`, and `bgjk_na` for hulls placed by isometries.
+//! * `cgmath`: conversions between `Vec3` and `cgmath::Point3<f32>` or
+//!   `cgmath::Vector3<f32>`.
+//! * `proptest`: `Arbitrary` for `Vec3` and strategies generating hulls, for
+//!   property testing code built on `bgjk`.
+//! * `rayon`: `bgjk_batch_par` and `bgjk_batch_indexed_par`, testing batches
+//!   of hull pairs across threads.
+#[cfg(feature = "bytemuck")]
+extern crate bytemuck;
+#[cfg(feature = "cgmath")]
+extern crate cgmath;
+#[cfg(feature = "glam")]
+extern crate glam;
+#[cfg(feature = "nalgebra")]
+extern crate nalgebra;
+#[cfg(feature = "proptest")]
+extern crate proptest;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "mint")]
+extern crate mint;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+
+use std::cell::Cell;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::Sum;
+use std::mem;
+use std::ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub,
+               SubAssign};
+
+// Ahead of the other modules, so their tests can use its macros
+#[cfg(test)]
+#[macro_use]
+mod test_util;
+mod aabb;
+mod batch;
+#[cfg(feature = "bytemuck")]
+mod bytemuck_impl;
+mod cache;
+mod centroid;
+#[cfg(feature = "cgmath")]
+mod cgmath_impl;
+mod config;
+mod distance;
+mod dvec3;
+mod enclosing;
+mod fixed;
+mod float;
+#[cfg(feature = "glam")]
+mod glam_impl;
+mod hull;
+mod incremental;
+mod manifold;
+mod margin;
+#[cfg(feature = "nalgebra")]
+mod nalgebra_impl;
+mod penetration;
+mod planar;
+mod plane;
+mod point;
+#[cfg(feature = "proptest")]
+mod proptest_impl;
+mod quickhull;
+mod raycast;
+#[cfg(feature = "rayon")]
+mod rayon_impl;
+#[cfg(feature = "mint")]
+mod mint_impl;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod shape;
+mod stats;
+mod toi;
+mod topo;
+mod trace;
+mod transformed;
+mod triangle;
+mod vec2;
+mod volume;
+mod weld;
+
+pub use aabb::{Aabb, aabb_hits_hull};
+pub use batch::{HullPair, bgjk_batch, bgjk_batch_into, find_overlaps};
+pub use cache::{PairCache, bgjk_cached};
+pub use centroid::centroid;
+pub use config::{Boundary, EmptyHull, GjkConfig, OnLimit};
+pub use distance::{ClosestFeature, GjkResult, closest_feature, closest_point_on_hull,
+                   closest_points, common_point, contact_normal, gjk_distance, gjk_distance_squared,
+                   gjk_query, signed_distance, within_distance};
+pub use dvec3::{DVec3, bgjk_f64};
+pub use enclosing::bounding_sphere;
+pub use fixed::{FixVec3, bgjk_fixed};
+pub use float::Float;
+pub use hull::{BgjkError, ConvexHull, HullError, try_bgjk};
+pub use incremental::{SimplexCache, bgjk_incremental};
+pub use manifold::{ContactPoint, Manifold, contact_manifold};
+pub use margin::{bgjk_margin, sphere_hits_hull};
+pub use penetration::{Penetration, penetration};
+use penetration::{expand, has_volume};
+pub use planar::{Penetration2, bgjk2, farthest2, penetration2, support2};
+pub use plane::{Plane, PlaneSide, classify_hull, hull_intersects_halfspace};
+pub use point::point_in_hull;
+pub use quickhull::{convex_hull, hull_volume, is_convex_hull};
+pub use raycast::{CastHit, RayHit, raycast_hull, segment_entry, segment_hits_hull, shape_cast};
+pub use shape::{Capsule, Cone, Cuboid, Cylinder, Ellipsoid, Point, Segment, Sphere, SupportMap,
+                Transformed, Triangle, bgjk_support};
+pub use stats::{GjkStats, bgjk_stats};
+pub use toi::toi_linear;
+pub use topo::TopoHull;
+pub use trace::{GjkEnd, GjkStep, bgjk_trace};
+pub use transformed::bgjk_transformed;
+pub use triangle::triangle_hits_hull;
+pub use vec2::Vec2;
+pub use volume::overlap_volume_estimate;
+pub use weld::{dedup_points, dedup_points_in_place, weld_points};
+#[cfg(feature = "glam")]
+pub use glam_impl::bgjk_glam;
+#[cfg(feature = "nalgebra")]
+pub use nalgebra_impl::bgjk_na;
+#[cfg(feature = "proptest")]
+pub use proptest_impl::{CoordinateRange, hull_in_box, hull_on_sphere};
+#[cfg(feature = "rayon")]
+pub use rayon_impl::{bgjk_batch_indexed_par, bgjk_batch_par};
 
 /// Vector for use in the `bgjk` function
 ///
 /// Uses cartesian spatial dimensions in the order
 /// x, y, z.
+///
+/// Prefer `Vec3::new` and the named accessors (`x()`, `set_x()` etc.)
+/// over the positional tuple fields, which are kept for compatibility.
+///
+/// This is the single precision `Vector3`, see `DVec3` for hulls that need
+/// double precision. Being an alias, `Vec3` builds vectors like a tuple
+/// struct but cannot be matched as one, patterns name `Vector3` instead:
+///
+/// ```
+/// use bgjk::{Vec3, Vector3};
+///
+/// let Vector3(x, y, z) = Vec3(1.0, 2.0, 3.0);
+/// assert_eq![(x, y, z), (1.0, 2.0, 3.0)];
+/// ```
+pub type Vec3 = Vector3<f32>;
+
+/// Create a `Vec3` from its x, y and z components, like a tuple struct
+#[allow(non_snake_case)]
+pub const fn Vec3(x: f32, y: f32, z: f32) -> Vec3 {
+	Vector3(x, y, z)
+}
+
+/// Vector over any `Float` scalar, of which `Vec3` and `DVec3` are the
+/// single and double precision instances
+///
+/// The layout is guaranteed to be three consecutive components without
+/// padding, in the order x, y, z.
 #[derive(Clone, Copy, Debug, Default)]
-pub struct Vec3(pub f32, pub f32, pub f32);
+#[repr(C)]
+pub struct Vector3<T>(pub T, pub T, pub T);
+
+// The float views below rely on this layout
+const _: () = assert![mem::size_of::<Vec3>() == 3 * mem::size_of::<f32>()];
+const _: () = assert![mem::align_of::<Vec3>() == mem::align_of::<f32>()];
+
+/// Cartesian axis of a `Vec3`
+///
+/// Can be used to index a `Vec3` exhaustively, as opposed to a `usize`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Axis {
+	/// The first component
+	X,
+	/// The second component
+	Y,
+	/// The third component
+	Z,
+}
 
 impl Eq for Vec3 {}
 
-impl PartialEq for Vec3 {
-	fn eq(&self, other: &Vec3) -> bool {
+impl<T: Float> PartialEq for Vector3<T> {
+	fn eq(&self, other: &Vector3<T>) -> bool {
 		self.0 == other.0 && self.1 == other.1 && self.2 == other.2
 	}
 }
 
-impl Sub for Vec3 {
-	type Output = Vec3;
-	fn sub(self, right: Vec3) -> Self::Output {
-		Vec3(self.0 - right.0, self.1 - right.1, self.2 - right.2)
+/// Hashes the bit patterns of the components
+///
+/// Negative zero is hashed as positive zero to stay consistent with
+/// `PartialEq`. Vectors containing NaN are never equal to anything, not
+/// even themselves, so they can be inserted into a set but never found.
+impl Hash for Vec3 {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		// Adding zero turns -0.0 into 0.0 and leaves everything else alone
+		(self.0 + 0.0).to_bits().hash(state);
+		(self.1 + 0.0).to_bits().hash(state);
+		(self.2 + 0.0).to_bits().hash(state);
 	}
 }
 
-impl Vec3 {
-	fn dot(&self, right: Vec3) -> f32 {
-		self.0 * right.0 + self.1 * right.1 + self.2 * right.2
+// Implements the by-reference variants of a binary operator in terms of
+// the by-value implementation, so generic code over `&Vec3` works too.
+macro_rules! forward_ref_binop {
+	($imp:ident, $method:ident) => {
+		impl<'a, T: Float> $imp<&'a Vector3<T>> for Vector3<T> {
+			type Output = Vector3<T>;
+			fn $method(self, right: &'a Vector3<T>) -> Self::Output {
+				$imp::$method(self, *right)
+			}
+		}
+
+		impl<'a, T: Float> $imp<Vector3<T>> for &'a Vector3<T> {
+			type Output = Vector3<T>;
+			fn $method(self, right: Vector3<T>) -> Self::Output {
+				$imp::$method(*self, right)
+			}
+		}
+
+		impl<'a, 'b, T: Float> $imp<&'b Vector3<T>> for &'a Vector3<T> {
+			type Output = Vector3<T>;
+			fn $method(self, right: &'b Vector3<T>) -> Self::Output {
+				$imp::$method(*self, *right)
+			}
+		}
+	};
+}
+
+impl<T: Float> Add for Vector3<T> {
+	type Output = Vector3<T>;
+	fn add(self, right: Vector3<T>) -> Self::Output {
+		Vector3(self.0 + right.0, self.1 + right.1, self.2 + right.2)
+	}
+}
+
+forward_ref_binop!(Add, add);
+
+impl<T: Float> Sub for Vector3<T> {
+	type Output = Vector3<T>;
+	fn sub(self, right: Vector3<T>) -> Self::Output {
+		Vector3(self.0 - right.0, self.1 - right.1, self.2 - right.2)
 	}
+}
+
+forward_ref_binop!(Sub, sub);
 
-	fn ones() -> Vec3 {
-		Vec3(1.0, 1.0, 1.0)
+impl<T: Float> Mul<T> for Vector3<T> {
+	type Output = Vector3<T>;
+	fn mul(self, right: T) -> Self::Output {
+		Vector3(self.0 * right, self.1 * right, self.2 * right)
 	}
 }
 
-impl Neg for Vec3 {
+impl Mul<Vec3> for f32 {
 	type Output = Vec3;
-	fn neg(self) -> Self::Output {
-		Vec3(-self.0, -self.1, -self.2)
+	fn mul(self, right: Vec3) -> Self::Output {
+		right * self
 	}
 }
 
-/// The BGJK algorithm
+impl Mul<Vector3<f64>> for f64 {
+	type Output = Vector3<f64>;
+	fn mul(self, right: Vector3<f64>) -> Self::Output {
+		right * self
+	}
+}
+
+/// Divides every component by the scalar
 ///
-/// The Boolean-GJK algorithm gives us the answer to the question:
-/// "do these convex hulls intersect?"
-/// This algorithm takes two hulls. The ordering of the points is not
-/// important. All points are assumed to be on the surface of the hull.
-/// Having interior points should not affect the qualitative result of
-/// the algorithm, but may cause slight (very minor) degradation in
-/// performance. The algorithm is O(n+m), where n and m are the amount
-/// of points in hull1 and hull2 respectively.
-pub fn bgjk(hull1: &[Vec3], hull2: &[Vec3]) -> bool {
-	let mut sp = Vec3::ones();
-	let mut dp = Vec3::default();
-	let (mut ap, mut bp, mut cp);
-
-	cp = support(hull1, hull2, sp);
-	sp = -cp;
-	bp = support(hull1, hull2, sp);
-	if bp.dot(sp) < 0.0 {
-		return false;
+/// Division by zero follows IEEE 754 semantics and yields infinities (or
+/// NaN for zero components), use `Vec3::checked_div` to catch that case.
+impl<T: Float> Div<T> for Vector3<T> {
+	type Output = Vector3<T>;
+	fn div(self, right: T) -> Self::Output {
+		Vector3(self.0 / right, self.1 / right, self.2 / right)
 	}
-	sp = dcross3(cp - bp, -bp);
-	let mut w = 2;
+}
 
-	loop {
-		ap = support(hull1, hull2, sp);
-		if ap.dot(sp) < 0.0 {
-			return false;
-		} else if simplex(&mut ap, &mut bp, &mut cp, &mut dp, &mut sp, &mut w) {
-			return true;
+/// Indexes the x, y, and z components by 0, 1, and 2 respectively
+///
+/// Panics for any other index.
+impl<T: Float> Index<usize> for Vector3<T> {
+	type Output = T;
+	fn index(&self, index: usize) -> &T {
+		match index {
+			0 => &self.0,
+			1 => &self.1,
+			2 => &self.2,
+			_ => panic!("Vec3 index out of range: the index is {} but must be 0, 1, or 2", index),
 		}
 	}
 }
 
-// Todo clean up signature, this has to be fixed, sending 6 ptrs...
-fn simplex(ap: &mut Vec3,
-           bp: &mut Vec3,
-           cp: &mut Vec3,
-           dp: &mut Vec3,
-           sp: &mut Vec3,
-           w: &mut i32)
-           -> bool {
-	let ao = -*ap;
-	let mut ab = *bp - *ap;
-	let mut ac = *cp - *ap;
-	let mut abc = cross(ab, ac);
-	match *w {
-		2 => {
-			let ab_abc = cross(ab, abc);
-			if ab_abc.dot(ao) > 0.0 {
-				*cp = *bp;
-				*bp = *ap;
-				*sp = dcross3(ab, ao);
-			} else {
-				let abc_ac = cross(abc, ac);
-				if abc_ac.dot(ao) > 0.0 {
-					*bp = *ap;
-					*sp = dcross3(ac, ao);
-				} else {
-					if abc.dot(ao) > 0.0 {
-						*dp = *cp;
-						*cp = *bp;
-						*bp = *ap;
-						*sp = abc;
-					} else {
-						*dp = *bp;
-						*bp = *ap;
-						*sp = -abc;
-					}
-					*w = 3;
-				}
-			}
-			false
-		}
-		3 => {
-			macro_rules! check_tetrahedron {
-				() => { check_tetra(Tetra(ap, bp, cp, dp), sp, w, ao, ab, ac, abc); };
-			};
-			if abc.dot(ao) > 0.0 {
-				check_tetrahedron![];;
-				false
-			} else {
-				let ad = *dp - *ap;
-				let acd = cross(ac, ad);
-				if acd.dot(ao) > 0.0 {
-					*bp = *cp;
-					*cp = *dp;
-					ab = ac;
-					ac = ad;
-					abc = acd;
-					check_tetrahedron![];;
-					false
-				} else {
-					let adb = cross(ad, ab);
-					if adb.dot(ao) > 0.0 {
-						*cp = *bp;
-						*bp = *dp;
-						ac = ab;
-						ab = ad;
-						abc = adb;
-						check_tetrahedron![];;
-						false
-					} else {
-						true
-					}
-				}
-			}
+impl<T: Float> IndexMut<usize> for Vector3<T> {
+	fn index_mut(&mut self, index: usize) -> &mut T {
+		match index {
+			0 => &mut self.0,
+			1 => &mut self.1,
+			2 => &mut self.2,
+			_ => panic!("Vec3 index out of range: the index is {} but must be 0, 1, or 2", index),
 		}
-		_ => false,
 	}
 }
 
-struct Tetra<'a>(&'a mut Vec3, &'a mut Vec3, &'a mut Vec3, &'a mut Vec3);
+impl<T: Float> Index<Axis> for Vector3<T> {
+	type Output = T;
+	fn index(&self, axis: Axis) -> &T {
+		match axis {
+			Axis::X => &self.0,
+			Axis::Y => &self.1,
+			Axis::Z => &self.2,
+		}
+	}
+}
 
-fn check_tetra(te: Tetra, sp: &mut Vec3, w: &mut i32, ao: Vec3, ab: Vec3, ac: Vec3, abc: Vec3) {
-	let ab_abc = cross(ab, abc);
-	if ab_abc.dot(ao) > 0.0 {
-		*te.2 = *te.1;
-		*te.1 = *te.0;
-		*sp = dcross3(ab, ao);
-		*w = 2;
-	} else {
-		let acp = cross(abc, ac);
-		if acp.dot(ao) > 0.0 {
-			*te.1 = *te.0;
-			*sp = dcross3(ac, ao);
-			*w = 2;
-		} else {
-			*te.3 = *te.2;
-			*te.2 = *te.1;
-			*te.1 = *te.0;
-			*sp = abc;
-			*w = 3;
+impl<T: Float> IndexMut<Axis> for Vector3<T> {
+	fn index_mut(&mut self, axis: Axis) -> &mut T {
+		match axis {
+			Axis::X => &mut self.0,
+			Axis::Y => &mut self.1,
+			Axis::Z => &mut self.2,
 		}
 	}
 }
 
-fn cross(a: Vec3, b: Vec3) -> Vec3 {
-	Vec3(a.1 * b.2 - a.2 * b.1,
-	     a.2 * b.0 - a.0 * b.2,
-	     a.0 * b.1 - a.1 * b.0)
+/// Formats as `(x, y, z)`
+///
+/// Formatter flags such as width and precision apply to each component,
+/// so `{:.3}` prints every component with three decimals.
+impl<T: Float> fmt::Display for Vector3<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "(")?;
+		fmt::Display::fmt(&self.0, f)?;
+		write!(f, ", ")?;
+		fmt::Display::fmt(&self.1, f)?;
+		write!(f, ", ")?;
+		fmt::Display::fmt(&self.2, f)?;
+		write!(f, ")")
+	}
 }
 
-fn cross3(a: Vec3, b: Vec3, c: Vec3) -> Vec3 {
-	cross(cross(a, b), c)
+/// Formats as `(x, y, z)` using scientific notation for each component
+impl<T: Float> fmt::LowerExp for Vector3<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "(")?;
+		fmt::LowerExp::fmt(&self.0, f)?;
+		write!(f, ", ")?;
+		fmt::LowerExp::fmt(&self.1, f)?;
+		write!(f, ", ")?;
+		fmt::LowerExp::fmt(&self.2, f)?;
+		write!(f, ")")
+	}
 }
 
-fn dcross3(a: Vec3, b: Vec3) -> Vec3 {
-	cross3(a, b, a)
+impl<T: Float> From<[T; 3]> for Vector3<T> {
+	fn from(array: [T; 3]) -> Vector3<T> {
+		Vector3(array[0], array[1], array[2])
+	}
 }
 
-fn farthest(vertices: &[Vec3], direction: Vec3) -> Vec3 {
-	let mut max: Option<f32> = None;
-	let mut max_vertex = Vec3::default();
-	for vertex in vertices {
-		let current = vertex.dot(direction);
-		if let Some(value) = max {
-			if current > value {
-				max = Some(current);
-				max_vertex = *vertex;
-			}
-		} else {
-			max = Some(current);
-			max_vertex = *vertex;
-		}
+impl<T: Float> From<Vector3<T>> for [T; 3] {
+	fn from(vector: Vector3<T>) -> [T; 3] {
+		[vector.0, vector.1, vector.2]
 	}
-	max_vertex
 }
 
-fn support(vertices_a: &[Vec3], vertices_b: &[Vec3], direction: Vec3) -> Vec3 {
-	farthest(vertices_a, direction) - farthest(vertices_b, -direction)
+impl<T: Float> From<(T, T, T)> for Vector3<T> {
+	fn from(tuple: (T, T, T)) -> Vector3<T> {
+		Vector3(tuple.0, tuple.1, tuple.2)
+	}
 }
 
+impl<T: Float> From<Vector3<T>> for (T, T, T) {
+	fn from(vector: Vector3<T>) -> (T, T, T) {
+		(vector.0, vector.1, vector.2)
+	}
+}
 
-#[cfg(test)]
-mod tests {
+impl<T: Float> AddAssign for Vector3<T> {
+	fn add_assign(&mut self, right: Vector3<T>) {
+		*self = *self + right;
+	}
+}
 
-	use std::f32;
-	use std::f32::consts::PI;
-	use super::{Vec3, bgjk};
-	static EPS: f32 = f32::EPSILON;
+/// Sums the vectors, starting from `Vec3::ZERO`
+impl<T: Float> Sum for Vector3<T> {
+	fn sum<I: Iterator<Item = Vector3<T>>>(iter: I) -> Vector3<T> {
+		iter.fold(Vector3::ZERO, Add::add)
+	}
+}
 
-	macro_rules! pts {
-		($($e:expr),*) => {
-			[$(
-				Vec3($e.0, $e.1, $e.2)
-			),*]
-		};
+impl<'a, T: Float> Sum<&'a Vector3<T>> for Vector3<T> {
+	fn sum<I: Iterator<Item = &'a Vector3<T>>>(iter: I) -> Vector3<T> {
+		iter.fold(Vector3::ZERO, Add::add)
 	}
+}
 
-	#[test]
-	fn square1() {
-		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
-		let shape2 = pts![(-2.0, 0.0, 0.0), (-3.0, 0.0, 0.0), (-2.0, 1.0, 0.0), (-3.0, 1.0, 0.0)];
-		assert_eq![bgjk(&shape1, &shape2), false];
+impl<T: Float> SubAssign for Vector3<T> {
+	fn sub_assign(&mut self, right: Vector3<T>) {
+		*self = *self - right;
 	}
+}
 
-	#[test]
-	fn exact_overlap() {
-		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
-		let shape2 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
-		assert_eq![bgjk(&shape1, &shape2), true];
+impl<T: Float> MulAssign<T> for Vector3<T> {
+	fn mul_assign(&mut self, right: T) {
+		*self = *self * right;
 	}
+}
 
-	#[test]
-	fn line_overlap() {
-		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0)];
-		let shape2 = pts![(0.5, 1.0, 0.0), (0.5, -1.0, 0.0)];
-		assert_eq![bgjk(&shape1, &shape2), true];
+impl<T: Float> DivAssign<T> for Vector3<T> {
+	fn div_assign(&mut self, right: T) {
+		*self = *self / right;
 	}
+}
 
-	#[test]
-	fn line_non_overlap() {
-		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0)];
-		let shape2 = pts![(1.5, 1.0, 0.0), (1.5, -1.0, 0.0)];
-		assert_eq![bgjk(&shape1, &shape2), false];
+impl<T: Float> Vector3<T> {
+	/// The zero vector
+	pub const ZERO: Vector3<T> = Vector3(T::ZERO, T::ZERO, T::ZERO);
+	/// The vector with all components set to one
+	pub const ONE: Vector3<T> = Vector3(T::ONE, T::ONE, T::ONE);
+	/// The unit vector along the x axis
+	pub const X: Vector3<T> = Vector3(T::ONE, T::ZERO, T::ZERO);
+	/// The unit vector along the y axis
+	pub const Y: Vector3<T> = Vector3(T::ZERO, T::ONE, T::ZERO);
+	/// The unit vector along the z axis
+	pub const Z: Vector3<T> = Vector3(T::ZERO, T::ZERO, T::ONE);
+
+	/// Create a vector from its x, y, and z components
+	///
+	/// Usable in constants and statics, e.g. for fixed level geometry.
+	pub const fn new(x: T, y: T, z: T) -> Vector3<T> {
+		Vector3(x, y, z)
 	}
 
-	#[test]
-	fn small_line_point_overlap() {
-		let shape1 = pts![(0.0, 0.0, 0.0), (0.01, 0.0, 0.0)];
-		let shape2 = pts![(0.005, 0.0, 0.1)];
-		assert_eq![bgjk(&shape1, &shape2), false];
+	/// View the components as an array `[x, y, z]` without copying
+	pub fn as_array(&self) -> &[T; 3] {
+		// Safety: `Vec3` is `repr(C)` with three `T` fields and no padding
+		unsafe { &*(self as *const Vector3<T> as *const [T; 3]) }
 	}
 
-	#[test]
-	fn line_point_non_overlap() {
-		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0)];
-		let shape2 = pts![(0.5, 0.0, 0.1)];
-		assert_eq![bgjk(&shape1, &shape2), false];
+	/// View the components as a mutable array `[x, y, z]` without copying
+	pub fn as_mut_array(&mut self) -> &mut [T; 3] {
+		// Safety: see `as_array`
+		unsafe { &mut *(self as *mut Vector3<T> as *mut [T; 3]) }
 	}
 
-	#[test]
-	fn point_overlap() {
-		let shape1 = pts![(0.5, 1.0, 0.0)];
-		let shape2 = pts![(0.5, 1.0, 0.0)];
-		assert_eq![bgjk(&shape1, &shape2), true];
+	/// The x component
+	pub const fn x(&self) -> T {
+		self.0
 	}
 
-	#[test]
-	fn point_no_overlap() {
-		let shape1 = pts![(0.5, 1.0, 0.0)];
-		let shape2 = pts![(1.0, 1.0, 0.0)];
-		assert_eq![bgjk(&shape1, &shape2), false];
+	/// The y component
+	pub const fn y(&self) -> T {
+		self.1
 	}
 
-	#[test]
-	fn empty_no_overlap() {
-		// An empty set defaults to a single point in origo in the set
-		let shape1: [Vec3; 0] = pts![];
-		let shape2 = pts![(1.0, 1.0, 1.0)];
-		assert_eq![bgjk(&shape1, &shape2), false];
+	/// The z component
+	pub const fn z(&self) -> T {
+		self.2
 	}
 
-	#[test]
-	fn side_by_side_squares() {
-		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
-		let shape2 = pts![(1.0, 0.0, 0.0), (2.0, 0.0, 0.0), (1.0, 1.0, 0.0), (2.0, 1.0, 0.0)];
-		assert_eq![bgjk(&shape1, &shape2), true];
+	/// Set the x component
+	pub fn set_x(&mut self, x: T) {
+		self.0 = x;
 	}
 
-	#[test]
-	fn side_by_side_squares_offset() {
-		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
-		let shape2 =
-			pts![(1.0 + EPS, 0.0, 0.0), (2.0, 0.0, 0.0), (1.0 + EPS, 1.0, 0.0), (2.0, 1.0, 0.0)];
-		assert_eq![bgjk(&shape1, &shape2), false];
+	/// Set the y component
+	pub fn set_y(&mut self, y: T) {
+		self.1 = y;
 	}
 
-	#[test]
-	fn single_point_square_overlap() {
-		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
-		let shape2 = pts![(1.0, 1.0, 0.0), (2.0, 1.0, 0.0), (1.0, 2.0, 0.0), (2.0, 2.0, 0.0)];
-		assert_eq![bgjk(&shape1, &shape2), true];
+	/// Set the z component
+	pub fn set_z(&mut self, z: T) {
+		self.2 = z;
 	}
 
-	#[test]
-	fn single_point_shape_overlap() {
-		let shape1 = pts![(0.0, 0.0, 0.0),
-		                 (1.0, 0.0, 0.0),
-		                 (0.0, 1.0, 0.0),
-		                 (1.0, 1.0, 0.0),
-		                 (0.0, 0.0, 1.0),
-		                 (1.0, 0.0, 1.0),
-		                 (0.0, 1.0, 1.0),
-		                 (1.0, 1.0, 1.0)];
-		let shape2 = pts![(1.0, 1.0, 1.0),
-		                 (2.0, 1.0, 1.0),
-		                 (1.0, 2.0, 1.0),
-		                 (2.0, 2.0, 1.0),
-		                 (1.0, 1.0, 2.0),
-		                 (2.0, 1.0, 2.0),
-		                 (1.0, 2.0, 2.0),
-		                 (2.0, 2.0, 2.0)];
-		assert_eq![bgjk(&shape1, &shape2), true];
+	/// The dot (inner) product
+	pub fn dot(&self, right: Vector3<T>) -> T {
+		self.0 * right.0 + self.1 * right.1 + self.2 * right.2
 	}
 
-	#[test]
-	fn single_point_shape_non_overlap() {
-		let shape1 = pts![(0.0, 0.0, 0.0),
-		                 (1.0, 0.0, 0.0),
-		                 (0.0, 1.0, 0.0),
-		                 (1.0, 1.0, 0.0),
-		                 (0.0, 0.0, 1.0),
-		                 (1.0, 0.0, 1.0),
-		                 (0.0, 1.0, 1.0),
-		                 (1.0, 1.0, 1.0)];
-		let shape2 = pts![(1.0, 1.0, 1.0 + EPS),
-		                 (2.0, 1.0, 1.0 + EPS),
-		                 (1.0, 2.0, 1.0 + EPS),
-		                 (2.0, 2.0, 1.0 + EPS),
-		                 (1.0, 1.0, 2.0),
-		                 (2.0, 1.0, 2.0),
-		                 (1.0, 2.0, 2.0),
-		                 (2.0, 2.0, 2.0)];
-		assert_eq![bgjk(&shape1, &shape2), false];
+	/// The cross product
+	///
+	/// Follows the right-hand rule in a right-handed coordinate system, so
+	/// `Vec3::X.cross(Vec3::Y) == Vec3::Z`.
+	pub fn cross(&self, right: Vector3<T>) -> Vector3<T> {
+		Vector3(self.1 * right.2 - self.2 * right.1,
+		        self.2 * right.0 - self.0 * right.2,
+		        self.0 * right.1 - self.1 * right.0)
 	}
 
-	#[test]
-	fn single_line_shape_overlap() {
-		let shape1 = pts![(0.0, 0.0, 0.0),
-		                 (1.0, 0.0, 0.0),
-		                 (0.0, 1.0, 0.0),
-		                 (1.0, 1.0, 0.0),
-		                 (0.0, 0.0, 1.0),
-		                 (1.0, 0.0, 1.0),
-		                 (0.0, 1.0, 1.0),
-		                 (1.0, 1.0, 1.0)];
-		let shape2 = pts![(1.0, 1.0, 0.0),
-		                 (2.0, 1.0, 0.0),
-		                 (1.0, 2.0, 0.0),
-		                 (2.0, 2.0, 0.0),
-		                 (1.0, 1.0, 1.0),
-		                 (2.0, 1.0, 1.0),
-		                 (1.0, 2.0, 1.0),
-		                 (2.0, 2.0, 1.0)];
-		assert_eq![bgjk(&shape1, &shape2), true];
+	/// The scalar triple product `a · (b × c)`
+	///
+	/// This is the signed volume of the parallelepiped spanned by the three
+	/// vectors. It is positive when `a`, `b`, `c` form a right-handed system
+	/// (as `Vec3::X`, `Vec3::Y`, `Vec3::Z` do), negative when left-handed, and
+	/// zero when they are coplanar. Swapping any two arguments flips the sign.
+	pub fn triple(a: Vector3<T>, b: Vector3<T>, c: Vector3<T>) -> T {
+		a.dot(b.cross(c))
 	}
 
-	#[test]
-	fn shape_projective_non_overlap() {
-		let shape1 = pts![(0.0, 0.0, 0.0),
-		                 (1.0, 0.0, 0.0),
-		                 (0.0, 1.0, 0.0),
-		                 (1.0, 1.0, 0.0),
-		                 (1.0, 0.0, 1.0),
-		                 (2.0, 0.0, 1.0),
-		                 (1.0, 1.0, 1.0),
-		                 (2.0, 1.0, 1.0)];
-		let shape2 = pts![(1.1, 1.0, 0.0),
-		                 (2.1, 1.0, 0.0),
-		                 (1.1, 2.0, 0.0),
-		                 (2.1, 2.0, 0.0),
-		                 (2.1, 1.0, 1.0),
+	/// Whether all components are neither infinite nor NaN
+	pub fn is_finite(&self) -> bool {
+		self.0.is_finite() && self.1.is_finite() && self.2.is_finite()
+	}
+
+	/// Whether any component is NaN
+	pub fn is_nan(&self) -> bool {
+		self.0.is_nan() || self.1.is_nan() || self.2.is_nan()
+	}
+
+	/// The euclidean length (magnitude) of the vector
+	///
+	/// A cheap bounding-sphere check can rule out distant hulls before
+	/// running `bgjk`:
+	///
+	/// ```
+	/// use bgjk::{bgjk, Vec3};
+	///
+	/// fn radius(hull: &[Vec3]) -> f32 {
+	///     hull.iter().map(|v| (*v - hull[0]).length()).fold(0.0, f32::max)
+	/// }
+	///
+	/// let hull1 = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+	/// let hull2 = [Vec3::new(9.0, 0.0, 0.0), Vec3::new(10.0, 0.0, 0.0), Vec3::new(9.0, 1.0, 0.0)];
+	/// let gap = (hull2[0] - hull1[0]).length();
+	/// let hit = gap <= radius(&hull1) + radius(&hull2) && bgjk(&hull1, &hull2);
+	/// assert![!hit];
+	/// ```
+	pub fn length(&self) -> T {
+		self.length_squared().sqrt()
+	}
+
+	/// The squared euclidean length of the vector, avoids the square root
+	pub fn length_squared(&self) -> T {
+		self.dot(*self)
+	}
+
+	/// Two unit vectors perpendicular to `self` and to each other
+	///
+	/// The pair `(a, b)` is right-handed with the direction of `self`, so
+	/// `a.cross(b)` equals `self.normalized()` up to rounding. Vectors without
+	/// a direction (see `try_normalized`) are treated as `Vec3::X`, giving
+	/// `(Vec3::Y, Vec3::Z)`.
+	pub fn any_orthonormal_pair(self) -> (Vector3<T>, Vector3<T>) {
+		let n = self.try_normalized().unwrap_or(Vector3::X);
+		// Zeroing the smaller of x and z keeps the result far from degenerate
+		let a = if n.0.abs() > n.2.abs() {
+			Vector3(-n.1, n.0, T::ZERO)
+		} else {
+			Vector3(T::ZERO, -n.2, n.1)
+		};
+		let a = a / a.length();
+		(a, n.cross(a))
+	}
+
+	/// A unit vector perpendicular to `self`, see `any_orthonormal_pair`
+	pub fn any_orthogonal(self) -> Vector3<T> {
+		self.any_orthonormal_pair().0
+	}
+
+	/// The euclidean distance between two points, same as `(self - other).length()`
+	pub fn distance(self, other: Vector3<T>) -> T {
+		(self - other).length()
+	}
+
+	/// The squared euclidean distance between two points, avoids the square root
+	pub fn distance_squared(self, other: Vector3<T>) -> T {
+		(self - other).length_squared()
+	}
+
+	/// The angle between two vectors in radians, in the range `[0, π]`
+	///
+	/// Returns `None` if either vector has no direction, see `try_normalized`.
+	/// The cosine is clamped before taking the arc cosine, so (nearly)
+	/// parallel vectors give a small angle instead of NaN.
+	pub fn try_angle_between(self, other: Vector3<T>) -> Option<T> {
+		let a = self.try_normalized()?;
+		let b = other.try_normalized()?;
+		Some(a.dot(b).clamp(-T::ONE, T::ONE).acos())
+	}
+
+	/// The angle between two vectors in radians, in the range `[0, π]`
+	///
+	/// Returns `0.0` if either vector has no direction, see
+	/// `try_angle_between`.
+	pub fn angle_between(self, other: Vector3<T>) -> T {
+		self.try_angle_between(other).unwrap_or(T::ZERO)
+	}
+
+	/// The component of `self` parallel to `onto`
+	///
+	/// Returns `Vec3::ZERO` when projecting onto the zero vector, so that
+	/// `reject_from` returns `self` unchanged in that case.
+	pub fn project_onto(self, onto: Vector3<T>) -> Vector3<T> {
+		let length_squared = onto.length_squared();
+		if length_squared == T::ZERO {
+			Vector3::ZERO
+		} else {
+			onto * (self.dot(onto) / length_squared)
+		}
+	}
+
+	/// The component of `self` perpendicular to `from`
+	///
+	/// Always satisfies `v.project_onto(n) + v.reject_from(n) == v` up to
+	/// rounding.
+	pub fn reject_from(self, from: Vector3<T>) -> Vector3<T> {
+		self - self.project_onto(from)
+	}
+
+	/// Whether every component differs from `other`'s by at most `epsilon`
+	///
+	/// Identical infinities compare equal, any other comparison involving
+	/// an infinity or a NaN does not.
+	pub fn approx_eq(&self, other: Vector3<T>, epsilon: T) -> bool {
+		self.approx_eq_relative(other, epsilon, T::ZERO)
+	}
+
+	/// Like `approx_eq`, but the tolerance grows with the magnitude
+	///
+	/// Each pair of components may differ by the larger of `absolute` and
+	/// `relative` times the larger of the two magnitudes.
+	pub fn approx_eq_relative(&self, other: Vector3<T>, absolute: T, relative: T) -> bool {
+		fn close<T: Float>(a: T, b: T, absolute: T, relative: T) -> bool {
+			a == b ||
+			a.is_finite() && b.is_finite() &&
+			(a - b).abs() <= absolute.max(relative * a.abs().max(b.abs()))
+		}
+		close(self.0, other.0, absolute, relative) && close(self.1, other.1, absolute, relative) &&
+		close(self.2, other.2, absolute, relative)
+	}
+
+	/// The unit vector in the same direction, if there is one
+	///
+	/// Returns `None` for the zero vector and for vectors with non-finite
+	/// components. The vector is rescaled by its largest component before
+	/// computing the length, so neither denormal nor huge vectors lose
+	/// their direction to underflow or overflow.
+	pub fn try_normalized(&self) -> Option<Vector3<T>> {
+		let largest = self.0.abs().max(self.1.abs()).max(self.2.abs());
+		if largest == T::ZERO || !self.is_finite() {
+			return None;
+		}
+		let scaled = *self / largest;
+		Some(scaled / scaled.length())
+	}
+
+	/// The unit vector in the same direction
+	///
+	/// Returns `Vec3::ZERO` instead of NaN components when the vector
+	/// has no direction, see `try_normalized`.
+	pub fn normalized(&self) -> Vector3<T> {
+		self.try_normalized().unwrap_or(Vector3::ZERO)
+	}
+
+	/// Normalize the vector in place, see `normalized`
+	pub fn normalize(&mut self) {
+		*self = self.normalized();
+	}
+
+	/// The vector with all components set to one, same as `Vec3::ONE`
+	pub const fn ones() -> Vector3<T> {
+		Vector3::ONE
+	}
+
+	/// Convert a slice of arrays (e.g. the output of a mesh loader) into a hull
+	pub fn slice_from_arrays(arrays: &[[T; 3]]) -> Vec<Vector3<T>> {
+		arrays.iter().map(|array| Vector3::from(*array)).collect()
+	}
+
+	/// The component-wise minimum
+	///
+	/// Follows `f32::min`: if one of a pair of components is NaN the other
+	/// one is chosen, so NaN only survives if both are NaN.
+	pub fn min(self, other: Vector3<T>) -> Vector3<T> {
+		Vector3(self.0.min(other.0), self.1.min(other.1), self.2.min(other.2))
+	}
+
+	/// The component-wise maximum
+	///
+	/// Follows `f32::max`, see `min` for the NaN handling.
+	pub fn max(self, other: Vector3<T>) -> Vector3<T> {
+		Vector3(self.0.max(other.0), self.1.max(other.1), self.2.max(other.2))
+	}
+
+	/// Restrict every component to the interval given by `lo` and `hi`
+	///
+	/// NaN components of `self` stay NaN. Panics if any component of `lo`
+	/// is greater than the corresponding component of `hi`, or if either
+	/// bound contains a NaN, just like `f32::clamp`.
+	pub fn clamp(self, lo: Vector3<T>, hi: Vector3<T>) -> Vector3<T> {
+		assert![lo.0 <= hi.0 && lo.1 <= hi.1 && lo.2 <= hi.2,
+		        "Vec3 clamp bounds are inverted or NaN: lo is {:?} and hi is {:?}",
+		        lo,
+		        hi];
+		Vector3(self.0.clamp(lo.0, hi.0), self.1.clamp(lo.1, hi.1), self.2.clamp(lo.2, hi.2))
+	}
+
+	/// Rotate the vector by `angle_radians` about `axis` through the origin
+	///
+	/// Uses Rodrigues' rotation formula. Positive angles rotate counter-
+	/// clockwise when looking down the axis towards the origin. The axis is
+	/// normalized internally, and a zero (or non-finite) axis returns the
+	/// vector unchanged.
+	pub fn rotated_about(self, axis: Vector3<T>, angle_radians: T) -> Vector3<T> {
+		match axis.try_normalized() {
+			Some(k) => {
+				let (sin, cos) = angle_radians.sin_cos();
+				self * cos + k.cross(self) * sin + k * (k.dot(self) * (T::ONE - cos))
+			}
+			None => self,
+		}
+	}
+
+	/// Rotate a whole hull in place, see `rotated_about`
+	pub fn rotate_points(points: &mut [Vector3<T>], axis: Vector3<T>, angle_radians: T) {
+		for point in points {
+			*point = point.rotated_about(axis, angle_radians);
+		}
+	}
+
+	/// Mirror the vector across the plane through the origin with the given normal
+	///
+	/// Computes `v - 2 (v · n) n`, which is only a reflection for a unit
+	/// length `normal`. Debug builds panic otherwise, normalize first if
+	/// unsure.
+	pub fn reflected(self, normal: Vector3<T>) -> Vector3<T> {
+		debug_assert![(normal.length_squared() - T::ONE).abs() <= T::from_f32(1e-4),
+		              "Vec3 reflection normal is not unit length: {:?}",
+		              normal];
+		self - normal * ((T::ONE + T::ONE) * self.dot(normal))
+	}
+
+	/// Mirror a whole hull in place, see `reflected`
+	pub fn reflect_points(points: &mut [Vector3<T>], normal: Vector3<T>) {
+		for point in points {
+			*point = point.reflected(normal);
+		}
+	}
+
+	/// The component-wise (Hadamard) product, not the dot product
+	///
+	/// Useful for non-uniform scaling, e.g. stretching a unit cube into a box.
+	pub fn mul_component(self, other: Vector3<T>) -> Vector3<T> {
+		Vector3(self.0 * other.0, self.1 * other.1, self.2 * other.2)
+	}
+
+	/// The component-wise quotient
+	///
+	/// Zero components in `other` follow IEEE 754 semantics and yield
+	/// infinities or NaN, like `Div`.
+	pub fn div_component(self, other: Vector3<T>) -> Vector3<T> {
+		Vector3(self.0 / other.0, self.1 / other.1, self.2 / other.2)
+	}
+
+	/// The component-wise absolute value
+	pub fn abs(self) -> Vector3<T> {
+		Vector3(self.0.abs(), self.1.abs(), self.2.abs())
+	}
+
+	/// The component-wise sign
+	///
+	/// Follows `f32::signum`: each component becomes `1.0` if it is positive
+	/// or `+0.0`, `-1.0` if it is negative or `-0.0`, and NaN stays NaN.
+	pub fn signum(self) -> Vector3<T> {
+		Vector3(self.0.signum(), self.1.signum(), self.2.signum())
+	}
+
+	/// The magnitudes of `self` with the signs of `sign`, component-wise
+	///
+	/// Follows `f32::copysign`, so the sign bit of zeroes and NaNs in `sign`
+	/// is copied as well.
+	pub fn copysign(self, sign: Vector3<T>) -> Vector3<T> {
+		Vector3(self.0.copysign(sign.0), self.1.copysign(sign.1), self.2.copysign(sign.2))
+	}
+
+	/// Linear interpolation from `self` towards `other`
+	///
+	/// `t` is not clamped, so values outside `[0, 1]` extrapolate along the
+	/// line through both points. For finite components the result is exactly
+	/// `self` at `t = 0` and exactly `other` at `t = 1`.
+	pub fn lerp(self, other: Vector3<T>, t: T) -> Vector3<T> {
+		self * (T::ONE - t) + other * t
+	}
+
+	/// Like `lerp`, but with `t` clamped to `[0, 1]`
+	///
+	/// A NaN `t` is passed through and yields NaN components.
+	pub fn lerp_clamped(self, other: Vector3<T>, t: T) -> Vector3<T> {
+		self.lerp(other, t.clamp(T::ZERO, T::ONE))
+	}
+
+	/// Collect anything convertible into a `Vec3` (arrays, tuples) into a hull
+	pub fn hull_from_iter<I>(points: I) -> Vec<Vector3<T>>
+		where I: IntoIterator,
+		      I::Item: Into<Vector3<T>>
+	{
+		points.into_iter().map(Into::into).collect()
+	}
+
+	/// Divide by a scalar, returning `None` if the divisor is zero
+	pub fn checked_div(self, right: T) -> Option<Vector3<T>> {
+		if right == T::ZERO {
+			None
+		} else {
+			Some(self / right)
+		}
+	}
+}
+
+impl Vec3 {
+	/// Create a vector from the x and y components of `xy` and the given z
+	pub const fn from_xy(xy: Vec2, z: f32) -> Vec3 {
+		Vec3(xy.0, xy.1, z)
+	}
+
+	/// The x and y components, dropping z
+	pub const fn xy(&self) -> Vec2 {
+		Vec2(self.0, self.1)
+	}
+
+	/// The x and z components, dropping y
+	pub const fn xz(&self) -> Vec2 {
+		Vec2(self.0, self.2)
+	}
+
+	/// The y and z components, dropping x
+	pub const fn yz(&self) -> Vec2 {
+		Vec2(self.1, self.2)
+	}
+
+	/// Drop the z component, same as `xy`
+	pub const fn truncate(&self) -> Vec2 {
+		self.xy()
+	}
+}
+
+impl<T: Float> Neg for Vector3<T> {
+	type Output = Vector3<T>;
+	fn neg(self) -> Self::Output {
+		Vector3(-self.0, -self.1, -self.2)
+	}
+}
+
+/// View a hull as a flat slice of floats `[x0, y0, z0, x1, ...]` without copying
+pub fn vecs_as_floats(hull: &[Vec3]) -> &[f32] {
+	// Safety: `Vec3` is `repr(C)` with three `f32` fields and no padding
+	unsafe { std::slice::from_raw_parts(hull.as_ptr() as *const f32, hull.len() * 3) }
+}
+
+/// View a hull as a mutable flat slice of floats, see `vecs_as_floats`
+pub fn vecs_as_floats_mut(hull: &mut [Vec3]) -> &mut [f32] {
+	// Safety: see `vecs_as_floats`
+	unsafe { std::slice::from_raw_parts_mut(hull.as_mut_ptr() as *mut f32, hull.len() * 3) }
+}
+
+/// The BGJK algorithm
+///
+/// The Boolean-GJK algorithm gives us the answer to the question:
+/// "do these convex hulls intersect?"
+/// This algorithm takes two hulls. The ordering of the points is not
+/// important. All points are assumed to be on the surface of the hull.
+/// Having interior points should not affect the qualitative result of
+/// the algorithm, but may cause slight (very minor) degradation in
+/// performance. The algorithm is O(n+m), where n and m are the amount
+/// of points in hull1 and hull2 respectively.
+///
+/// A hull with no points intersects nothing, not even another such hull.
+/// It used to be taken as a single point at the origin, which made an
+/// accidentally empty hull hit everything around the origin. Hulls that
+/// rely on that can be tested with `EmptyHull::Origin` in `bgjk_config`.
+///
+/// The search visits at most 64 support points, like `GjkConfig::default`,
+/// and reports an intersection when it runs out, so it may report a hit
+/// for degenerate touching hulls, such as a corner of one barely apart
+/// from the other, that it creeps towards without ever settling. Use
+/// `bgjk_config` with `OnLimit::Miss` or a larger `max_iterations` to
+/// decide those differently.
+///
+/// In debug builds this panics if any point is infinite or NaN, since
+/// such hulls silently give meaningless answers.
+///
+/// Works in the precision of the hulls, `f32` by default. Hulls with
+/// features too fine for `f32` can be tested in `f64` instead, by giving
+/// `DVec3` hulls or naming the scalar:
+///
+/// ```
+/// use bgjk::{bgjk, DVec3};
+///
+/// let block = [DVec3::new(0.0, 0.0, 0.0), DVec3::new(10.0, 0.0, 0.0), DVec3::new(0.0, 10.0, 0.0)];
+/// let gap = DVec3::new(10.0 + 1e-7, 0.0, 0.0);
+/// let beside: Vec<DVec3> = block.iter().map(|v| *v + gap).collect();
+/// assert![!bgjk::<f64>(&block, &beside)];
+/// // The same gap is lost to rounding in `f32`
+/// let narrow = |hull: &[DVec3]| hull.iter().map(|v| v.to_f32()).collect::<Vec<_>>();
+/// assert![bgjk::<f32>(&narrow(&block), &narrow(&beside))];
+/// ```
+pub fn bgjk<T: Float>(hull1: &[Vector3<T>], hull2: &[Vector3<T>]) -> bool {
+	debug_assert![hull1.iter().chain(hull2).all(Vector3::is_finite),
+	              "bgjk called with a non-finite point in a hull"];
+	if either_empty(hull1, hull2) {
+		return false;
+	}
+	bgjk_support(hull1, hull2)
+}
+
+/// The BGJK algorithm starting from a given search direction, see `bgjk`
+///
+/// Hulls that move little between frames tend to stay separated along the
+/// same axis. Starting the search from last frame's separating axis, such
+/// as the one from `bgjk_separating_axis_with_hint`, typically finds it
+/// again within a support point or two. The answer is the same for every
+/// hint, only the amount of work changes. A zero or non-finite hint starts
+/// along `Vec3::ONE` like `bgjk`.
+///
+/// ```
+/// use bgjk::{bgjk_separating_axis, bgjk_with_hint, Vec3};
+///
+/// let hull1 = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// let hull2: Vec<Vec3> = hull1.iter().map(|v| *v + Vec3::new(3.0, 0.0, 0.0)).collect();
+/// let axis = bgjk_separating_axis(&hull1, &hull2).unwrap();
+/// let moved: Vec<Vec3> = hull2.iter().map(|v| *v + Vec3::new(-0.1, 0.1, 0.0)).collect();
+/// assert![!bgjk_with_hint(&hull1, &moved, axis)];
+/// ```
+pub fn bgjk_with_hint<T: Float>(hull1: &[Vector3<T>],
+                                hull2: &[Vector3<T>],
+                                initial_direction: Vector3<T>)
+                                -> bool {
+	bgjk_config(hull1, hull2, &GjkConfig { initial_direction, ..GjkConfig::default() })
+}
+
+/// The BGJK algorithm with a cap on its iterations, see `bgjk`
+///
+/// Nearly degenerate hulls, such as coplanar hulls meeting at a glancing
+/// angle, can take many iterations to decide. The search gives up after
+/// `config.max_iterations` support points and answers according to
+/// `config.on_limit`. With a nonzero `config.tolerance` it also stops as
+/// soon as it finds the hulls to be closer than that.
+///
+/// With `config.boundary` set to `Boundary::Exclusive`, hulls that only
+/// share a face, an edge or a corner do not intersect. Intersecting hulls
+/// then also get their penetration depth computed, see `penetration`, and
+/// only hulls overlapping deeper than `config.tolerance` intersect.
+///
+/// With `config.empty` set to `EmptyHull::Origin`, a hull with no points
+/// is a point at the origin instead of intersecting nothing.
+///
+/// ```
+/// use bgjk::{bgjk_config, GjkConfig, OnLimit, Vec3};
+///
+/// let hull1 = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// let hull2: Vec<Vec3> = hull1.iter().map(|v| *v + Vec3::new(0.5, 0.0, 0.0)).collect();
+/// let config = GjkConfig { max_iterations: 16, on_limit: OnLimit::Miss, ..GjkConfig::default() };
+/// assert![bgjk_config(&hull1, &hull2, &config)];
+/// ```
+pub fn bgjk_config<T: Float>(hull1: &[Vector3<T>],
+                             hull2: &[Vector3<T>],
+                             config: &GjkConfig<T>) -> bool {
+	debug_assert![hull1.iter().chain(hull2).all(Vector3::is_finite),
+	              "bgjk called with a non-finite point in a hull"];
+	if config.empty == EmptyHull::Miss && either_empty(hull1, hull2) {
+		return false;
+	}
+	match config.boundary {
+		Boundary::Inclusive => {
+			gjk_search(|direction| hull_support(hull1, hull2, direction), config).hit(config)
+		}
+		// Hulls without volume, including an empty hull taken as the origin,
+		// have no interior to overlap
+		Boundary::Exclusive if !has_volume(hull1) || !has_volume(hull2) => false,
+		Boundary::Exclusive => {
+			// Interiors overlap when the hulls need moving apart to only touch
+			let search = GjkConfig { tolerance: T::ZERO, ..*config };
+			let minkowski = |direction| support(hull1, hull2, direction);
+			match gjk_tetrahedron(minkowski, &search) {
+				Ok(seed) => expand(seed, minkowski).depth > config.tolerance,
+				Err(Some(_)) => false,
+				Err(None) => config.on_limit == OnLimit::Hit,
+			}
+		}
+	}
+}
+
+/// A direction along which two hulls are separated, `None` if they intersect
+///
+/// This is the search direction `bgjk` stops on when it reports no
+/// intersection. Projected onto the axis, every point of `hull1` lies
+/// strictly below every point of `hull2`, so the axis points from `hull1`
+/// towards `hull2`. It is not normalized. Hulls `bgjk` runs out of
+/// iterations on also give `None`.
+///
+/// Caching the axis and checking it first on the next query is a cheap way
+/// to confirm that hulls which moved only slightly are still apart.
+///
+/// ```
+/// use bgjk::{bgjk_separating_axis, Vec3};
+///
+/// let hull1 = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// let hull2: Vec<Vec3> = hull1.iter().map(|v| *v + Vec3::new(3.0, 0.0, 0.0)).collect();
+/// let axis = bgjk_separating_axis(&hull1, &hull2).unwrap();
+/// let top = hull1.iter().map(|v| v.dot(axis)).fold(f32::MIN, f32::max);
+/// let bottom = hull2.iter().map(|v| v.dot(axis)).fold(f32::MAX, f32::min);
+/// assert![top < bottom];
+/// ```
+pub fn bgjk_separating_axis<T: Float>(hull1: &[Vector3<T>],
+                                      hull2: &[Vector3<T>]) -> Option<Vector3<T>> {
+	bgjk_separating_axis_with_hint(hull1, hull2, Vector3::ONE)
+}
+
+/// `bgjk_separating_axis` starting from a given search direction, see `bgjk_with_hint`
+///
+/// Feeding each frame the axis of the frame before keeps hulls that stay
+/// apart cheap to check.
+pub fn bgjk_separating_axis_with_hint<T: Float>(hull1: &[Vector3<T>],
+                                                hull2: &[Vector3<T>],
+                                                initial_direction: Vector3<T>)
+                                                -> Option<Vector3<T>> {
+	match bgjk_simplex_with_hint(hull1, hull2, initial_direction) {
+		(false, simplex) => Some(simplex.direction),
+		(true, _) => None,
+	}
+}
+
+/// The simplex `bgjk` ends with, see `bgjk_simplex`
+#[derive(Clone, Copy, Debug)]
+pub struct Simplex<T = f32> {
+	points: [Vector3<T>; 4],
+	vertices: [(usize, usize); 4],
+	len: usize,
+	/// The last search direction, not normalized. When the hulls do not
+	/// intersect, it is an axis they are separated along, pointing from the
+	/// first hull towards the second.
+	pub direction: Vector3<T>,
+}
+
+impl<T> Simplex<T> {
+	/// The corners in the Minkowski difference `hull1 - hull2`, between one
+	/// and four of them, with the one found last first
+	///
+	/// Four corners form a tetrahedron around the origin when the hulls
+	/// intersect. Otherwise the corners are the vertex, segment or triangle
+	/// the search had got to, and there are none where a hull is empty.
+	pub fn points(&self) -> &[Vector3<T>] {
+		&self.points[..self.len]
+	}
+
+	/// For each corner the indices of the points of the first and the second
+	/// hull it is the difference of
+	pub fn vertices(&self) -> &[(usize, usize)] {
+		&self.vertices[..self.len]
+	}
+}
+
+/// The BGJK algorithm, also returning the simplex it ends with, see `bgjk`
+///
+/// Useful for building on the algorithm, such as seeding an expanding
+/// polytope from the tetrahedron around the origin, and for inspecting
+/// what the search did. An empty hull gives a simplex without corners,
+/// along with the hint as the separating direction.
+///
+/// ```
+/// use bgjk::{bgjk_simplex, Vec3};
+///
+/// let hull1 = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// let hull2: Vec<Vec3> = hull1.iter().map(|v| *v + Vec3::new(0.0, 0.0, 2.0)).collect();
+/// let (hit, simplex) = bgjk_simplex(&hull1, &hull2);
+/// assert![!hit];
+/// for (point, &(i, j)) in simplex.points().iter().zip(simplex.vertices()) {
+///     assert_eq![*point, hull1[i] - hull2[j]];
+/// }
+/// ```
+pub fn bgjk_simplex<T: Float>(hull1: &[Vector3<T>], hull2: &[Vector3<T>]) -> (bool, Simplex<T>) {
+	bgjk_simplex_with_hint(hull1, hull2, Vector3::ONE)
+}
+
+/// `bgjk_simplex` starting from a given search direction, see `bgjk_with_hint`
+///
+/// The answer is the same for every hint, the simplex and its direction
+/// need not be.
+pub fn bgjk_simplex_with_hint<T: Float>(hull1: &[Vector3<T>],
+                                        hull2: &[Vector3<T>],
+                                        initial_direction: Vector3<T>)
+                                        -> (bool, Simplex<T>) {
+	debug_assert![hull1.iter().chain(hull2).all(Vector3::is_finite),
+	              "bgjk called with a non-finite point in a hull"];
+	let config = GjkConfig { initial_direction, ..GjkConfig::default() };
+	if either_empty(hull1, hull2) {
+		// Nothing to search, any direction keeps no points apart
+		let usable = initial_direction.is_finite() && initial_direction != Vector3::ZERO;
+		let direction = if usable { initial_direction } else { Vector3::ONE };
+		let points = [Vector3::ZERO; 4];
+		let simplex = Simplex { points, vertices: [(0, 0); 4], len: 0, direction };
+		return (false, simplex);
+	}
+	let search = gjk_search(|direction| hull_support(hull1, hull2, direction), &config);
+	let simplex = Simplex {
+		points: search.corners.map(|corner| corner.point),
+		vertices: search.corners.map(|corner| corner.from),
+		len: search.len,
+		direction: search.direction,
+	};
+	(search.hit(&config), simplex)
+}
+
+/// The vertex indices of the simplex `bgjk` ends with, see `bgjk_witness_indices`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SmallSimplexIds {
+	ids: [(usize, usize); 4],
+	len: usize,
+}
+
+impl SmallSimplexIds {
+	/// For each corner of the simplex, between one and four of them, the
+	/// indices of the points of the first and the second hull it is the
+	/// difference of, with the corner found last first
+	pub fn pairs(&self) -> &[(usize, usize)] {
+		&self.ids[..self.len]
+	}
+}
+
+/// The BGJK algorithm, also returning the vertices of its final simplex, see `bgjk`
+///
+/// Handy for caching contacts between frames and for pointing at the
+/// vertices responsible for a hit. When several vertices of a hull are
+/// equally far along a search direction, the one with the lowest index is
+/// taken, so equal hulls always give equal indices. Every index is in
+/// range of its hull, and an empty hull gives none. Equivalent to
+/// `bgjk_simplex` without the points.
+///
+/// ```
+/// use bgjk::{bgjk_witness_indices, Vec3};
+///
+/// let hull1 = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// let hull2 = [Vec3::new(0.2, 0.2, -1.0), Vec3::new(0.2, 0.2, 1.0)];
+/// let (hit, ids) = bgjk_witness_indices(&hull1, &hull2);
+/// assert![hit];
+/// assert![ids.pairs().iter().all(|&(i, j)| i < hull1.len() && j < hull2.len())];
+/// ```
+pub fn bgjk_witness_indices<T: Float>(hull1: &[Vector3<T>],
+                                      hull2: &[Vector3<T>])
+                                      -> (bool, SmallSimplexIds) {
+	let (hit, simplex) = bgjk_simplex(hull1, hull2);
+	(hit, SmallSimplexIds { ids: simplex.vertices, len: simplex.len })
+}
+
+// The BGJK loop on the Minkowski difference given by its support function,
+// lets other hull representations share the algorithm without copying
+fn gjk<T: Float, F: Fn(Vector3<T>) -> Vector3<T>>(support: F) -> bool {
+	let config = GjkConfig::default();
+	gjk_search(|direction| Corner { point: support(direction), from: () }, &config).hit(&config)
+}
+
+// Like `gjk`, but returns the final tetrahedron enclosing the origin, or the
+// simplex that came within the tolerance of it. Otherwise gives the direction
+// in which the support fell short of the origin, or `None` when out of
+// iterations. Of the simplex only the points the search got to are set.
+fn gjk_tetrahedron<T, F>(support: F,
+                         config: &GjkConfig<T>)
+                         -> Result<[Vector3<T>; 4], Option<Vector3<T>>>
+	where T: Float,
+	      F: Fn(Vector3<T>) -> Vector3<T>
+{
+	let search = gjk_search(|direction| Corner { point: support(direction), from: () }, config);
+	match search.ending {
+		Ending::Enclosed | Ending::Touching => Ok(search.corners.map(|corner| corner.point)),
+		Ending::Separated => Err(Some(search.direction)),
+		Ending::OutOfIterations => Err(None),
+	}
+}
+
+// A corner of the simplex, a point of the Minkowski difference with what it
+// is the difference of
+#[derive(Clone, Copy)]
+struct Corner<T, P> {
+	point: Vector3<T>,
+	from: P,
+}
+
+// How the search ended
+#[derive(Clone, Copy, PartialEq)]
+enum Ending {
+	Enclosed,
+	// Came within the tolerance of the origin
+	Touching,
+	Separated,
+	OutOfIterations,
+}
+
+// Where the search ended: the tetrahedron around the origin when enclosed,
+// otherwise the simplex it had got to, and the last search direction
+struct Search<T, P> {
+	ending: Ending,
+	corners: [Corner<T, P>; 4],
+	len: usize,
+	direction: Vector3<T>,
+	support_calls: u32,
+}
+
+impl<T, P> Search<T, P> {
+	fn hit(&self, config: &GjkConfig<T>) -> bool {
+		match self.ending {
+			Ending::Enclosed | Ending::Touching => true,
+			Ending::Separated => false,
+			Ending::OutOfIterations => config.on_limit == OnLimit::Hit,
+		}
+	}
+}
+
+fn gjk_search<T, P, F>(support: F, config: &GjkConfig<T>) -> Search<T, P>
+	where T: Float,
+	      P: Copy,
+	      F: Fn(Vector3<T>) -> Corner<T, P>
+{
+	gjk_steps(support, config, |_| ())
+}
+
+// Like `gjk_search`, showing `on_step` the simplex, the search direction
+// and the new support point before each update of the simplex
+fn gjk_steps<T, P, F, S>(support: F, config: &GjkConfig<T>, on_step: S) -> Search<T, P>
+	where T: Float,
+	      P: Copy,
+	      F: Fn(Vector3<T>) -> Corner<T, P>,
+	      S: FnMut(&GjkStep<T>)
+{
+	gjk_resume(support, config, None, on_step)
+}
+
+// A simplex for the main loop of the search to start from instead of the
+// first two support points: a segment `corners[..2]` with `len` 2, or a
+// triangle with `len` 3 whose normal `(c - b) x (d - b)` is `direction`
+// and does not point away from the origin
+#[derive(Clone, Copy)]
+struct Seed<T, P> {
+	corners: [Corner<T, P>; 3],
+	len: usize,
+	direction: Vector3<T>,
+}
+
+// Like `gjk_steps`, starting from `seed` where there is one
+fn gjk_resume<T, P, F, S>(support: F,
+                          config: &GjkConfig<T>,
+                          seed: Option<Seed<T, P>>,
+                          mut on_step: S)
+                          -> Search<T, P>
+	where T: Float,
+	      P: Copy,
+	      F: Fn(Vector3<T>) -> Corner<T, P>,
+	      S: FnMut(&GjkStep<T>)
+{
+	let hint = config.initial_direction;
+	let mut sp = if hint.is_finite() && hint != Vector3::ZERO { hint } else { Vector3::ONE };
+	let (mut ap, mut bp, mut cp, mut dp);
+
+	let calls = Cell::new(0);
+	let support = |direction| {
+		calls.set(calls.get() + 1);
+		support(direction)
+	};
+	let ended = |ending, corners, len, direction| {
+		Search {
+			ending,
+			corners,
+			len,
+			direction,
+			support_calls: calls.get(),
+		}
+	};
+
+	let mut w;
+	if let Some(seed) = seed {
+		let [b, c, d] = seed.corners;
+		bp = b;
+		cp = c;
+		dp = d;
+		sp = seed.direction;
+		w = seed.len as i32;
+	} else {
+		cp = support(sp);
+		dp = cp;
+		// A hint, such as last frame's separating axis, may already separate
+		// the hulls. The default start is no guess and leaves `bgjk` as it was.
+		if sp != Vector3::ONE && cp.point.dot(sp) < T::ZERO {
+			return ended(Ending::Separated, [cp, cp, cp, cp], 1, sp);
+		}
+		sp = -cp.point;
+		bp = support(sp);
+		if bp.point.dot(sp) < T::ZERO {
+			return ended(Ending::Separated, [cp, bp, dp, cp], 1, sp);
+		}
+		sp = dcross3(cp.point - bp.point, -bp.point);
+		w = 2;
+	}
+
+	for _ in 0..config.max_iterations {
+		ap = support(sp);
+		if ap.point.dot(sp) < T::ZERO {
+			return ended(Ending::Separated, [bp, cp, dp, ap], w as usize, sp);
+		}
+		on_step(&GjkStep {
+			simplex: [bp.point,
+			          cp.point,
+			          if w == 3 { dp.point } else { Vector3::ZERO },
+			          Vector3::ZERO],
+			w: w as usize,
+			direction: sp,
+			support: ap.point,
+			end: None,
+		});
+		if simplex(&mut ap, &mut bp, &mut cp, &mut dp, &mut sp, &mut w) {
+			return ended(Ending::Enclosed, [ap, bp, cp, dp], 4, sp);
+		}
+		// The search direction is perpendicular to the simplex, through the origin
+		let (gap, tolerance) = (-bp.point.dot(sp), config.tolerance);
+		if tolerance > T::ZERO && gap * gap <= tolerance * tolerance * sp.length_squared() {
+			return ended(Ending::Touching, [bp, cp, dp, ap], w as usize, sp);
+		}
+	}
+	ended(Ending::OutOfIterations, [bp, cp, dp, bp], w as usize, sp)
+}
+
+// Todo clean up signature, this has to be fixed, sending 6 ptrs...
+fn simplex<T: Float, P: Copy>(ap: &mut Corner<T, P>,
+                              bp: &mut Corner<T, P>,
+                              cp: &mut Corner<T, P>,
+                              dp: &mut Corner<T, P>,
+                              sp: &mut Vector3<T>,
+                              w: &mut i32)
+                              -> bool {
+	let ao = -ap.point;
+	let ab = bp.point - ap.point;
+	let ac = cp.point - ap.point;
+	let abc = cross(ab, ac);
+	match *w {
+		2 => {
+			let ab_abc = cross(ab, abc);
+			if ab_abc.dot(ao) > T::ZERO {
+				*cp = *bp;
+				*bp = *ap;
+				*sp = dcross3(ab, ao);
+			} else {
+				let abc_ac = cross(abc, ac);
+				if abc_ac.dot(ao) > T::ZERO {
+					*bp = *ap;
+					*sp = dcross3(ac, ao);
+				} else {
+					if abc.dot(ao) > T::ZERO {
+						*dp = *cp;
+						*cp = *bp;
+						*bp = *ap;
+						*sp = abc;
+					} else {
+						*dp = *bp;
+						*bp = *ap;
+						*sp = -abc;
+					}
+					*w = 3;
+				}
+			}
+			false
+		}
+		3 => {
+			// The faces around the new point, each with the edges it shares with
+			// the next and previous face. The origin is in the region of a face
+			// it is in front of and within the edges of, and the first face in
+			// front of it only gives the region of an edge when no other does.
+			// The integer `simplex` of fixed.rs repeats this loop, keep the two
+			// alike.
+			let ad = dp.point - ap.point;
+			let faces = [(abc, *bp, *cp, ab, ac),
+			             (cross(ac, ad), *cp, *dp, ac, ad),
+			             (cross(ad, ab), *dp, *bp, ad, ab)];
+			let mut chosen = None;
+			for &(normal, second, third, first_edge, second_edge) in &faces {
+				if normal.dot(ao) > T::ZERO {
+					let region = triangle_region(ao, first_edge, second_edge, normal);
+					if chosen.is_none() || region == Region::Face {
+						chosen = Some((region, normal, second, third, first_edge, second_edge));
+					}
+					if region == Region::Face {
+						break;
+					}
+				}
+			}
+			match chosen {
+				Some((Region::FirstEdge, _, second, _, edge, _)) => {
+					*cp = second;
+					*bp = *ap;
+					*sp = dcross3(edge, ao);
+					*w = 2;
+				}
+				Some((Region::SecondEdge, _, _, third, _, edge)) => {
+					*cp = third;
+					*bp = *ap;
+					*sp = dcross3(edge, ao);
+					*w = 2;
+				}
+				Some((Region::Face, normal, second, third, _, _)) => {
+					*dp = third;
+					*cp = second;
+					*bp = *ap;
+					*sp = normal;
+				}
+				None => return true,
+			}
+			false
+		}
+		_ => false,
+	}
+}
+
+// Where the origin is around a triangle it is in front of, seen from the
+// triangle's newest corner, the edges to its other two corners
+#[derive(Clone, Copy, PartialEq)]
+enum Region {
+	FirstEdge,
+	SecondEdge,
+	Face,
+}
+
+fn triangle_region<T: Float>(ao: Vector3<T>,
+                             ab: Vector3<T>,
+                             ac: Vector3<T>,
+                             abc: Vector3<T>) -> Region {
+	if cross(ab, abc).dot(ao) > T::ZERO {
+		Region::FirstEdge
+	} else if cross(abc, ac).dot(ao) > T::ZERO {
+		Region::SecondEdge
+	} else {
+		Region::Face
+	}
+}
+
+fn cross<T: Float>(a: Vector3<T>, b: Vector3<T>) -> Vector3<T> {
+	a.cross(b)
+}
+
+fn cross3<T: Float>(a: Vector3<T>, b: Vector3<T>, c: Vector3<T>) -> Vector3<T> {
+	cross(cross(a, b), c)
+}
+
+fn dcross3<T: Float>(a: Vector3<T>, b: Vector3<T>) -> Vector3<T> {
+	cross3(a, b, a)
+}
+
+// The first of the farthest vertices, `None` for no vertices
+fn farthest<T: Float>(vertices: &[Vector3<T>], direction: Vector3<T>) -> Option<Vector3<T>> {
+	vertices.get(farthest_index(vertices, direction)).cloned()
+}
+
+// The index of the first of the farthest vertices, `0` for no vertices
+fn farthest_index<T: Float>(vertices: &[Vector3<T>], direction: Vector3<T>) -> usize {
+	let mut max: Option<T> = None;
+	let mut max_index = 0;
+	for (index, vertex) in vertices.iter().enumerate() {
+		let current = vertex.dot(direction);
+		if max.is_none_or(|value| current > value) {
+			max = Some(current);
+			max_index = index;
+		}
+	}
+	max_index
+}
+
+// The support of the Minkowski difference, taking an empty hull as the
+// origin like `EmptyHull::Origin`. Queries that have empty hulls intersect
+// nothing check `either_empty` before searching.
+fn support<T: Float>(vertices_a: &[Vector3<T>],
+                     vertices_b: &[Vector3<T>],
+                     direction: Vector3<T>) -> Vector3<T> {
+	let at = |vertices: &[Vector3<T>], direction| {
+		farthest(vertices, direction).unwrap_or(Vector3::ZERO)
+	};
+	at(vertices_a, direction) - at(vertices_b, -direction)
+}
+
+// Whether either hull has no points, and so intersects nothing by default,
+// see `EmptyHull`
+fn either_empty<V>(hull1: &[V], hull2: &[V]) -> bool {
+	hull1.is_empty() || hull2.is_empty()
+}
+
+// Like `support`, also giving the indices of the vertices it took
+fn hull_support<T: Float>(vertices_a: &[Vector3<T>],
+                          vertices_b: &[Vector3<T>],
+                          direction: Vector3<T>)
+                          -> Corner<T, (usize, usize)> {
+	let (a, b) = (farthest_index(vertices_a, direction), farthest_index(vertices_b, -direction));
+	let at = |vertices: &[Vector3<T>], index| vertices.get(index).cloned().unwrap_or(Vector3::ZERO);
+	Corner {
+		point: at(vertices_a, a) - at(vertices_b, b),
+		from: (a, b),
+	}
+}
+
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+
+	use std::f32;
+	use std::collections::HashSet;
+	use std::f32::consts::PI;
+	use std::mem;
+	use super::{Axis, EmptyHull, GjkConfig, Vec2, Vec3, bgjk, bgjk2, bgjk_config,
+	            bgjk_separating_axis, bgjk_simplex, bgjk_stats, bgjk_support, bgjk_with_hint,
+	            bgjk_witness_indices, vecs_as_floats, vecs_as_floats_mut};
+	use super::test_util::{cases, cube_at, unit_cube};
+	static EPS: f32 = f32::EPSILON;
+
+	// Whether `bgjk_separating_axis` finds an axis the hulls project apart on
+	fn separated(hull1: &[Vec3], hull2: &[Vec3]) -> bool {
+		bgjk_separating_axis(hull1, hull2).is_some_and(|axis| {
+			let top = hull1.iter().map(|v| v.dot(axis)).fold(f32::MIN, f32::max);
+			let bottom = hull2.iter().map(|v| v.dot(axis)).fold(f32::MAX, f32::min);
+			top < bottom
+		})
+	}
+
+	#[test]
+	fn separated_on_suite() {
+		for (name, hull1, hull2, hit) in cases() {
+			if !hit {
+				assert![separated(&hull1, &hull2), "{}", name];
+			}
+		}
+	}
+
+	// Whether `bgjk2` agrees with `bgjk` on hulls in the z = 0 plane, other
+	// hulls are skipped
+	fn planar_agrees(hull1: &[Vec3], hull2: &[Vec3]) -> bool {
+		let planar = |hull: &[Vec3]| hull.iter().all(|v| v.2 == 0.0);
+		if !planar(hull1) || !planar(hull2) {
+			return true;
+		}
+		let truncated = |hull: &[Vec3]| hull.iter().map(Vec3::truncate).collect::<Vec<Vec2>>();
+		bgjk2(&truncated(hull1), &truncated(hull2)) == bgjk(hull1, hull2)
+	}
+
+	#[test]
+	fn planar_agrees_on_suite() {
+		for (name, hull1, hull2, _) in cases() {
+			assert![planar_agrees(&hull1, &hull2), "{}", name];
+		}
+	}
+
+	// `bgjk` searches the slices by vertex index, `bgjk_support` through
+	// `SupportMap`, both have to give each case its answer
+	#[test]
+	fn slice_and_support_paths() {
+		for (name, hull1, hull2, hit) in cases() {
+			assert_eq![bgjk(&hull1, &hull2), hit, "{}", name];
+			assert_eq![bgjk_support(&hull1[..], &hull2[..]), hit, "{}", name];
+			assert_eq![bgjk(&hull2, &hull1), hit, "{}", name];
+			assert_eq![bgjk_support(&hull2[..], &hull1[..]), hit, "{}", name];
+		}
+	}
+
+	// Whether `bgjk_stats` agrees with `bgjk` within a few iterations
+	fn converges(hull1: &[Vec3], hull2: &[Vec3]) -> bool {
+		let (hit, stats) = bgjk_stats(hull1, hull2);
+		hit == bgjk(hull1, hull2) && stats.iterations < 20
+	}
+
+	#[test]
+	fn converges_on_suite() {
+		for (name, hull1, hull2, _) in cases() {
+			assert![converges(&hull1, &hull2), "{}", name];
+			assert![converges(&hull2, &hull1), "{}", name];
+		}
+	}
+
+	// Whether `bgjk_with_hint` agrees with `bgjk` for every hint, including
+	// hints pointing the wrong way and ones it has to fall back from
+	fn hints_agree(hull1: &[Vec3], hull2: &[Vec3]) -> bool {
+		let expected = bgjk(hull1, hull2);
+		let axis = bgjk_separating_axis(hull1, hull2).unwrap_or(Vec3::ONE);
+		let hints = [Vec3::ONE,
+		             -Vec3::ONE,
+		             Vec3::X,
+		             -Vec3::X,
+		             Vec3::Y,
+		             -Vec3::Y,
+		             Vec3::Z,
+		             -Vec3::Z,
+		             axis,
+		             -axis,
+		             Vec3(1e-30, -1e-30, 1e-30),
+		             Vec3(1e30, -1e30, 1e30),
+		             Vec3::ZERO,
+		             Vec3(f32::NAN, 0.0, 1.0),
+		             Vec3(f32::INFINITY, 0.0, 0.0)];
+		hints.iter().all(|&hint| bgjk_with_hint(hull1, hull2, hint) == expected)
+	}
+
+	#[test]
+	fn hints_agree_on_suite() {
+		for (name, hull1, hull2, _) in cases() {
+			assert![hints_agree(&hull1, &hull2), "{}", name];
+			assert![hints_agree(&hull2, &hull1), "{}", name];
+		}
+	}
+
+	#[test]
+	fn square1() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let shape2 = pts![(-2.0, 0.0, 0.0), (-3.0, 0.0, 0.0), (-2.0, 1.0, 0.0), (-3.0, 1.0, 0.0)];
+		assert_eq![bgjk(&shape1, &shape2), false];
+	}
+
+	#[test]
+	fn no_separating_axis_on_overlap() {
+		let square = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let line = pts![(0.5, 1.0, 0.0), (0.5, -1.0, 0.0)];
+		assert![bgjk_separating_axis(&square, &square).is_none()];
+		assert![bgjk_separating_axis(&square, &line).is_none()];
+	}
+
+	#[test]
+	fn simplex_encloses_origin() {
+		let cube = cube_at(Vec3::ZERO);
+		for &by in &[Vec3(0.5, 0.5, 0.5), Vec3(0.9, -0.3, 0.2), Vec3(-0.2, 0.7, -0.6), Vec3::ZERO] {
+			let other = cube_at(by);
+			let (hit, simplex) = bgjk_simplex(&cube, &other);
+			assert_eq![hit, true];
+			let points = simplex.points();
+			assert_eq![points.len(), 4];
+			for (point, &(i, j)) in points.iter().zip(simplex.vertices()) {
+				assert_eq![*point, cube[i] - other[j]];
+			}
+			// The origin is on the inner side of every face, as is the opposite corner
+			for &(a, b, c, d) in &[(0, 1, 2, 3), (0, 1, 3, 2), (0, 2, 3, 1), (1, 2, 3, 0)] {
+				let normal = (points[b] - points[a]).cross(points[c] - points[a]);
+				let inside = normal.dot(points[d] - points[a]);
+				assert![normal.dot(-points[a]) * inside >= -EPS];
+			}
+		}
+	}
+
+	#[test]
+	fn witnesses_on_touching_faces() {
+		let cube = cube_at(Vec3::ZERO);
+		for &by in &[Vec3::X, Vec3(1.0, 0.3, -0.2), Vec3(1.0, -0.7, 0.9), Vec3::X + Vec3::Y] {
+			let other = cube_at(by);
+			let (hit, ids) = bgjk_witness_indices(&cube, &other);
+			assert_eq![hit, true];
+			assert_eq![ids.pairs().len(), 4];
+			// The corners from the faces at x = 1 alone reach the origin
+			let touching: Vec<Vec3> = ids.pairs()
+				.iter()
+				.filter(|&&(i, j)| cube[i].0 == 1.0 && other[j].0 == 1.0)
+				.map(|&(i, j)| cube[i] - other[j])
+				.collect();
+			assert![touching.len() >= 2];
+			assert_eq![bgjk(&touching, &[Vec3::ZERO]), true];
+		}
+	}
+
+	#[test]
+	fn witnesses_in_range() {
+		let cube = cube_at(Vec3::ZERO);
+		for &point in &[Vec3(0.5, 0.5, 0.5), Vec3(3.0, 0.0, 0.0), Vec3(0.0, -2.0, 1.0), Vec3::ONE] {
+			let point = [point];
+			for &(hull1, hull2) in &[(&point[..], &cube[..]), (&cube, &point), (&point, &point)] {
+				let (hit, ids) = bgjk_witness_indices(hull1, hull2);
+				assert_eq![hit, bgjk(hull1, hull2)];
+				assert![!ids.pairs().is_empty() && ids.pairs().len() <= 4];
+				assert![ids.pairs().iter().all(|&(i, j)| i < hull1.len() && j < hull2.len())];
+			}
+		}
+	}
+
+	#[test]
+	fn witnesses_take_lowest_index() {
+		// Every vertex twice, the first copies at even indices
+		let cube = cube_at(Vec3::ZERO);
+		let doubled: Vec<Vec3> = cube.iter().flat_map(|&v| vec![v, v]).collect();
+		for &by in &[Vec3(0.5, 0.5, 0.5), Vec3(1.0, 0.0, 0.0), Vec3(2.0, 0.5, 0.0)] {
+			let other: Vec<Vec3> = doubled.iter().map(|&v| v + by).collect();
+			let (hit, ids) = bgjk_witness_indices(&doubled, &other);
+			assert_eq![hit, bgjk(&cube, &cube_at(by))];
+			assert![ids.pairs().iter().all(|&(i, j)| i % 2 == 0 && j % 2 == 0)];
+		}
+	}
+
+	#[test]
+	fn simplex_separates() {
+		let cube = cube_at(Vec3::ZERO);
+		for &by in &[Vec3(1.5, 0.0, 0.0), Vec3(1.1, 1.1, 1.1), Vec3(-0.3, 0.4, -2.0), Vec3(0.2, -1.01, 0.5)] {
+			let other = cube_at(by);
+			let (hit, simplex) = bgjk_simplex(&cube, &other);
+			assert_eq![hit, false];
+			assert![(1..4).contains(&simplex.points().len())];
+			for (point, &(i, j)) in simplex.points().iter().zip(simplex.vertices()) {
+				assert_eq![*point, cube[i] - other[j]];
+			}
+			let axis = simplex.direction;
+			let top = cube.iter().map(|v| v.dot(axis)).fold(f32::MIN, f32::max);
+			let bottom = other.iter().map(|v| v.dot(axis)).fold(f32::MAX, f32::min);
+			assert![top < bottom];
+		}
+	}
+
+	#[test]
+	fn exact_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let shape2 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		assert_eq![bgjk(&shape1, &shape2), true];
+	}
+
+	#[test]
+	fn line_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0)];
+		let shape2 = pts![(0.5, 1.0, 0.0), (0.5, -1.0, 0.0)];
+		assert_eq![bgjk(&shape1, &shape2), true];
+	}
+
+	#[test]
+	fn line_non_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0)];
+		let shape2 = pts![(1.5, 1.0, 0.0), (1.5, -1.0, 0.0)];
+		assert_eq![bgjk(&shape1, &shape2), false];
+	}
+
+	#[test]
+	fn small_line_point_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (0.01, 0.0, 0.0)];
+		let shape2 = pts![(0.005, 0.0, 0.1)];
+		assert_eq![bgjk(&shape1, &shape2), false];
+	}
+
+	#[test]
+	fn line_point_non_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0)];
+		let shape2 = pts![(0.5, 0.0, 0.1)];
+		assert_eq![bgjk(&shape1, &shape2), false];
+	}
+
+	#[test]
+	fn point_overlap() {
+		let shape1 = pts![(0.5, 1.0, 0.0)];
+		let shape2 = pts![(0.5, 1.0, 0.0)];
+		assert_eq![bgjk(&shape1, &shape2), true];
+	}
+
+	#[test]
+	fn point_no_overlap() {
+		let shape1 = pts![(0.5, 1.0, 0.0)];
+		let shape2 = pts![(1.0, 1.0, 0.0)];
+		assert_eq![bgjk(&shape1, &shape2), false];
+	}
+
+	#[test]
+	fn empty_no_overlap() {
+		// An empty set intersects nothing
+		let shape1: [Vec3; 0] = pts![];
+		let shape2 = pts![(1.0, 1.0, 1.0)];
+		assert_eq![bgjk(&shape1, &shape2), false];
+	}
+
+	#[test]
+	fn empty_around_origin() {
+		// Where an empty set used to be a point in origo, inside both shapes
+		let shape1: [Vec3; 0] = pts![];
+		let tetrahedron =
+			pts![(-1.0, -1.0, -1.0), (1.0, -1.0, -1.0), (0.0, 1.0, -1.0), (0.0, 0.0, 1.0)];
+		let square = pts![(-1.0, -1.0, 0.0), (1.0, -1.0, 0.0), (-1.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let origin = GjkConfig { empty: EmptyHull::Origin, ..GjkConfig::default() };
+		for shape2 in &[&tetrahedron[..], &square[..]] {
+			for &(a, b) in &[(&shape1[..], *shape2), (*shape2, &shape1[..])] {
+				assert_eq![bgjk(a, b), false];
+				assert_eq![bgjk_config(a, b, &origin), true];
+			}
+		}
+	}
+
+	#[test]
+	fn empty_empty() {
+		let shape1: [Vec3; 0] = pts![];
+		let shape2: [Vec3; 0] = pts![];
+		assert_eq![bgjk(&shape1, &shape2), false];
+		let origin = GjkConfig { empty: EmptyHull::Origin, ..GjkConfig::default() };
+		assert_eq![bgjk_config(&shape1, &shape2, &origin), true];
+		let (hit, simplex) = bgjk_simplex(&shape1, &shape2);
+		assert![!hit && simplex.points().is_empty() && simplex.vertices().is_empty()];
+		assert_eq![simplex.direction, Vec3::ONE];
+	}
+
+	#[test]
+	fn side_by_side_squares() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let shape2 = pts![(1.0, 0.0, 0.0), (2.0, 0.0, 0.0), (1.0, 1.0, 0.0), (2.0, 1.0, 0.0)];
+		assert_eq![bgjk(&shape1, &shape2), true];
+	}
+
+	#[test]
+	fn side_by_side_squares_offset() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let shape2 =
+			pts![(1.0 + EPS, 0.0, 0.0), (2.0, 0.0, 0.0), (1.0 + EPS, 1.0, 0.0), (2.0, 1.0, 0.0)];
+		assert_eq![bgjk(&shape1, &shape2), false];
+	}
+
+	#[test]
+	fn single_point_square_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let shape2 = pts![(1.0, 1.0, 0.0), (2.0, 1.0, 0.0), (1.0, 2.0, 0.0), (2.0, 2.0, 0.0)];
+		assert_eq![bgjk(&shape1, &shape2), true];
+	}
+
+	#[test]
+	fn single_point_shape_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0),
+		                 (1.0, 0.0, 0.0),
+		                 (0.0, 1.0, 0.0),
+		                 (1.0, 1.0, 0.0),
+		                 (0.0, 0.0, 1.0),
+		                 (1.0, 0.0, 1.0),
+		                 (0.0, 1.0, 1.0),
+		                 (1.0, 1.0, 1.0)];
+		let shape2 = pts![(1.0, 1.0, 1.0),
+		                 (2.0, 1.0, 1.0),
+		                 (1.0, 2.0, 1.0),
+		                 (2.0, 2.0, 1.0),
+		                 (1.0, 1.0, 2.0),
+		                 (2.0, 1.0, 2.0),
+		                 (1.0, 2.0, 2.0),
+		                 (2.0, 2.0, 2.0)];
+		assert_eq![bgjk(&shape1, &shape2), true];
+	}
+
+	#[test]
+	fn single_point_shape_non_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0),
+		                 (1.0, 0.0, 0.0),
+		                 (0.0, 1.0, 0.0),
+		                 (1.0, 1.0, 0.0),
+		                 (0.0, 0.0, 1.0),
+		                 (1.0, 0.0, 1.0),
+		                 (0.0, 1.0, 1.0),
+		                 (1.0, 1.0, 1.0)];
+		let shape2 = pts![(1.0, 1.0, 1.0 + EPS),
+		                 (2.0, 1.0, 1.0 + EPS),
+		                 (1.0, 2.0, 1.0 + EPS),
+		                 (2.0, 2.0, 1.0 + EPS),
+		                 (1.0, 1.0, 2.0),
+		                 (2.0, 1.0, 2.0),
+		                 (1.0, 2.0, 2.0),
+		                 (2.0, 2.0, 2.0)];
+		assert_eq![bgjk(&shape1, &shape2), false];
+	}
+
+	#[test]
+	fn single_line_shape_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0),
+		                 (1.0, 0.0, 0.0),
+		                 (0.0, 1.0, 0.0),
+		                 (1.0, 1.0, 0.0),
+		                 (0.0, 0.0, 1.0),
+		                 (1.0, 0.0, 1.0),
+		                 (0.0, 1.0, 1.0),
+		                 (1.0, 1.0, 1.0)];
+		let shape2 = pts![(1.0, 1.0, 0.0),
+		                 (2.0, 1.0, 0.0),
+		                 (1.0, 2.0, 0.0),
+		                 (2.0, 2.0, 0.0),
+		                 (1.0, 1.0, 1.0),
+		                 (2.0, 1.0, 1.0),
+		                 (1.0, 2.0, 1.0),
+		                 (2.0, 2.0, 1.0)];
+		assert_eq![bgjk(&shape1, &shape2), true];
+	}
+
+	#[test]
+	fn shape_projective_non_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0),
+		                 (1.0, 0.0, 0.0),
+		                 (0.0, 1.0, 0.0),
+		                 (1.0, 1.0, 0.0),
+		                 (1.0, 0.0, 1.0),
+		                 (2.0, 0.0, 1.0),
+		                 (1.0, 1.0, 1.0),
+		                 (2.0, 1.0, 1.0)];
+		let shape2 = pts![(1.1, 1.0, 0.0),
+		                 (2.1, 1.0, 0.0),
+		                 (1.1, 2.0, 0.0),
+		                 (2.1, 2.0, 0.0),
+		                 (2.1, 1.0, 1.0),
 		                 (3.1, 1.0, 1.0),
 		                 (2.1, 2.0, 1.0),
 		                 (3.1, 2.0, 1.0)];
@@ -406,80 +1910,970 @@ mod tests {
 	}
 
 	#[test]
-	fn shape_projective_overlap() {
-		let shape1 = pts![(0.0, 0.0, 0.0),
-		                 (1.0, 0.0, 0.0),
-		                 (0.0, 1.0, 0.0),
-		                 (1.0, 1.0, 0.0),
-		                 (1.0, 0.0, 1.0),
-		                 (2.0, 0.0, 1.0),
-		                 (1.0, 1.0, 1.0),
-		                 (2.0, 1.0, 1.0)];
-		let shape2 = pts![(1.1, 1.0, 0.0),
-		                 (2.1, 1.0, 0.0),
-		                 (1.1, 2.0, 0.0),
-		                 (2.1, 2.0, 0.0),
-		                 (2.0, 1.0, 1.0),
-		                 (3.1, 1.0, 1.0),
-		                 (2.0, 2.0, 1.0),
-		                 (3.1, 2.0, 1.0)];
-		assert_eq![bgjk(&shape1, &shape2), true];
+	fn shape_projective_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0),
+		                 (1.0, 0.0, 0.0),
+		                 (0.0, 1.0, 0.0),
+		                 (1.0, 1.0, 0.0),
+		                 (1.0, 0.0, 1.0),
+		                 (2.0, 0.0, 1.0),
+		                 (1.0, 1.0, 1.0),
+		                 (2.0, 1.0, 1.0)];
+		let shape2 = pts![(1.1, 1.0, 0.0),
+		                 (2.1, 1.0, 0.0),
+		                 (1.1, 2.0, 0.0),
+		                 (2.1, 2.0, 0.0),
+		                 (2.0, 1.0, 1.0),
+		                 (3.1, 1.0, 1.0),
+		                 (2.0, 2.0, 1.0),
+		                 (3.1, 2.0, 1.0)];
+		assert_eq![bgjk(&shape1, &shape2), true];
+	}
+
+	#[test]
+	fn shape_non_overlap() {
+		let (mut shape1, mut shape2) = (vec![], vec![]);
+		let units = 100;
+		shape1.reserve(units);
+		shape2.reserve(units);
+		for i in 0..units {
+			let radian = i as f32 / units as f32 * 2.0 * PI;
+			shape1.push(Vec3(radian.cos(), radian.sin(), 0.0));
+			shape2.push(Vec3(radian.cos(), radian.sin(), EPS));
+		}
+		assert_eq![bgjk(&shape1, &shape2), false];
+	}
+
+	#[test]
+	fn shape_overlap() {
+		let (mut shape1, mut shape2) = (vec![], vec![]);
+		let units = 100;
+		shape1.reserve(units);
+		shape2.reserve(units);
+		for i in 0..units {
+			let radian = i as f32 / units as f32 * 2.0 * PI;
+			shape1.push(Vec3(radian.cos(), radian.sin(), 0.0));
+			shape2.push(Vec3(radian.cos(), radian.sin(), 0.0));
+		}
+		assert_eq![bgjk(&shape1, &shape2), true];
+	}
+
+	#[test]
+	fn shape_section() {
+		let (mut shape1, mut shape2) = (vec![], vec![]);
+		let units = 100;
+		shape1.reserve(units);
+		shape2.reserve(units);
+		for i in 0..units {
+			let radian = i as f32 / units as f32 * 2.0 * PI;
+			shape1.push(Vec3(radian.cos(), radian.sin(), 0.0));
+			shape2.push(Vec3(radian.cos() + 0.5, radian.sin(), 0.0));
+		}
+		assert_eq![bgjk(&shape1, &shape2), true];
+	}
+
+	#[test]
+	fn shape_away() {
+		let (mut shape1, mut shape2) = (vec![], vec![]);
+		let units = 100;
+		shape1.reserve(units);
+		shape2.reserve(units);
+		for i in 0..units {
+			let radian = i as f32 / units as f32 * 2.0 * PI;
+			shape1.push(Vec3(radian.cos(), radian.sin(), 0.0));
+			shape2.push(Vec3(radian.cos() + 2.0 + 2.0 * EPS, radian.sin(), 0.0));
+		}
+		assert_eq![bgjk(&shape1, &shape2), false];
+	}
+
+	// The counterexample proptest `symmetric` shrank to, on which the
+	// tetrahedron step cycled forever while it took the edge of the first
+	// face in front of the origin over a face holding it
+	#[test]
+	fn symmetric_counterexample() {
+		let a = pts![(0.85714537, 0.0, 0.51507455),
+		             (-0.65454865, -0.14857095, -0.74127775),
+		             (0.6000292, 0.34966347, 0.719514),
+		             (0.88851756, -0.04747572, 0.45637986)];
+		let b = pts![(0.7751085, 0.28117922, 0.6288645),
+		             (1.2802012, -0.9599578, -0.17369407),
+		             (2.5, 0.0, 0.0),
+		             (1.8342001, -0.7973364, 0.5025584)];
+		assert_eq![bgjk(&a, &b), false];
+		assert_eq![bgjk(&b, &a), false];
+	}
+
+	#[test]
+	fn add_components() {
+		assert_eq![Vec3(1.0, 2.0, 3.0) + Vec3(4.0, -5.0, 0.5), Vec3(5.0, -3.0, 3.5)];
+	}
+
+	#[test]
+	fn add_by_reference() {
+		let a = pts![(1.0, 2.0, 3.0), (0.0, -1.0, 0.5)];
+		let b = pts![(1.0, 1.0, 1.0), (2.0, 2.0, 2.0)];
+		let sums: Vec<Vec3> = a.iter().zip(b.iter()).map(|(x, y)| x + y).collect();
+		assert_eq![sums, vec![Vec3(2.0, 3.0, 4.0), Vec3(2.0, 1.0, 2.5)]];
+		assert_eq![a.iter().fold(Vec3::default(), |acc, x| acc + x), Vec3(1.0, 1.0, 3.5)];
+		assert_eq![a.iter().map(|x| x + Vec3(1.0, 0.0, 0.0)).next(), Some(Vec3(2.0, 2.0, 3.0))];
+	}
+
+	#[test]
+	fn add_then_sub_is_identity() {
+		let values = pts![(0.0, 0.0, 0.0), (1.0, -2.0, 3.5), (0.25, 1e3, -7.0), (-0.5, 0.5, 16.0)];
+		for a in &values {
+			for b in &values {
+				assert_eq![*a + *b - *b, *a];
+				assert_eq![a + b - b, *a];
+			}
+		}
+	}
+
+	#[test]
+	fn add_non_finite() {
+		let inf = Vec3(f32::INFINITY, f32::NEG_INFINITY, 1.0);
+		let sum = inf + Vec3(1.0, 1.0, f32::NAN);
+		assert_eq![sum.0, f32::INFINITY];
+		assert_eq![sum.1, f32::NEG_INFINITY];
+		assert![sum.2.is_nan()];
+		assert![(inf + -inf).0.is_nan()];
+		assert![(inf - inf).0.is_nan()];
+	}
+
+	#[test]
+	fn translated_hull() {
+		let square = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let moved: Vec<Vec3> = square.iter().map(|v| v + Vec3(0.5, 0.5, 0.0)).collect();
+		assert_eq![bgjk(&square, &moved), true];
+		let away: Vec<Vec3> = square.iter().map(|v| v + Vec3(-3.0, 0.0, 0.0)).collect();
+		assert_eq![bgjk(&square, &away), false];
+	}
+
+	#[test]
+	fn scalar_multiplication() {
+		let v = Vec3(1.0, -2.0, 0.5);
+		assert_eq![v * 2.0, Vec3(2.0, -4.0, 1.0)];
+		assert_eq![2.0 * v, v * 2.0];
+		assert_eq![v * 1.0, v];
+		assert_eq![v * 0.0, Vec3(0.0, -0.0, 0.0)];
+		assert_eq![v * -3.0, -v * 3.0];
+		assert_eq![-3.0 * v, -(3.0 * v)];
+	}
+
+	#[test]
+	fn scaled_hull() {
+		let cube = unit_cube();
+		let other: Vec<Vec3> = cube.iter().map(|v| v + Vec3(2.0, 0.0, 0.0)).collect();
+		assert_eq![bgjk(&cube, &other), false];
+		let grown: Vec<Vec3> = cube.iter().map(|v| *v * 2.5).collect();
+		assert_eq![bgjk(&grown, &other), true];
+		let flattened: Vec<Vec3> = cube.iter().map(|v| 0.0 * *v).collect();
+		assert_eq![bgjk(&flattened, &cube), true];
+		assert_eq![bgjk(&flattened, &other), false];
+		let mirrored: Vec<Vec3> = other.iter().map(|v| *v * -1.0).collect();
+		assert_eq![bgjk(&mirrored, &cube), false];
+		assert_eq![bgjk(&mirrored, &grown), false];
+		let mirrored_grown: Vec<Vec3> = grown.iter().map(|v| -1.0 * *v).collect();
+		assert_eq![bgjk(&mirrored, &mirrored_grown), true];
+	}
+
+	#[test]
+	fn scalar_division() {
+		let cube = unit_cube();
+		let sum = cube.iter().fold(Vec3::default(), |acc, v| acc + v);
+		assert_eq![sum / cube.len() as f32, Vec3(0.5, 0.5, 0.5)];
+		assert_eq![Vec3(1.0, -2.0, 0.5) / -2.0, Vec3(-0.5, 1.0, -0.25)];
+	}
+
+	#[test]
+	fn scalar_division_by_zero() {
+		let v = Vec3(1.0, -1.0, 0.0) / 0.0;
+		assert_eq![v.0, f32::INFINITY];
+		assert_eq![v.1, f32::NEG_INFINITY];
+		assert![v.2.is_nan()];
+		assert_eq![Vec3(1.0, 1.0, 1.0).checked_div(0.0), None];
+		assert_eq![Vec3(1.0, 1.0, 1.0).checked_div(-0.0), None];
+		assert_eq![Vec3(1.0, 2.0, 4.0).checked_div(2.0), Some(Vec3(0.5, 1.0, 2.0))];
+	}
+
+	#[test]
+	fn scalar_division_round_trip() {
+		let points = pts![(1.0, -2.0, 0.5), (1e-3, 7.0, -1e4), (0.1, 0.2, 0.3)];
+		for v in &points {
+			for k in &[3.0f32, -0.7, 1e-3, 123.456] {
+				assert![((*v * *k) / *k).approx_eq_relative(*v, 0.0, EPS)];
+			}
+		}
+	}
+
+	#[test]
+	fn compound_assignment() {
+		let mut v = Vec3(1.0, 2.0, 3.0);
+		v += Vec3(1.0, 1.0, 1.0);
+		assert_eq![v, Vec3(2.0, 3.0, 4.0)];
+		v -= Vec3(0.5, 0.5, 0.5);
+		assert_eq![v, Vec3(1.5, 2.5, 3.5)];
+		v *= 2.0;
+		assert_eq![v, Vec3(3.0, 5.0, 7.0)];
+		v /= 4.0;
+		assert_eq![v, Vec3(0.75, 1.25, 1.75)];
+	}
+
+	#[test]
+	fn translate_in_place() {
+		let other = unit_cube();
+		let delta = Vec3(1.5, 0.25, 0.0);
+		let mut hull = unit_cube();
+		for v in hull.iter_mut() {
+			*v += delta;
+		}
+		let fresh: Vec<Vec3> = unit_cube().iter().map(|v| v + delta).collect();
+		assert_eq![hull.to_vec(), fresh];
+		assert_eq![bgjk(&hull, &other), bgjk(&fresh, &other)];
+		assert_eq![bgjk(&hull, &other), false];
+		for v in hull.iter_mut() {
+			*v -= delta;
+			*v *= 3.0;
+			*v /= 2.0;
+		}
+		let fresh: Vec<Vec3> = unit_cube().iter().map(|v| *v * 3.0 / 2.0).collect();
+		assert_eq![hull.to_vec(), fresh];
+		assert_eq![bgjk(&hull, &other), true];
+	}
+
+	#[test]
+	fn index_by_axis() {
+		let mut v = Vec3(1.0, 2.0, 3.0);
+		assert_eq![(v[0], v[1], v[2]), (1.0, 2.0, 3.0)];
+		assert_eq![(v[Axis::X], v[Axis::Y], v[Axis::Z]), (1.0, 2.0, 3.0)];
+		v[0] = -1.0;
+		v[Axis::Y] = -2.0;
+		v[2] += 1.0;
+		assert_eq![v, Vec3(-1.0, -2.0, 4.0)];
 	}
 
 	#[test]
-	fn shape_non_overlap() {
-		let (mut shape1, mut shape2) = (vec![], vec![]);
-		let units = 100;
-		shape1.reserve(units);
-		shape2.reserve(units);
-		for i in 0..units {
-			let radian = i as f32 / units as f32 * 2.0 * PI;
-			shape1.push(Vec3(radian.cos(), radian.sin(), 0.0));
-			shape2.push(Vec3(radian.cos(), radian.sin(), EPS));
+	fn index_extents() {
+		let hull: Vec<Vec3> = unit_cube().iter().map(|v| *v * 2.0 + Vec3(0.0, 1.0, -3.0)).collect();
+		let mut extents = [(f32::INFINITY, f32::NEG_INFINITY); 3];
+		for v in &hull {
+			for (axis, extent) in extents.iter_mut().enumerate() {
+				extent.0 = extent.0.min(v[axis]);
+				extent.1 = extent.1.max(v[axis]);
+			}
 		}
-		assert_eq![bgjk(&shape1, &shape2), false];
+		assert_eq![extents, [(0.0, 2.0), (1.0, 3.0), (-3.0, -1.0)]];
 	}
 
 	#[test]
-	fn shape_overlap() {
-		let (mut shape1, mut shape2) = (vec![], vec![]);
-		let units = 100;
-		shape1.reserve(units);
-		shape2.reserve(units);
-		for i in 0..units {
-			let radian = i as f32 / units as f32 * 2.0 * PI;
-			shape1.push(Vec3(radian.cos(), radian.sin(), 0.0));
-			shape2.push(Vec3(radian.cos(), radian.sin(), 0.0));
+	#[should_panic(expected = "Vec3 index out of range: the index is 3 but must be 0, 1, or 2")]
+	fn index_out_of_range() {
+		let v = Vec3(1.0, 2.0, 3.0);
+		let _ = v[3];
+	}
+
+	#[test]
+	#[should_panic(expected = "Vec3 index out of range")]
+	fn index_mut_out_of_range() {
+		let mut v = Vec3(1.0, 2.0, 3.0);
+		v[usize::MAX] = 0.0;
+	}
+
+	#[test]
+	fn array_and_tuple_conversions() {
+		let v = Vec3(1.0, -2.0, 3.5);
+		let array: [f32; 3] = v.into();
+		assert_eq![array, [1.0, -2.0, 3.5]];
+		assert_eq![Vec3::from(array), v];
+		let tuple: (f32, f32, f32) = v.into();
+		assert_eq![tuple, (1.0, -2.0, 3.5)];
+		assert_eq![Vec3::from(tuple), v];
+	}
+
+	#[test]
+	fn hull_from_arrays() {
+		let loaded = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [1.0, 1.0, 0.0]];
+		let hull = Vec3::slice_from_arrays(&loaded);
+		assert_eq![hull.len(), loaded.len()];
+		for (vertex, array) in hull.iter().zip(loaded.iter()) {
+			assert_eq![<[f32; 3]>::from(*vertex), *array];
 		}
-		assert_eq![bgjk(&shape1, &shape2), true];
+		let other = pts![(0.5, 0.5, -1.0), (0.5, 0.5, 1.0)];
+		assert_eq![bgjk(&hull, &other), true];
+		let other = pts![(1.5, 0.5, -1.0), (1.5, 0.5, 1.0)];
+		assert_eq![bgjk(&hull, &other), false];
 	}
 
 	#[test]
-	fn shape_section() {
-		let (mut shape1, mut shape2) = (vec![], vec![]);
-		let units = 100;
-		shape1.reserve(units);
-		shape2.reserve(units);
-		for i in 0..units {
-			let radian = i as f32 / units as f32 * 2.0 * PI;
-			shape1.push(Vec3(radian.cos(), radian.sin(), 0.0));
-			shape2.push(Vec3(radian.cos() + 0.5, radian.sin(), 0.0));
+	fn sum() {
+		let cube = unit_cube();
+		assert_eq![cube.iter().sum::<Vec3>(), Vec3(4.0, 4.0, 4.0)];
+		assert_eq![cube.iter().cloned().sum::<Vec3>() / cube.len() as f32, Vec3(0.5, 0.5, 0.5)];
+		let moved: Vec<Vec3> = cube.iter().map(|v| *v * 2.0 - Vec3(3.0, 0.0, 1.0)).collect();
+		assert_eq![moved.iter().sum::<Vec3>() / moved.len() as f32, Vec3(-2.0, 1.0, 0.0)];
+		assert_eq![Vec::<Vec3>::new().into_iter().sum::<Vec3>(), Vec3::ZERO];
+		let huge = [Vec3(f32::MAX, 0.0, 0.0), Vec3(f32::MAX, 0.0, 0.0)];
+		assert_eq![huge.iter().sum::<Vec3>().0, f32::INFINITY];
+	}
+
+	#[test]
+	fn hull_from_iter() {
+		let tuples = (0..4).map(|i| ((i % 2) as f32, (i / 2) as f32, 0.0));
+		let hull = Vec3::hull_from_iter(tuples);
+		assert_eq![hull, pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)]];
+		assert_eq![bgjk(&hull, &pts![(0.5, 0.5, -1.0), (0.5, 0.5, 1.0)]), true];
+		assert_eq![bgjk(&hull, &pts![(1.5, 0.5, -1.0), (1.5, 0.5, 1.0)]), false];
+		let arrays = vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0]];
+		assert_eq![Vec3::hull_from_iter(arrays), pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0)]];
+		assert_eq![Vec3::hull_from_iter(unit_cube().iter().cloned()), unit_cube()];
+	}
+
+	#[test]
+	fn display() {
+		assert_eq![format!("{}", Vec3(1.0, 2.5, -3.0)), "(1, 2.5, -3)"];
+		assert_eq![format!("{:.3}", Vec3(1.0, 2.5, -1.0 / 3.0)), "(1.000, 2.500, -0.333)"];
+		assert_eq![format!("{:5.1}", Vec3(1.0, -2.0, 30.0)), "(  1.0,  -2.0,  30.0)"];
+		assert_eq![format!("{}", Vec3(-0.0, 0.0, -0.0)), "(-0, 0, -0)"];
+	}
+
+	#[test]
+	fn display_exponent() {
+		assert_eq![format!("{:e}", Vec3(1.0, 1500.0, -0.25)), "(1e0, 1.5e3, -2.5e-1)"];
+		assert_eq![format!("{:.2e}", Vec3(EPS, 0.0, -EPS)), "(1.19e-7, 0.00e0, -1.19e-7)"];
+	}
+
+	#[test]
+	fn hash_consistent_with_eq() {
+		let mut set = HashSet::new();
+		assert![set.insert(Vec3(0.0, 1.0, -0.0))];
+		assert![!set.insert(Vec3(-0.0, 1.0, 0.0))];
+		assert![set.contains(&Vec3(0.0, 1.0, 0.0))];
+		assert![set.insert(Vec3(0.0, 1.0, EPS))];
+		assert_eq![set.len(), 2];
+		let nan = Vec3(f32::NAN, 0.0, 0.0);
+		assert![set.insert(nan)];
+		assert![!set.contains(&nan)];
+	}
+
+	#[test]
+	fn hash_dedup_hull() {
+		let cube = unit_cube();
+		let repeated: Vec<Vec3> = cube.iter().chain(cube.iter()).chain(cube.iter()).cloned().collect();
+		let unique: HashSet<Vec3> = repeated.iter().cloned().collect();
+		assert_eq![unique.len(), cube.len()];
+		let deduped: Vec<Vec3> = unique.into_iter().collect();
+		let others = [pts![(0.5, 0.5, 0.5)], pts![(1.0, 1.0, 1.0 + EPS)], pts![(1.0, 1.0, 1.0)]];
+		for other in &others {
+			assert_eq![bgjk(&deduped, other), bgjk(&repeated, other)];
 		}
-		assert_eq![bgjk(&shape1, &shape2), true];
 	}
 
 	#[test]
-	fn shape_away() {
-		let (mut shape1, mut shape2) = (vec![], vec![]);
-		let units = 100;
-		shape1.reserve(units);
-		shape2.reserve(units);
-		for i in 0..units {
-			let radian = i as f32 / units as f32 * 2.0 * PI;
-			shape1.push(Vec3(radian.cos(), radian.sin(), 0.0));
-			shape2.push(Vec3(radian.cos() + 2.0 + 2.0 * EPS, radian.sin(), 0.0));
+	fn min_max_extents() {
+		let hull: Vec<Vec3> = unit_cube().iter().map(|v| *v * 2.0 + Vec3(0.0, 1.0, -3.0)).collect();
+		let start = (Vec3::ONE * f32::INFINITY, Vec3::ONE * f32::NEG_INFINITY);
+		let (lo, hi) = hull.iter().fold(start, |(lo, hi), v| (lo.min(*v), hi.max(*v)));
+		assert_eq![lo, Vec3(0.0, 1.0, -3.0)];
+		assert_eq![hi, Vec3(2.0, 3.0, -1.0)];
+		assert_eq![Vec3(1.0, -1.0, 0.0).min(Vec3(-1.0, 1.0, 0.0)), Vec3(-1.0, -1.0, 0.0)];
+		assert_eq![Vec3(1.0, -1.0, 0.0).max(Vec3(-1.0, 1.0, 0.0)), Vec3(1.0, 1.0, 0.0)];
+	}
+
+	#[test]
+	fn min_max_nan() {
+		let nan = Vec3(f32::NAN, 1.0, f32::NAN);
+		assert_eq![nan.min(Vec3(2.0, 2.0, -2.0)), Vec3(2.0, 1.0, -2.0)];
+		assert_eq![Vec3(2.0, 2.0, -2.0).max(nan), Vec3(2.0, 2.0, -2.0)];
+		assert![nan.min(nan).0.is_nan()];
+		assert_eq![nan.max(nan).1, 1.0];
+	}
+
+	#[test]
+	fn clamp() {
+		let (lo, hi) = (Vec3(-1.0, 0.0, 2.0), Vec3(1.0, 0.0, 4.0));
+		assert_eq![Vec3(-5.0, 3.0, 3.0).clamp(lo, hi), Vec3(-1.0, 0.0, 3.0)];
+		assert_eq![Vec3(5.0, -3.0, 5.0).clamp(lo, hi), Vec3(1.0, 0.0, 4.0)];
+		assert_eq![lo.clamp(lo, hi), lo];
+		assert_eq![hi.clamp(lo, hi), hi];
+		assert![Vec3(f32::NAN, 0.0, 0.0).clamp(lo, hi).0.is_nan()];
+		let noisy = pts![(1.0 + EPS, -EPS, 0.5), (-EPS, 1.0 + EPS, 0.5), (0.5, 0.5, 1.0 + EPS)];
+		for v in &noisy {
+			let snapped = v.clamp(Vec3::ZERO, Vec3::ONE);
+			assert_eq![bgjk(&[snapped], &unit_cube()), true];
+		}
+	}
+
+	#[test]
+	#[should_panic(expected = "Vec3 clamp bounds are inverted or NaN")]
+	fn clamp_inverted_bounds() {
+		let _ = Vec3::ZERO.clamp(Vec3(0.0, 1.0, 0.0), Vec3(1.0, 0.0, 1.0));
+	}
+
+	#[test]
+	#[should_panic(expected = "Vec3 clamp bounds are inverted or NaN")]
+	fn clamp_nan_bounds() {
+		let _ = Vec3::ZERO.clamp(Vec3(0.0, 0.0, f32::NAN), Vec3::ONE);
+	}
+
+	#[test]
+	fn rotate_quarter_turn() {
+		assert![Vec3::X.rotated_about(Vec3::Z, PI / 2.0).approx_eq(Vec3::Y, EPS)];
+		assert![Vec3::Y.rotated_about(Vec3::X * 5.0, PI / 2.0).approx_eq(Vec3::Z, EPS)];
+		assert![Vec3::Z.rotated_about(-Vec3::Y, PI / 2.0).approx_eq(-Vec3::X, EPS)];
+		let mut cube = unit_cube();
+		Vec3::rotate_points(&mut cube, Vec3::Z, PI / 2.0);
+		let by_hand: Vec<Vec3> = unit_cube().iter().map(|v| Vec3(-v.1, v.0, v.2)).collect();
+		for (rotated, expected) in cube.iter().zip(by_hand.iter()) {
+			assert![rotated.approx_eq(*expected, 2.0 * EPS)];
+		}
+		let probes: [&[Vec3]; 5] = [&pts![(-0.5, 0.5, 0.5)],
+		                            &pts![(0.5, 0.5, 0.5)],
+		                            &pts![(-1.5, 0.5, 0.5)],
+		                            &pts![(-0.5, 0.5, 0.9), (-0.5, 0.5, 1.5)],
+		                            &pts![(0.2, -1.0, 0.0), (0.2, 2.0, 0.0), (0.2, 0.0, 2.0)]];
+		for probe in probes.iter() {
+			assert_eq![bgjk(&cube, probe), bgjk(&by_hand, probe)];
+		}
+	}
+
+	#[test]
+	fn rotate_full_turn() {
+		let axes = pts![(0.0, 0.0, 1.0), (1.0, 2.0, 3.0), (-0.3, 0.1, -7.0), (1e-3, 0.0, 1e3)];
+		for axis in &axes {
+			let mut hull = unit_cube();
+			for _ in 0..8 {
+				Vec3::rotate_points(&mut hull, *axis, PI / 4.0);
+			}
+			for (rotated, original) in hull.iter().zip(unit_cube().iter()) {
+				assert![rotated.approx_eq(*original, 1e-5)];
+			}
+			let v = Vec3(1.0, -2.0, 0.5);
+			assert![v.rotated_about(*axis, 2.0 * PI).approx_eq(v, 1e-5)];
+			assert![(v.rotated_about(*axis, 1.0).length() - v.length()).abs() <= 4.0 * EPS];
+			let along = v.project_onto(*axis);
+			assert![v.rotated_about(*axis, 1.0).project_onto(*axis).approx_eq(along, 1e-5)];
+		}
+	}
+
+	#[test]
+	fn rotate_zero_axis() {
+		let v = Vec3(1.0, -2.0, 0.5);
+		assert_eq![v.rotated_about(Vec3::ZERO, 1.0), v];
+		assert_eq![v.rotated_about(Vec3(f32::NAN, 0.0, 0.0), 1.0), v];
+		assert_eq![Vec3::X.rotated_about(Vec3::X, 1.0), Vec3::X];
+	}
+
+	#[test]
+	fn reflect_hull() {
+		let mut hull: Vec<Vec3> = unit_cube().iter().map(|v| v + Vec3(0.5, 0.0, 0.0)).collect();
+		let original = hull.clone();
+		Vec3::reflect_points(&mut hull, Vec3::X);
+		let by_hand: Vec<Vec3> = original.iter().map(|v| Vec3(-v.0, v.1, v.2)).collect();
+		assert_eq![hull, by_hand];
+		let probes: [&[Vec3]; 4] = [&pts![(-1.0, 0.5, 0.5)],
+		                            &pts![(1.0, 0.5, 0.5)],
+		                            &pts![(-0.25, 0.5, 0.5)],
+		                            &pts![(0.0, 0.5, 0.5), (-0.6, 0.5, 0.5)]];
+		for probe in probes.iter() {
+			assert_eq![bgjk(&hull, probe), bgjk(&by_hand, probe)];
+		}
+		assert_eq![bgjk(&hull, &original), false];
+		Vec3::reflect_points(&mut hull, Vec3::X);
+		assert_eq![hull, original];
+	}
+
+	#[test]
+	fn reflect_twice() {
+		let normals = pts![(1.0, 2.0, 3.0), (-0.3, 0.1, -7.0), (1.0, 1.0, 0.0), (0.0, 0.0, -1.0)];
+		let points = pts![(1.0, -2.0, 0.5), (0.1, 0.2, 0.3), (-7.0, 3.0, 11.0)];
+		for normal in &normals {
+			let n = normal.normalized();
+			for v in &points {
+				let once = v.reflected(n);
+				assert![(once.length() - v.length()).abs() <= 4.0 * EPS * v.length()];
+				assert![(once.dot(n) + v.dot(n)).abs() <= 4.0 * EPS * v.length()];
+				assert![once.reflected(n).approx_eq(*v, 8.0 * EPS * v.length())];
+			}
+		}
+	}
+
+	#[test]
+	#[cfg(debug_assertions)]
+	#[should_panic(expected = "Vec3 reflection normal is not unit length")]
+	fn reflect_non_unit_normal() {
+		let _ = Vec3::ONE.reflected(Vec3(0.0, 2.0, 0.0));
+	}
+
+	#[test]
+	fn component_product() {
+		let (a, b) = (Vec3(1.0, -2.0, 0.5), Vec3(4.0, 3.0, -2.0));
+		assert_eq![a.mul_component(b), Vec3(4.0, -6.0, -1.0)];
+		assert_eq![a.mul_component(b), b.mul_component(a)];
+		assert_eq![a.mul_component(Vec3::ONE), a];
+		assert_eq![a.div_component(b), Vec3(0.25, -2.0 / 3.0, -0.25)];
+		assert_eq![a.mul_component(b).div_component(b), a];
+		let v = Vec3(1.0, -1.0, 0.0).div_component(Vec3::ZERO);
+		assert_eq![(v.0, v.1), (f32::INFINITY, f32::NEG_INFINITY)];
+		assert![v.2.is_nan()];
+	}
+
+	#[test]
+	fn stretched_hull() {
+		let size = Vec3(2.0, 3.0, 4.0);
+		let stretched: Vec<Vec3> = unit_cube().iter().map(|v| v.mul_component(size)).collect();
+		let built = pts![(0.0, 0.0, 0.0),
+		                 (2.0, 0.0, 0.0),
+		                 (0.0, 3.0, 0.0),
+		                 (2.0, 3.0, 0.0),
+		                 (0.0, 0.0, 4.0),
+		                 (2.0, 0.0, 4.0),
+		                 (0.0, 3.0, 4.0),
+		                 (2.0, 3.0, 4.0)];
+		assert_eq![stretched, built.to_vec()];
+		let probes: [&[Vec3]; 6] = [&pts![(1.0, 1.5, 2.0)],
+		                            &pts![(2.0, 3.0, 4.0)],
+		                            &pts![(2.0 + 2.0 * EPS, 1.0, 1.0)],
+		                            &pts![(1.0, 3.5, 1.0)],
+		                            &pts![(1.0, 1.0, 3.9), (1.0, 1.0, 5.0)],
+		                            &pts![(3.0, 0.0, 0.0), (3.0, 4.0, 0.0), (3.0, 0.0, 5.0)]];
+		for probe in probes.iter() {
+			assert_eq![bgjk(&stretched, probe), bgjk(&built, probe)];
+		}
+		let shrunk: Vec<Vec3> = stretched.iter().map(|v| v.div_component(size)).collect();
+		assert_eq![shrunk, unit_cube().to_vec()];
+	}
+
+	#[test]
+	fn abs_and_signum() {
+		let values = [2.5f32, -2.5, 0.0, -0.0];
+		for &x in &values {
+			for &y in &values {
+				for &z in &values {
+					let v = Vec3(x, y, z);
+					let (abs, signum) = (v.abs(), v.signum());
+					for axis in 0..3 {
+						assert_eq![abs[axis].to_bits(), v[axis].abs().to_bits()];
+						assert_eq![signum[axis], if v[axis].is_sign_negative() { -1.0 } else { 1.0 }];
+						assert_eq![abs[axis], signum[axis] * v[axis]];
+					}
+					assert_eq![v.abs().copysign(v), v];
+					assert_eq![Vec3::ONE.copysign(v), signum];
+				}
+			}
+		}
+		let nan = Vec3(f32::NAN, f32::INFINITY, f32::NEG_INFINITY);
+		assert![nan.signum().0.is_nan()];
+		assert_eq![(nan.signum().1, nan.signum().2), (1.0, -1.0)];
+		assert_eq![(nan.abs().1, nan.abs().2), (f32::INFINITY, f32::INFINITY)];
+	}
+
+	#[test]
+	fn copysign() {
+		let v = Vec3(1.0, -2.0, 3.0);
+		assert_eq![v.copysign(Vec3(-5.0, 5.0, -0.0)), Vec3(-1.0, 2.0, -3.0)];
+		assert_eq![v.copysign(Vec3::ONE), v.abs()];
+		assert_eq![v.copysign(-Vec3::ONE), -v.abs()];
+		let mirrored: Vec<Vec3> = unit_cube().iter().map(|p| p.copysign(Vec3(-1.0, 1.0, 1.0))).collect();
+		assert_eq![bgjk(&mirrored, &pts![(-0.5, 0.5, 0.5)]), true];
+		assert_eq![bgjk(&mirrored, &pts![(0.5, 0.5, 0.5)]), false];
+	}
+
+	#[test]
+	fn lerp_endpoints() {
+		let points = pts![(1.0, -2.0, 0.5), (0.1, 0.2, 0.3), (1e-3, 7.0, -1e4), (1e30, -1e-30, 3.0)];
+		for a in &points {
+			for b in &points {
+				assert_eq![a.lerp(*b, 0.0), *a];
+				assert_eq![a.lerp(*b, 1.0), *b];
+			}
+		}
+		let (a, b) = (Vec3(0.0, 2.0, -4.0), Vec3(4.0, 2.0, 0.0));
+		assert_eq![a.lerp(b, 0.5), Vec3(2.0, 2.0, -2.0)];
+		assert_eq![a.lerp(b, 2.0), Vec3(8.0, 2.0, 4.0)];
+		assert_eq![a.lerp(b, -1.0), Vec3(-4.0, 2.0, -8.0)];
+		assert_eq![a.lerp_clamped(b, 2.0), b];
+		assert_eq![a.lerp_clamped(b, -1.0), a];
+		assert_eq![a.lerp_clamped(b, 0.25), a.lerp(b, 0.25)];
+	}
+
+	#[test]
+	fn lerp_swept_hull() {
+		let cube = unit_cube();
+		let start: Vec<Vec3> = cube.iter().map(|v| v + Vec3(-3.0, 0.0, 0.0)).collect();
+		let end: Vec<Vec3> = cube.iter().map(|v| v + Vec3(3.0, 0.0, 0.0)).collect();
+		let at = |t: f32| -> Vec<Vec3> {
+			start.iter().zip(end.iter()).map(|(a, b)| a.lerp(*b, t)).collect()
+		};
+		// The moving cube overlaps for offsets in [-1, 1], that is t in [1/3, 2/3]
+		assert_eq![at(0.0), start];
+		assert_eq![at(1.0), end];
+		assert_eq![bgjk(&at(0.0), &cube), bgjk(&start, &cube)];
+		assert_eq![bgjk(&at(1.0), &cube), bgjk(&end, &cube)];
+		for &(t, hit) in &[(0.0, false), (0.3, false), (0.35, true), (0.5, true), (0.65, true),
+		                  (0.7, false), (1.0, false)] {
+			assert_eq![bgjk(&at(t), &cube), hit];
+		}
+	}
+
+	#[test]
+	fn finite_and_nan() {
+		assert![Vec3(1.0, -2.0, 3.0).is_finite()];
+		assert![!Vec3(1.0, -2.0, 3.0).is_nan()];
+		assert![Vec3(f32::MAX, f32::MIN, 1e-40).is_finite()];
+		for axis in 0..3 {
+			let mut v = Vec3::ONE;
+			v[axis] = f32::NAN;
+			assert![!v.is_finite()];
+			assert![v.is_nan()];
+			for &inf in &[f32::INFINITY, f32::NEG_INFINITY] {
+				v[axis] = inf;
+				assert![!v.is_finite()];
+				assert![!v.is_nan()];
+			}
+		}
+	}
+
+	#[test]
+	#[cfg(debug_assertions)]
+	#[should_panic(expected = "bgjk called with a non-finite point in a hull")]
+	fn nan_hull() {
+		let mut exploded = unit_cube();
+		exploded[5] = Vec3(0.0, f32::NAN, 0.0);
+		bgjk(&unit_cube(), &exploded);
+	}
+
+	#[test]
+	#[cfg(debug_assertions)]
+	#[should_panic(expected = "bgjk called with a non-finite point in a hull")]
+	fn infinite_hull() {
+		bgjk(&[Vec3(f32::INFINITY, 0.0, 0.0)], &unit_cube());
+	}
+
+	#[test]
+	fn layout() {
+		assert_eq![mem::size_of::<Vec3>(), 3 * mem::size_of::<f32>()];
+		assert_eq![mem::align_of::<Vec3>(), mem::align_of::<f32>()];
+		assert_eq![mem::size_of::<[Vec3; 4]>(), 12 * mem::size_of::<f32>()];
+	}
+
+	#[test]
+	fn swizzles() {
+		let v = Vec3(1.0, 2.0, 3.0);
+		assert_eq![v.xy(), Vec2(1.0, 2.0)];
+		assert_eq![v.xz(), Vec2(1.0, 3.0)];
+		assert_eq![v.yz(), Vec2(2.0, 3.0)];
+		assert_eq![v.truncate(), v.xy()];
+		assert_eq![Vec3::from_xy(v.xy(), v.2), v];
+		assert_eq![v.truncate().extend(v.z()), v];
+		assert_eq![Vec2(1.0, 2.0).extend(0.0), Vec3::from(Vec2(1.0, 2.0))];
+	}
+
+	#[test]
+	fn array_views() {
+		let mut v = Vec3(1.0, 2.0, 3.0);
+		assert_eq![v.as_array(), &[1.0, 2.0, 3.0]];
+		v.as_mut_array()[1] = -2.0;
+		v.as_mut_array()[2] *= 2.0;
+		assert_eq![v, Vec3(1.0, -2.0, 6.0)];
+		assert_eq![*v.as_array(), <[f32; 3]>::from(v)];
+	}
+
+	#[test]
+	fn float_views() {
+		let mut hull = unit_cube();
+		assert_eq![vecs_as_floats(&hull).len(), 24];
+		assert_eq![&vecs_as_floats(&hull)[3..6], &[1.0, 0.0, 0.0]];
+		assert_eq![vecs_as_floats(&[]).len(), 0];
+		let other = pts![(1.5, 0.5, 0.5)];
+		assert_eq![bgjk(&hull, &other), false];
+		for x in vecs_as_floats_mut(&mut hull).iter_mut().step_by(3) {
+			*x *= 2.0;
+		}
+		assert_eq![hull[1], Vec3(2.0, 0.0, 0.0)];
+		assert_eq![bgjk(&hull, &other), true];
+	}
+
+	#[test]
+	fn named_components() {
+		let mut v = Vec3::new(1.0, 2.0, 3.0);
+		assert_eq![v, Vec3(1.0, 2.0, 3.0)];
+		assert_eq![(v.x(), v.y(), v.z()), (v.0, v.1, v.2)];
+		v.set_x(-1.0);
+		v.set_y(-2.0);
+		v.set_z(-3.0);
+		assert_eq![v, Vec3(-1.0, -2.0, -3.0)];
+		assert_eq![(v.x(), v.y(), v.z()), (v.0, v.1, v.2)];
+	}
+
+	static WALL: [Vec3; 8] = [Vec3::new(0.0, 0.0, 0.0),
+	                          Vec3::new(4.0, 0.0, 0.0),
+	                          Vec3::new(0.0, 3.0, 0.0),
+	                          Vec3::new(4.0, 3.0, 0.0),
+	                          Vec3::new(0.0, 0.0, 0.5),
+	                          Vec3::new(4.0, 0.0, 0.5),
+	                          Vec3::new(0.0, 3.0, 0.5),
+	                          Vec3::new(4.0, 3.0, 0.5)];
+	const X_COMPONENT: f32 = Vec3::X.x();
+
+	#[test]
+	fn static_hull() {
+		assert_eq![X_COMPONENT, 1.0];
+		assert_eq![bgjk(&WALL, &unit_cube()), true];
+		let behind: Vec<Vec3> = unit_cube().iter().map(|v| v + Vec3::new(0.0, 0.0, 0.6)).collect();
+		assert_eq![bgjk(&WALL, &behind), false];
+	}
+
+	#[test]
+	fn constants() {
+		assert_eq![Vec3::ZERO, Vec3(0.0, 0.0, 0.0)];
+		assert_eq![Vec3::ONE, Vec3(1.0, 1.0, 1.0)];
+		assert_eq![Vec3::X, Vec3(1.0, 0.0, 0.0)];
+		assert_eq![Vec3::Y, Vec3(0.0, 1.0, 0.0)];
+		assert_eq![Vec3::Z, Vec3(0.0, 0.0, 1.0)];
+		assert_eq![Vec3::default(), Vec3::ZERO];
+		assert_eq![Vec3::ones(), Vec3::ONE];
+		assert_eq![Vec3::X + Vec3::Y + Vec3::Z, Vec3::ONE];
+	}
+
+	#[test]
+	fn length() {
+		assert_eq![Vec3::ZERO.length(), 0.0];
+		assert_eq![Vec3::X.length(), 1.0];
+		assert_eq![Vec3(3.0, -4.0, 0.0).length(), 5.0];
+		assert_eq![Vec3(3.0, -4.0, 0.0).length_squared(), 25.0];
+		assert_eq![Vec3(1.0, 2.0, 2.0).length(), 3.0];
+		assert_eq![Vec3(1.0, 2.0, 2.0).length_squared(), 9.0];
+	}
+
+	#[test]
+	fn length_non_finite() {
+		assert_eq![Vec3(f32::INFINITY, 0.0, 0.0).length(), f32::INFINITY];
+		assert_eq![Vec3(0.0, f32::NEG_INFINITY, 0.0).length_squared(), f32::INFINITY];
+		assert![Vec3(0.0, 0.0, f32::NAN).length().is_nan()];
+		assert![Vec3(f32::INFINITY, f32::NAN, 0.0).length_squared().is_nan()];
+		assert_eq![Vec3(f32::MAX, 0.0, 0.0).length_squared(), f32::INFINITY];
+	}
+
+	#[test]
+	fn dot_and_cross() {
+		assert_eq![Vec3(1.0, 2.0, 3.0).dot(Vec3(4.0, -5.0, 6.0)), 12.0];
+		assert_eq![Vec3::X.cross(Vec3::Y), Vec3::Z];
+		assert_eq![Vec3::Y.cross(Vec3::Z), Vec3::X];
+		assert_eq![Vec3::Z.cross(Vec3::X), Vec3::Y];
+		assert_eq![Vec3::Y.cross(Vec3::X), -Vec3::Z];
+		assert_eq![Vec3::X.cross(Vec3::X), Vec3::ZERO];
+		assert_eq![Vec3::triple(Vec3::X, Vec3::Y, Vec3::Z), 1.0];
+	}
+
+	#[test]
+	fn triple_product() {
+		assert_eq![Vec3::triple(Vec3::X, Vec3::Y, Vec3::Z), 1.0];
+		assert_eq![Vec3::triple(Vec3::Y, Vec3::Z, Vec3::X), 1.0];
+		assert_eq![Vec3::triple(Vec3::Y, Vec3::X, Vec3::Z), -1.0];
+		assert_eq![Vec3::triple(Vec3::X * 2.0, Vec3::Y * 3.0, Vec3::Z * 4.0), 24.0];
+		assert_eq![Vec3::triple(Vec3::X, Vec3::Y, Vec3(1.0, 1.0, 1.0)), 1.0];
+		let (a, b) = (Vec3(1.0, 2.0, 3.0), Vec3(-4.0, 0.5, 2.0));
+		assert_eq![Vec3::triple(a, b, a * 2.0 - b), 0.0];
+		assert_eq![Vec3::triple(a, a, b), 0.0];
+		let points = pts![(1.0, 2.0, 3.0), (-4.0, 0.5, 2.0), (0.0, -1.0, 7.0), (3.0, 3.0, -3.0)];
+		for a in &points {
+			for b in &points {
+				for c in &points {
+					let volume = Vec3::triple(*a, *b, *c);
+					assert_eq![Vec3::triple(*b, *a, *c), -volume];
+					assert_eq![Vec3::triple(*a, *c, *b), -volume];
+					assert_eq![Vec3::triple(*c, *b, *a), -volume];
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn cross_orthogonal() {
+		let points = pts![(1.0, 2.0, 3.0), (-4.0, 0.5, 2.0), (0.0, -1.0, 7.0), (3.0, 3.0, -3.0)];
+		for a in &points {
+			for b in &points {
+				let c = a.cross(*b);
+				assert_eq![c.dot(*a), 0.0];
+				assert_eq![c.dot(*b), 0.0];
+				assert_eq![b.cross(*a), -c];
+			}
+		}
+	}
+
+	#[test]
+	fn orthonormal_pair() {
+		let mut directions = pts![(1.0, 0.0, 0.0),
+		                         (0.0, 1.0, 0.0),
+		                         (0.0, 0.0, 1.0),
+		                         (1.0, 1e-7, -1e-7),
+		                         (1e-7, 1e-7, 1.0),
+		                         (1.0, 0.0, 1.0 + EPS),
+		                         (1.0, 2.0, 3.0),
+		                         (1e-30, 5e-31, 0.0),
+		                         (1e30, -1e30, 1e29)]
+			.to_vec();
+		directions.extend(directions.clone().iter().map(|d| -*d));
+		for i in 0..100 {
+			let (theta, phi) = (i as f32 * 0.37, i as f32 * 0.11);
+			directions.push(Vec3(theta.cos() * phi.sin(), theta.sin() * phi.sin(), phi.cos()));
+		}
+		for d in &directions {
+			let n = d.normalized();
+			let (a, b) = d.any_orthonormal_pair();
+			assert![(a.length() - 1.0).abs() <= 4.0 * EPS];
+			assert![(b.length() - 1.0).abs() <= 4.0 * EPS];
+			assert![a.dot(n).abs() <= 4.0 * EPS];
+			assert![b.dot(n).abs() <= 4.0 * EPS];
+			assert![a.dot(b).abs() <= 4.0 * EPS];
+			assert![a.cross(b).approx_eq(n, 4.0 * EPS)];
+			assert_eq![d.any_orthogonal(), a];
+		}
+	}
+
+	#[test]
+	fn orthonormal_pair_degenerate() {
+		assert_eq![Vec3::ZERO.any_orthonormal_pair(), (Vec3::Y, Vec3::Z)];
+		assert_eq![Vec3(f32::NAN, 0.0, 0.0).any_orthonormal_pair(), (Vec3::Y, Vec3::Z)];
+		assert_eq![Vec3::ZERO.any_orthogonal(), Vec3::Y];
+	}
+
+	#[test]
+	fn distance() {
+		let points = pts![(1.0, -2.0, 0.5), (0.1, 0.2, 0.3), (-7.0, 3.0, 11.0), (2.5, 2.5, -2.5)];
+		for a in &points {
+			assert_eq![a.distance(*a), 0.0];
+			assert_eq![a.distance_squared(*a), 0.0];
+			for b in &points {
+				assert_eq![a.distance(*b), (*a - *b).length()];
+				assert_eq![a.distance_squared(*b), (*a - *b).length_squared()];
+				assert_eq![a.distance(*b), b.distance(*a)];
+				for c in &points {
+					assert![a.distance(*c) <= (a.distance(*b) + b.distance(*c)) * (1.0 + EPS)];
+				}
+			}
+		}
+		assert_eq![Vec3(1.0, 2.0, 3.0).distance(Vec3(4.0, 6.0, 3.0)), 5.0];
+		assert_eq![Vec3(1.0, 2.0, 3.0).distance_squared(Vec3(4.0, 6.0, 3.0)), 25.0];
+	}
+
+	#[test]
+	fn angle_between() {
+		let v = Vec3(1.0, 2.0, -3.0);
+		assert_eq![v.angle_between(v), 0.0];
+		assert_eq![v.angle_between(v * 7.5), 0.0];
+		assert![(v.angle_between(-v) - PI).abs() <= EPS];
+		assert![(Vec3::X.angle_between(Vec3::Y) - PI / 2.0).abs() <= EPS];
+		assert![(Vec3::Y.angle_between(-Vec3::Z) - PI / 2.0).abs() <= EPS];
+		assert![(Vec3::X.angle_between(Vec3(1.0, 1.0, 0.0)) - PI / 4.0).abs() <= EPS];
+		assert![(v.angle_between(v.cross(Vec3::Z)) - PI / 2.0).abs() <= 2.0 * EPS];
+	}
+
+	#[test]
+	fn angle_between_near_parallel() {
+		let directions = pts![(1.0, 1.0, 1.0), (0.1, 0.2, 0.3), (-7.0, 3.0, 11.0), (0.0, 1.0, 1e-3)];
+		for d in &directions {
+			for &(small, large) in &[(1e-20f32, 1e20f32), (1e-3, 1e30), (1.0, 1e36)] {
+				let angle = (*d * small).angle_between(*d * large);
+				assert![(0.0..1e-3).contains(&angle)];
+				let angle = (*d * small).angle_between(-*d * large);
+				assert![angle <= PI && angle > PI - 1e-3];
+			}
+		}
+	}
+
+	#[test]
+	fn angle_between_degenerate() {
+		assert_eq![Vec3::ZERO.try_angle_between(Vec3::X), None];
+		assert_eq![Vec3::X.try_angle_between(Vec3::ZERO), None];
+		assert_eq![Vec3(f32::NAN, 1.0, 0.0).try_angle_between(Vec3::X), None];
+		assert_eq![Vec3::ZERO.angle_between(Vec3::X), 0.0];
+		assert_eq![Vec3::X.try_angle_between(Vec3::X), Some(0.0)];
+	}
+
+	#[test]
+	fn project_and_reject() {
+		let v = Vec3(3.0, -4.0, 5.0);
+		assert_eq![v.project_onto(Vec3::X), Vec3(3.0, 0.0, 0.0)];
+		assert_eq![v.project_onto(Vec3::Y * -2.0), Vec3(0.0, -4.0, 0.0)];
+		assert_eq![v.reject_from(Vec3::Z), Vec3(3.0, -4.0, 0.0)];
+		assert_eq![v.project_onto(v), v];
+		assert_eq![v.reject_from(v), Vec3::ZERO];
+		let points = pts![(1.0, -2.0, 0.5), (0.1, 0.2, 0.3), (-7.0, 3.0, 11.0), (2.5, 2.5, -2.5)];
+		for a in &points {
+			for axis in &points {
+				let (projected, rejected) = (a.project_onto(*axis), a.reject_from(*axis));
+				let scale = a.length() * axis.length();
+				assert![(projected + rejected).approx_eq(*a, 4.0 * EPS * a.length())];
+				assert![rejected.dot(*axis).abs() <= 8.0 * EPS * scale];
+				assert![projected.cross(*axis).approx_eq(Vec3::ZERO, 8.0 * EPS * scale)];
+			}
+		}
+	}
+
+	#[test]
+	fn project_onto_zero() {
+		let v = Vec3(3.0, -4.0, 5.0);
+		assert_eq![v.project_onto(Vec3::ZERO), Vec3::ZERO];
+		assert_eq![v.reject_from(Vec3::ZERO), v];
+		assert_eq![Vec3::ZERO.project_onto(v), Vec3::ZERO];
+	}
+
+	#[test]
+	fn approx_eq() {
+		let v = Vec3(1.0, -2.0, 3.0);
+		assert![v.approx_eq(v, 0.0)];
+		assert![v.approx_eq(v + Vec3(5e-4, -5e-4, 0.0), 1e-3)];
+		assert![!v.approx_eq(v + Vec3(0.0, 0.0, 2e-3), 1e-3)];
+		assert![v.approx_eq_relative(v * 1.001, 0.0, 1e-3)];
+		assert![!v.approx_eq_relative(v * 1.01, 0.0, 1e-3)];
+		assert![Vec3(1e6, 0.0, 0.0).approx_eq_relative(Vec3(1e6 + 1.0, 0.0, 0.0), 1e-3, 1e-5)];
+		assert![!Vec3(1e6, 0.0, 0.0).approx_eq(Vec3(1e6 + 1.0, 0.0, 0.0), 1e-3)];
+	}
+
+	#[test]
+	fn approx_eq_non_finite() {
+		let inf = Vec3(f32::INFINITY, 0.0, f32::NEG_INFINITY);
+		assert![inf.approx_eq(inf, 0.0)];
+		assert![!inf.approx_eq(-inf, f32::MAX)];
+		assert![!inf.approx_eq(Vec3(f32::MAX, 0.0, f32::NEG_INFINITY), f32::MAX)];
+		assert![!inf.approx_eq_relative(Vec3(f32::MAX, 0.0, -f32::MAX), 0.0, 1.0)];
+		let nan = Vec3(0.0, f32::NAN, 0.0);
+		assert![!nan.approx_eq(nan, f32::INFINITY)];
+		assert![!nan.approx_eq_relative(Vec3::ZERO, f32::INFINITY, f32::INFINITY)];
+	}
+
+	#[test]
+	fn normalize_zero() {
+		assert_eq![Vec3::ZERO.try_normalized(), None];
+		assert_eq![Vec3::ZERO.normalized(), Vec3::ZERO];
+		let mut v = Vec3::ZERO;
+		v.normalize();
+		assert_eq![v, Vec3::ZERO];
+		assert_eq![Vec3(f32::NAN, 1.0, 0.0).try_normalized(), None];
+		assert_eq![Vec3(f32::INFINITY, 1.0, 0.0).try_normalized(), None];
+	}
+
+	#[test]
+	fn normalize_denormal() {
+		let tiny = Vec3(1e-40, 0.0, -1e-40);
+		assert_eq![tiny.length_squared(), 0.0];
+		let unit = tiny.try_normalized().unwrap();
+		assert![unit.approx_eq(Vec3(0.5f32.sqrt(), 0.0, -(0.5f32.sqrt())), EPS)];
+	}
+
+	#[test]
+	fn normalize_magnitudes() {
+		let directions = pts![(1.0, 0.0, 0.0), (1.0, 2.0, 3.0), (-0.3, 0.1, -7.0), (1.0, 1.0, 1.0)];
+		for direction in &directions {
+			for magnitude in &[1e-30f32, 1e-3, 1.0, 1e3, 1e30, f32::MAX] {
+				let v = direction.normalized() * *magnitude;
+				assert![(v.normalized().length() - 1.0).abs() <= 2.0 * EPS];
+				let mut w = v;
+				w.normalize();
+				assert_eq![w, v.normalized()];
+			}
 		}
-		assert_eq![bgjk(&shape1, &shape2), false];
 	}
 
 }
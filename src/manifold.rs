@@ -0,0 +1,397 @@
+//! Contact manifolds, several contact points for stable resting contact.
+use std::cmp::Ordering;
+use super::{Float, Vector3, contact_normal, penetration};
+
+// How far a point may lie below the farthest extent of its hull along the
+// normal and still count as part of the touching feature, relative to the
+// thickness of the hull along the normal
+const FEATURE_TOLERANCE: f32 = 1e-4;
+
+/// A point of a `Manifold`
+#[derive(Clone, Copy, Debug)]
+pub struct ContactPoint<T = f32> {
+	/// Halfway between the surfaces of the two hulls
+	pub position: Vector3<T>,
+	/// How far the hulls overlap along the normal at this point
+	pub depth: T,
+}
+
+/// The contact region of two intersecting hulls, see `contact_manifold`
+#[derive(Clone, Copy, Debug)]
+pub struct Manifold<T = f32> {
+	/// Unit direction pointing from the first hull towards the second
+	pub normal: Vector3<T>,
+	/// The largest depth of the points
+	pub depth: T,
+	points: [ContactPoint<T>; 4],
+	len: usize,
+}
+
+impl<T> Manifold<T> {
+	/// The contact points, between one and four of them
+	pub fn points(&self) -> &[ContactPoint<T>] {
+		&self.points[..self.len]
+	}
+}
+
+// The points of a hull on its touching feature, ordered counter-clockwise
+// around the normal when they form a polygon, and how far the hull extends
+// along `direction`
+struct Feature<T> {
+	points: Vec<Vector3<T>>,
+	extent: T,
+}
+
+impl<T: Float> Feature<T> {
+	fn new(hull: &[Vector3<T>],
+	       direction: Vector3<T>,
+	       (u, v): (Vector3<T>, Vector3<T>)) -> Feature<T> {
+		let (lo, hi) = hull.iter().fold((hull[0].dot(direction), hull[0].dot(direction)),
+		                                |(lo, hi), point| {
+			                                let height = point.dot(direction);
+			                                (lo.min(height), hi.max(height))
+		                                });
+		let tolerance = (hi - lo) * T::from_f32(FEATURE_TOLERANCE);
+		let on: Vec<Vector3<T>> =
+			hull.iter().cloned().filter(|point| point.dot(direction) >= hi - tolerance).collect();
+		Feature {
+			points: convex_polygon(on, u, v),
+			extent: hi,
+		}
+	}
+}
+
+// Twice the signed area of the triangle `o`, `a`, `b` in the plane
+fn cross2<T: Float>(o: (T, T), a: (T, T), b: (T, T)) -> T {
+	(a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+}
+
+// The corners of the convex hull of the points projected onto the plane
+// spanned by `u` and `v`, counter-clockwise, using Andrew's monotone chain
+fn convex_polygon<T: Float>(mut points: Vec<Vector3<T>>,
+                            u: Vector3<T>,
+                            v: Vector3<T>) -> Vec<Vector3<T>> {
+	let flat = |point: &Vector3<T>| (point.dot(u), point.dot(v));
+	points.sort_by(|a, b| {
+		let (a, b) = (flat(a), flat(b));
+		a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+	});
+	if points.len() < 3 {
+		points.dedup_by(|a, b| flat(a) == flat(b));
+		return points;
+	}
+	let mut corners: Vec<Vector3<T>> = Vec::with_capacity(points.len() + 1);
+	for pass in 0..2 {
+		let start = corners.len();
+		for i in 0..points.len() {
+			let point = if pass == 0 { points[i] } else { points[points.len() - 1 - i] };
+			while corners.len() >= start + 2 &&
+			      cross2(flat(&corners[corners.len() - 2]),
+			             flat(&corners[corners.len() - 1]),
+			             flat(&point)) <= T::ZERO {
+				corners.pop();
+			}
+			corners.push(point);
+		}
+		// The last corner of each chain starts the other one
+		corners.pop();
+	}
+	if corners.len() == 2 && flat(&corners[0]) == flat(&corners[1]) {
+		corners.pop();
+	}
+	corners
+}
+
+// Clip the incident feature against the sides of the reference polygon
+fn clip_to_polygon<T: Float>(incident: &[Vector3<T>],
+                             reference: &[Vector3<T>],
+                             u: Vector3<T>,
+                             v: Vector3<T>)
+                             -> Vec<Vector3<T>> {
+	let flat = |point: Vector3<T>| (point.dot(u), point.dot(v));
+	let mut kept = incident.to_vec();
+	for i in 0..reference.len() {
+		let (a, b) = (flat(reference[i]), flat(reference[(i + 1) % reference.len()]));
+		let side = |point: Vector3<T>| cross2(a, b, flat(point));
+		let input = kept;
+		kept = Vec::with_capacity(input.len() + 1);
+		// A segment is clipped as an open path, a polygon as a closed one
+		let edges = if input.len() == 2 { 1 } else { input.len() };
+		if input.len() == 1 && side(input[0]) >= T::ZERO {
+			kept.push(input[0]);
+		}
+		for j in 0..edges {
+			let (from, to) = (input[j], input[(j + 1) % input.len()]);
+			let (from_side, to_side) = (side(from), side(to));
+			if from_side >= T::ZERO && (input.len() != 2 || j == 0) {
+				kept.push(from);
+			}
+			if (from_side >= T::ZERO) != (to_side >= T::ZERO) {
+				kept.push(from + (to - from) * (from_side / (from_side - to_side)));
+			}
+			if input.len() == 2 && to_side >= T::ZERO {
+				kept.push(to);
+			}
+		}
+		if kept.is_empty() {
+			break;
+		}
+	}
+	kept
+}
+
+// The contact of two segments in the plane, their crossing or the overlap
+// of the incident one with the reference one when they are parallel
+fn clip_to_segment<T: Float>(incident: &[Vector3<T>],
+                             reference: &[Vector3<T>],
+                             u: Vector3<T>,
+                             v: Vector3<T>)
+                             -> Vec<Vector3<T>> {
+	let flat = |point: Vector3<T>| (point.dot(u), point.dot(v));
+	let (a, b) = (flat(reference[0]), flat(reference[1]));
+	if incident.len() == 1 {
+		return incident.to_vec();
+	}
+	let (c, d) = (flat(incident[0]), flat(incident[1]));
+	let denominator = cross2((T::ZERO, T::ZERO), (b.0 - a.0, b.1 - a.1), (d.0 - c.0, d.1 - c.1));
+	let along = incident[1] - incident[0];
+	let scale = along.length_squared() + (b.0 - a.0).abs() + (b.1 - a.1).abs();
+	if denominator.abs() > T::EPSILON * scale {
+		let t = cross2(a, b, c) / -denominator;
+		return vec![incident[0] + along * t.clamp(T::ZERO, T::ONE)];
+	}
+	// Parallel, keep the part of the incident segment beside the reference one
+	let direction = (b.0 - a.0, b.1 - a.1);
+	let project = |point: (T, T)| (point.0 - a.0) * direction.0 + (point.1 - a.1) * direction.1;
+	let end = project(b);
+	let (from, to) = (project(c), project(d));
+	let mut kept = Vec::with_capacity(2);
+	for &bound in &[T::ZERO, end] {
+		if (from - bound) * (to - bound) < T::ZERO {
+			kept.push(incident[0] + along * ((bound - from) / (to - from)));
+		}
+	}
+	for (point, at) in incident.iter().zip(&[from, to]) {
+		if *at >= T::ZERO && *at <= end {
+			kept.push(*point);
+		}
+	}
+	kept
+}
+
+// Keep the deepest point and the three that span the largest area with it
+fn reduce<T: Float>(points: &mut Vec<ContactPoint<T>>, normal: Vector3<T>) {
+	if points.len() <= 4 {
+		return;
+	}
+	let deepest = (0..points.len())
+		.max_by(|&i, &j| points[i].depth.partial_cmp(&points[j].depth).unwrap_or(Ordering::Equal))
+		.expect("more than four points");
+	let first = points.swap_remove(deepest);
+	let mut chosen = vec![first];
+	let area = |a: Vector3<T>, b: Vector3<T>, c: Vector3<T>| (b - a).cross(c - a).dot(normal);
+	let pick = |points: &[ContactPoint<T>], score: &dyn Fn(Vector3<T>) -> T| {
+		(0..points.len())
+			.max_by(|&i, &j| {
+				score(points[i].position)
+					.partial_cmp(&score(points[j].position))
+					.unwrap_or(Ordering::Equal)
+			})
+			.expect("points left to pick from")
+	};
+	let second = points.swap_remove(pick(points, &|p| p.distance_squared(first.position)));
+	chosen.push(second);
+	let third =
+		points.swap_remove(pick(points, &|p| area(first.position, second.position, p).abs()));
+	let sign = area(first.position, second.position, third.position);
+	let fourth =
+		points.swap_remove(pick(points, &|p| -area(first.position, second.position, p) * sign));
+	chosen.push(third);
+	chosen.push(fourth);
+	*points = chosen;
+}
+
+/// The contact region of two intersecting convex hulls
+///
+/// Finds the normal with `contact_normal`, or with `penetration` for deeper
+/// overlaps, then takes the feature of each hull that touches the other one
+/// along that normal: the points within a small tolerance of its farthest
+/// extent, forming a face, an edge or a vertex. The feature with the most
+/// points is the reference, the other one, the incident feature, is clipped
+/// against its sides. This gives four points for boxes resting face to face
+/// and a single point for edges crossing each other. Larger contact regions
+/// are reduced to the deepest point and three points spanning a large area
+/// with it.
+///
+/// Returns `None` when the hulls do not intersect.
+///
+/// ```
+/// use bgjk::{contact_manifold, Vec3};
+///
+/// let cube = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0),
+///             Vec3::new(1.0, 1.0, 0.0), Vec3::new(0.0, 0.0, 1.0), Vec3::new(1.0, 0.0, 1.0),
+///             Vec3::new(0.0, 1.0, 1.0), Vec3::new(1.0, 1.0, 1.0)];
+/// let stacked: Vec<Vec3> = cube.iter().map(|v| *v + Vec3::new(0.5, 0.0, 1.0)).collect();
+/// let manifold = contact_manifold(&cube, &stacked).unwrap();
+/// assert_eq![manifold.points().len(), 4];
+/// ```
+pub fn contact_manifold<T: Float>(hull1: &[Vector3<T>],
+                                  hull2: &[Vector3<T>]) -> Option<Manifold<T>> {
+	let normal = match contact_normal(hull1, hull2) {
+		Some(normal) => normal,
+		None => penetration(hull1, hull2)?.normal,
+	};
+	if hull1.is_empty() || hull2.is_empty() {
+		return None;
+	}
+	let (u, v) = normal.any_orthonormal_pair();
+	let feature1 = Feature::new(hull1, normal, (u, v));
+	let mut feature2 = Feature::new(hull2, -normal, (u, v));
+	feature2.extent = -feature2.extent;
+
+	// Depth below the reference feature, and the point halfway to it
+	let reference_is_first = feature1.points.len() >= feature2.points.len();
+	let (reference, incident) = if reference_is_first {
+		(&feature1, &feature2)
+	} else {
+		(&feature2, &feature1)
+	};
+	let clipped = match reference.points.len() {
+		1 => incident.points[..1].to_vec(),
+		2 => clip_to_segment(&incident.points, &reference.points, u, v),
+		_ => clip_to_polygon(&incident.points, &reference.points, u, v),
+	};
+	let contact = |point: Vector3<T>| {
+		let depth = if reference_is_first {
+			feature1.extent - point.dot(normal)
+		} else {
+			point.dot(normal) - feature2.extent
+		};
+		let half = if reference_is_first { depth } else { -depth } / (T::ONE + T::ONE);
+		ContactPoint {
+			position: point + normal * half,
+			depth,
+		}
+	};
+	let mut points: Vec<ContactPoint<T>> = clipped.into_iter().map(&contact).collect();
+	let slack = (feature1.extent - feature2.extent).abs() * T::from_f32(FEATURE_TOLERANCE);
+	let deepest = points.iter().fold(None, |deepest: Option<T>, point| {
+		Some(deepest.map_or(point.depth, |deepest| deepest.max(point.depth)))
+	});
+	match deepest {
+		Some(deepest) => points.retain(|point| point.depth >= deepest.min(-slack)),
+		None => points.push(contact(incident.points[0])),
+	}
+	reduce(&mut points, normal);
+
+	let mut manifold = Manifold {
+		normal,
+		depth: points[0].depth,
+		points: [points[0]; 4],
+		len: points.len(),
+	};
+	for (i, point) in points.iter().enumerate() {
+		manifold.points[i] = *point;
+		manifold.depth = manifold.depth.max(point.depth);
+	}
+	Some(manifold)
+}
+
+#[cfg(test)]
+mod tests {
+
+	use std::f32;
+	use super::super::{DVec3, Vec3, contact_manifold};
+	use super::super::test_util::{cube_at, shifted, unit_cube};
+	static EPS: f32 = f32::EPSILON;
+
+	#[test]
+	fn stacked_cubes() {
+		let cube = unit_cube();
+		let manifold = contact_manifold(&cube, &shifted(&cube, Vec3::Z)).unwrap();
+		assert![manifold.normal.approx_eq(Vec3::Z, 4.0 * EPS)];
+		assert![manifold.depth.abs() <= 4.0 * EPS];
+		assert_eq![manifold.points().len(), 4];
+		for point in manifold.points() {
+			assert![(point.position.z() - 1.0).abs() <= 4.0 * EPS];
+			assert![point.depth.abs() <= 4.0 * EPS];
+		}
+	}
+
+	#[test]
+	fn offset_stacked_cubes() {
+		let cube = unit_cube();
+		let manifold = contact_manifold(&cube, &shifted(&cube, Vec3(0.5, 0.25, 0.9))).unwrap();
+		assert![manifold.normal.approx_eq(Vec3::Z, 4.0 * EPS)];
+		assert![(manifold.depth - 0.1).abs() <= 4.0 * EPS];
+		assert_eq![manifold.points().len(), 4];
+		for point in manifold.points() {
+			assert![point.position.x() >= 0.5 - 4.0 * EPS && point.position.x() <= 1.0 + 4.0 * EPS];
+			assert![point.position.y() >= 0.25 - 4.0 * EPS && point.position.y() <= 1.0 + 4.0 * EPS];
+			assert![(point.position.z() - 0.95).abs() <= 4.0 * EPS];
+			assert![(point.depth - 0.1).abs() <= 4.0 * EPS];
+		}
+		let mut corners: Vec<(f32, f32)> =
+			manifold.points().iter().map(|p| (p.position.x(), p.position.y())).collect();
+		corners.sort_by(|a, b| a.partial_cmp(b).unwrap());
+		let expected = [(0.5, 0.25), (0.5, 1.0), (1.0, 0.25), (1.0, 1.0)];
+		for (corner, expected) in corners.iter().zip(&expected) {
+			assert![(corner.0 - expected.0).abs() <= 4.0 * EPS];
+			assert![(corner.1 - expected.1).abs() <= 4.0 * EPS];
+		}
+	}
+
+	#[test]
+	fn cube_on_ground() {
+		let cube = unit_cube();
+		let ground: Vec<Vec3> = cube.iter()
+			.map(|v| v.mul_component(Vec3(20.0, 20.0, 1.0)) - Vec3(10.0, 10.0, 1.0))
+			.collect();
+		let manifold = contact_manifold(&ground, &shifted(&cube, Vec3(2.0, -3.0, -0.01))).unwrap();
+		assert![manifold.normal.approx_eq(Vec3::Z, 4.0 * EPS)];
+		assert![(manifold.depth - 0.01).abs() <= 16.0 * EPS];
+		assert_eq![manifold.points().len(), 4];
+		for point in manifold.points() {
+			assert![point.position.x() >= 2.0 - 4.0 * EPS && point.position.x() <= 3.0 + 4.0 * EPS];
+			assert![point.position.y() >= -3.0 - 4.0 * EPS && point.position.y() <= -2.0 + 4.0 * EPS];
+		}
+	}
+
+	#[test]
+	fn crossed_edges() {
+		let mut lower: Vec<Vec3> = unit_cube().iter().map(|v| *v - Vec3::ONE * 0.5).collect();
+		let mut upper = lower.clone();
+		Vec3::rotate_points(&mut lower, Vec3::Y, f32::consts::FRAC_PI_4);
+		Vec3::rotate_points(&mut upper, Vec3::X, f32::consts::FRAC_PI_4);
+		let upper = shifted(&upper, Vec3(0.0, 0.0, f32::consts::SQRT_2 - 0.01));
+		let manifold = contact_manifold(&lower, &upper).unwrap();
+		assert![manifold.normal.approx_eq(Vec3::Z, 1e-4)];
+		assert![(manifold.depth - 0.01).abs() <= 1e-4];
+		assert_eq![manifold.points().len(), 1];
+		let expected = Vec3(0.0, 0.0, f32::consts::FRAC_1_SQRT_2 - 0.005);
+		assert![manifold.points()[0].position.approx_eq(expected, 1e-4)];
+	}
+
+	#[test]
+	fn apart() {
+		let cube = unit_cube();
+		assert![contact_manifold(&cube, &shifted(&cube, Vec3(0.0, 0.0, 1.5))).is_none()];
+	}
+
+	#[test]
+	fn double_precision() {
+		let cube: Vec<DVec3> = unit_cube().iter().map(|&v| v.into()).collect();
+		let other: Vec<DVec3> = cube.iter().map(|v| *v + DVec3::new(0.5, 0.5, 0.95)).collect();
+		let manifold = contact_manifold(&cube, &other).unwrap();
+		assert![manifold.normal.approx_eq(DVec3::Z, 1e-12)];
+		assert![(manifold.depth - 0.05).abs() <= 1e-12];
+		assert_eq![manifold.points().len(), 4];
+	}
+
+	#[test]
+	fn empty_hull() {
+		let cube = cube_at(Vec3(-0.5, -0.5, -0.5));
+		assert![contact_manifold(&[], &cube).is_none()];
+		assert![contact_manifold(&cube, &[]).is_none()];
+	}
+}
@@ -0,0 +1,192 @@
+//! BGJK on hulls inflated by a collision margin.
+use super::{Float, Vector3, bgjk, either_empty, farthest, gjk, gjk_distance_squared};
+
+/// The BGJK algorithm on hulls grown by a margin, see `bgjk`
+///
+/// Each hull is treated as its Minkowski sum with a sphere of radius
+/// `margin`, so its faces move outwards by the margin and its edges and
+/// corners are rounded. A single point with a margin is a sphere and a
+/// segment with a margin is a capsule.
+///
+/// Margins of zero give exactly the answer of `bgjk`. Margins must not be
+/// negative, debug builds panic on one, since shrinking a hull is not a
+/// Minkowski sum with a sphere.
+///
+/// ```
+/// use bgjk::{bgjk_margin, Vec3};
+///
+/// let center = [Vec3::new(0.0, 0.0, 0.0)];
+/// let other = [Vec3::new(1.0, 0.0, 0.0)];
+/// assert![bgjk_margin(&center, 0.6, &other, 0.6)];
+/// assert![!bgjk_margin(&center, 0.4, &other, 0.4)];
+/// ```
+pub fn bgjk_margin<T: Float>(hull1: &[Vector3<T>],
+                             margin1: T,
+                             hull2: &[Vector3<T>],
+                             margin2: T)
+                             -> bool {
+	debug_assert![margin1 >= T::ZERO && margin2 >= T::ZERO,
+	              "bgjk_margin called with a negative margin: {}, {}",
+	              margin1,
+	              margin2];
+	let margin = margin1 + margin2;
+	if margin == T::ZERO {
+		return bgjk(hull1, hull2);
+	}
+	debug_assert![hull1.iter().chain(hull2).all(Vector3::is_finite),
+	              "bgjk called with a non-finite point in a hull"];
+	if either_empty(hull1, hull2) {
+		return false;
+	}
+	let at = |hull: &[Vector3<T>], direction| farthest(hull, direction).unwrap_or(Vector3::ZERO);
+	gjk(|direction| {
+		let grown = direction.try_normalized().map_or(Vector3::ZERO, |unit| unit * margin);
+		at(hull1, direction) - at(hull2, -direction) + grown
+	})
+}
+
+/// Whether the sphere around `center` with `radius` intersects `hull`
+///
+/// The sphere is the point `center` with a margin of `radius`, but instead
+/// of growing the hull this compares the distance from the center to the
+/// hull, see `gjk_distance`, with the radius. Nothing is tessellated and a
+/// sphere exactly tangent to the hull intersects it, as touching hulls do
+/// for `bgjk`. The radius must not be negative, debug builds panic on one.
+///
+/// ```
+/// use bgjk::{sphere_hits_hull, Vec3};
+///
+/// let triangle = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// assert![sphere_hits_hull(Vec3::new(0.2, 0.2, 1.0), 1.0, &triangle)];
+/// assert![!sphere_hits_hull(Vec3::new(0.2, 0.2, 1.0), 0.9, &triangle)];
+/// ```
+pub fn sphere_hits_hull<T: Float>(center: Vector3<T>, radius: T, hull: &[Vector3<T>]) -> bool {
+	debug_assert![radius >= T::ZERO, "sphere_hits_hull called with a negative radius: {}", radius];
+	!hull.is_empty() && gjk_distance_squared(&[center], hull) <= radius * radius
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+
+	use std::f32::consts::PI;
+	use super::super::{Vec3, bgjk, bgjk_margin, sphere_hits_hull};
+	use super::super::test_util::{cube_at, shifted, unit_cube};
+
+	#[test]
+	fn zero_margin() {
+		let cube = unit_cube();
+		let offsets = [Vec3(0.5, 0.5, 0.5), Vec3(1.0, 0.0, 0.0), Vec3(1.1, 0.0, 0.0), Vec3::ONE];
+		for &by in &offsets {
+			let other = shifted(&cube, by);
+			assert_eq![bgjk_margin(&cube, 0.0, &other, 0.0), bgjk(&cube, &other)];
+		}
+	}
+
+	#[test]
+	fn cubes_apart() {
+		let cube = unit_cube();
+		let other = shifted(&cube, Vec3(1.1, 0.0, 0.0));
+		assert_eq![bgjk_margin(&cube, 0.049, &other, 0.049), false];
+		assert_eq![bgjk_margin(&cube, 0.051, &other, 0.051), true];
+		assert_eq![bgjk_margin(&cube, 0.11, &other, 0.0), true];
+		assert_eq![bgjk_margin(&cube, 0.0, &other, 0.2), true];
+	}
+
+	#[test]
+	fn rounded_corners() {
+		let cube = unit_cube();
+		// The corners are 0.1 * sqrt(3) apart, more than the 0.1 between the faces
+		let other = shifted(&cube, Vec3(1.1, 1.1, 1.1));
+		assert_eq![bgjk_margin(&cube, 0.05, &other, 0.05), false];
+		assert_eq![bgjk_margin(&cube, 0.09, &other, 0.09), true];
+	}
+
+	#[test]
+	fn spheres() {
+		let center = pts![(0.0, 0.0, 0.0)];
+		for &distance in &[0.5, 1.0, 2.0] {
+			let other = pts![(distance * 0.6, distance * -0.8, 0.0)];
+			for &radius in &[0.1, 0.3, 0.45] {
+				let radius2 = distance - radius;
+				assert_eq![bgjk_margin(&center, radius, &other, radius2 * 0.999), false];
+				assert_eq![bgjk_margin(&center, radius, &other, radius2 * 1.001), true];
+			}
+		}
+	}
+
+	#[test]
+	#[should_panic(expected = "negative margin")]
+	#[cfg(debug_assertions)]
+	fn negative_margin() {
+		bgjk_margin(&unit_cube(), -0.1, &unit_cube(), 0.0);
+	}
+
+	// A sphere of latitude and longitude rings with its vertices on the surface
+	fn tessellated(center: Vec3, radius: f32) -> Vec<Vec3> {
+		let mut sphere = vec![center + Vec3::Z * radius, center - Vec3::Z * radius];
+		for ring in 1..24 {
+			let (sin_polar, cos_polar) = (ring as f32 * PI / 24.0).sin_cos();
+			for step in 0..48 {
+				let (sin, cos) = (step as f32 * PI / 24.0).sin_cos();
+				sphere.push(center + Vec3(sin_polar * cos, sin_polar * sin, cos_polar) * radius);
+			}
+		}
+		sphere
+	}
+
+	#[test]
+	fn sphere_matches_tessellation() {
+		let cube = unit_cube();
+		let mut hits = 0;
+		let steps = [-0.9, -0.4, 0.1, 0.5, 0.9, 1.3, 1.8];
+		for &x in &steps {
+			for &y in &steps {
+				for &z in &steps {
+					let center = Vec3(x, y, z);
+					let hit = sphere_hits_hull(center, 0.5, &cube);
+					// The tessellation lies within a hundredth of the radius inside the sphere
+					if bgjk(&tessellated(center, 0.5), &cube) {
+						assert_eq![hit, true];
+					}
+					if hit {
+						assert_eq![bgjk(&tessellated(center, 0.51), &cube), true];
+					}
+					hits += hit as usize;
+				}
+			}
+		}
+		assert![hits > 0 && hits < steps.len() * steps.len() * steps.len()];
+	}
+
+	#[test]
+	fn sphere_tangent() {
+		let cube = unit_cube();
+		assert_eq![sphere_hits_hull(Vec3(0.5, 0.5, 1.5), 0.5, &cube), true];
+		assert_eq![sphere_hits_hull(Vec3(-0.25, 0.5, 0.5), 0.25, &cube), true];
+		assert_eq![sphere_hits_hull(Vec3(0.5, 0.5, 1.5), 0.4999, &cube), false];
+		// Tangent to an edge and to a corner
+		assert_eq![sphere_hits_hull(Vec3(1.3, 1.4, 0.5), 0.5, &cube), true];
+		assert_eq![sphere_hits_hull(Vec3(1.3, 1.4, 0.5), 0.4999, &cube), false];
+		assert_eq![sphere_hits_hull(Vec3(-0.2, -0.4, 1.4), 0.6, &cube), true];
+		assert_eq![sphere_hits_hull(Vec3(-0.2, -0.4, 1.4), 0.5999, &cube), false];
+	}
+
+	#[test]
+	fn sphere_overlapping() {
+		let cube = unit_cube();
+		assert_eq![sphere_hits_hull(Vec3(0.5, 0.5, 0.5), 0.1, &cube), true];
+		assert_eq![sphere_hits_hull(Vec3(0.5, 0.5, 0.5), 10.0, &cube), true];
+		assert_eq![sphere_hits_hull(Vec3(0.9, 0.2, 1.2), 0.3, &cube), true];
+		assert_eq![sphere_hits_hull(Vec3(0.5, 0.5, 0.5), 0.0, &cube), true];
+		assert_eq![sphere_hits_hull(Vec3(2.5, 0.5, 0.5), 0.0, &cube), false];
+	}
+
+	#[test]
+	fn empty_hull() {
+		let cube = cube_at(Vec3(-0.5, -0.5, -0.5));
+		assert![!bgjk_margin(&[], 1.0, &cube, 1.0)];
+		assert![!bgjk_margin(&cube, 1.0, &[], 1.0)];
+		assert![!sphere_hits_hull(Vec3::ZERO, 1.0, &[])];
+	}
+}
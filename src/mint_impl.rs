@@ -0,0 +1,75 @@
+//! Conversions between `Vec3` and the `mint` interchange types.
+use mint::{Point3, Vector3};
+use super::Vec3;
+
+impl From<Point3<f32>> for Vec3 {
+	fn from(point: Point3<f32>) -> Vec3 {
+		Vec3(point.x, point.y, point.z)
+	}
+}
+
+impl From<Vec3> for Point3<f32> {
+	fn from(vector: Vec3) -> Point3<f32> {
+		Point3 { x: vector.0, y: vector.1, z: vector.2 }
+	}
+}
+
+impl From<Vector3<f32>> for Vec3 {
+	fn from(vector: Vector3<f32>) -> Vec3 {
+		Vec3(vector.x, vector.y, vector.z)
+	}
+}
+
+impl From<Vec3> for Vector3<f32> {
+	fn from(vector: Vec3) -> Vector3<f32> {
+		Vector3 { x: vector.0, y: vector.1, z: vector.2 }
+	}
+}
+
+impl Vec3 {
+	/// Convert a slice of `mint` points into a hull
+	pub fn slice_from_mint(points: &[Point3<f32>]) -> Vec<Vec3> {
+		points.iter().map(|point| Vec3::from(*point)).collect()
+	}
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+
+	use std::f32;
+	use mint::{Point3, Vector3};
+	use super::super::{Vec3, bgjk};
+
+	#[test]
+	fn round_trip() {
+		let values = [Vec3(1.0, -2.0, 3.5), Vec3(-0.0, f32::MAX, f32::MIN_POSITIVE)];
+		for v in &values {
+			let point: Point3<f32> = (*v).into();
+			assert_eq![(point.x, point.y, point.z), (v.0, v.1, v.2)];
+			assert_eq![Vec3::from(point), *v];
+			let vector: Vector3<f32> = (*v).into();
+			assert_eq![(vector.x, vector.y, vector.z), (v.0, v.1, v.2)];
+			assert_eq![Vec3::from(vector), *v];
+		}
+		let back: Point3<f32> = Vec3::from(Point3 { x: -0.0, y: 0.0, z: 1.0 }).into();
+		assert![back.x.is_sign_negative() && back.y.is_sign_positive()];
+	}
+
+	#[test]
+	fn mint_hull() {
+		let square = [Point3 { x: 0.0, y: 0.0, z: 0.0 },
+		              Point3 { x: 1.0, y: 0.0, z: 0.0 },
+		              Point3 { x: 0.0, y: 1.0, z: 0.0 },
+		              Point3 { x: 1.0, y: 1.0, z: 0.0 }];
+		let hull = Vec3::slice_from_mint(&square);
+		assert_eq![hull, vec![Vec3::ZERO, Vec3::X, Vec3::Y, Vec3(1.0, 1.0, 0.0)]];
+		let through = Vec3::slice_from_mint(&[Point3 { x: 0.5, y: 0.5, z: -1.0 },
+		                                      Point3 { x: 0.5, y: 0.5, z: 1.0 }]);
+		assert_eq![bgjk(&hull, &through), true];
+		let beside = Vec3::slice_from_mint(&[Point3 { x: 1.5, y: 0.5, z: -1.0 },
+		                                     Point3 { x: 1.5, y: 0.5, z: 1.0 }]);
+		assert_eq![bgjk(&hull, &beside), false];
+	}
+
+}
@@ -0,0 +1,109 @@
+//! Conversions between `Vec3` and the `nalgebra` point and vector types.
+use nalgebra::{Isometry3, Point3, Vector3};
+use super::{Vec3, either_empty, gjk};
+
+impl From<Point3<f32>> for Vec3 {
+	fn from(point: Point3<f32>) -> Vec3 {
+		Vec3(point.x, point.y, point.z)
+	}
+}
+
+impl From<Vec3> for Point3<f32> {
+	fn from(vector: Vec3) -> Point3<f32> {
+		Point3::new(vector.0, vector.1, vector.2)
+	}
+}
+
+impl From<Vector3<f32>> for Vec3 {
+	fn from(vector: Vector3<f32>) -> Vec3 {
+		Vec3(vector.x, vector.y, vector.z)
+	}
+}
+
+impl From<Vec3> for Vector3<f32> {
+	fn from(vector: Vec3) -> Vector3<f32> {
+		Vector3::new(vector.0, vector.1, vector.2)
+	}
+}
+
+// The farthest point of the placed hull, found in the hull's local frame
+fn farthest(points: &[Point3<f32>], isometry: &Isometry3<f32>, direction: Vec3) -> Vec3 {
+	let local = Vec3::from(isometry.inverse_transform_vector(&direction.into()));
+	let mut max: Option<f32> = None;
+	let mut max_point = Point3::origin();
+	for point in points {
+		let current = Vec3::from(*point).dot(local);
+		if max.is_none_or(|value| current > value) {
+			max = Some(current);
+			max_point = *point;
+		}
+	}
+	Vec3::from(isometry * max_point)
+}
+
+/// The BGJK algorithm on hulls placed in the world by isometries, see `bgjk`
+///
+/// Equivalent to transforming every point of each hull by its isometry
+/// and calling `bgjk`, but the isometries are only applied to the support
+/// points the algorithm visits, so nothing is allocated.
+pub fn bgjk_na(hull1: &[Point3<f32>],
+               iso1: &Isometry3<f32>,
+               hull2: &[Point3<f32>],
+               iso2: &Isometry3<f32>)
+               -> bool {
+	debug_assert![hull1.iter().chain(hull2).all(|point| Vec3::from(*point).is_finite()),
+	              "bgjk called with a non-finite point in a hull"];
+	if either_empty(hull1, hull2) {
+		return false;
+	}
+	gjk(|direction| farthest(hull1, iso1, direction) - farthest(hull2, iso2, -direction))
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+
+	use std::f32::consts::PI;
+	use nalgebra::{Isometry3, Point3, Vector3};
+	use super::super::{Vec3, bgjk, bgjk_na};
+
+	fn unit_cube() -> Vec<Point3<f32>> {
+		(0..8).map(|i| Point3::new((i & 1) as f32, (i >> 1 & 1) as f32, (i >> 2) as f32)).collect()
+	}
+
+	fn placed(hull: &[Point3<f32>], isometry: &Isometry3<f32>) -> Vec<Vec3> {
+		hull.iter().map(|point| Vec3::from(isometry * point)).collect()
+	}
+
+	#[test]
+	fn round_trip() {
+		let v = Vec3(1.0, -2.0, 3.5);
+		let point: Point3<f32> = v.into();
+		assert_eq![(point.x, point.y, point.z), (1.0, -2.0, 3.5)];
+		assert_eq![Vec3::from(point), v];
+		let vector: Vector3<f32> = v.into();
+		assert_eq![(vector.x, vector.y, vector.z), (1.0, -2.0, 3.5)];
+		assert_eq![Vec3::from(vector), v];
+	}
+
+	#[test]
+	fn same_as_transformed() {
+		let cube = unit_cube();
+		let placements = [(Isometry3::identity(), Isometry3::translation(0.5, 0.5, 0.5)),
+		                  (Isometry3::identity(), Isometry3::translation(1.1, 0.0, 0.0)),
+		                  (Isometry3::new(Vector3::new(3.0, 0.0, 0.0), Vector3::z() * PI / 4.0),
+		                   Isometry3::translation(1.5, 0.0, 0.0)),
+		                  (Isometry3::new(Vector3::new(3.0, 0.0, 0.0), Vector3::z() * PI / 4.0),
+		                   Isometry3::translation(1.2, 0.0, 0.0)),
+		                  (Isometry3::new(Vector3::new(0.0, 0.0, 2.0), Vector3::new(0.3, 0.2, 0.1)),
+		                   Isometry3::new(Vector3::new(0.0, 0.5, 3.5), Vector3::new(-1.0, 0.5, 0.0)))];
+		let expected = [true, false, true, false, true];
+		for (&(iso1, iso2), &hit) in placements.iter().zip(expected.iter()) {
+			let (world1, world2) = (placed(&cube, &iso1), placed(&cube, &iso2));
+			assert_eq![bgjk_na(&cube, &iso1, &cube, &iso2), bgjk(&world1, &world2)];
+			assert_eq![bgjk_na(&cube, &iso1, &cube, &iso2), hit];
+			assert_eq![bgjk_na(&cube, &iso2, &cube, &iso1), hit];
+		}
+	}
+
+}
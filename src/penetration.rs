@@ -0,0 +1,308 @@
+//! Penetration depth of intersecting hulls, the Expanding Polytope Algorithm.
+use super::{Float, GjkConfig, Vector3, either_empty, gjk_tetrahedron, support};
+
+// Upper bound on the points added to the polytope, the loop normally stops
+// on convergence long before
+const MAX_ITERATIONS: usize = 128;
+
+/// How deep two intersecting hulls overlap, see `penetration`
+#[derive(Clone, Copy, Debug)]
+pub struct Penetration<T = f32> {
+	/// Unit direction pointing from the first hull towards the second
+	pub normal: Vector3<T>,
+	/// The distance to move the second hull along `normal` to separate them
+	pub depth: T,
+}
+
+impl<T: Float> Penetration<T> {
+	fn touching(normal: Vector3<T>) -> Penetration<T> {
+		Penetration {
+			normal,
+			depth: T::ZERO,
+		}
+	}
+}
+
+// A triangle of the polytope, wound counter-clockwise seen from outside,
+// with its outward unit normal and distance from the origin. Faces too thin
+// to have a normal have none and are never the closest.
+#[derive(Clone, Copy)]
+struct Face<T> {
+	indices: [usize; 3],
+	normal: Option<Vector3<T>>,
+	distance: T,
+}
+
+impl<T: Float> Face<T> {
+	fn new(points: &[Vector3<T>], indices: [usize; 3]) -> Face<T> {
+		let a = points[indices[0]];
+		let normal = (points[indices[1]] - a).cross(points[indices[2]] - a).try_normalized();
+		Face {
+			indices,
+			normal,
+			distance: normal.map_or(T::ZERO, |normal| normal.dot(a)),
+		}
+	}
+}
+
+// The candidate with the largest `distance`, and that distance
+fn farthest_from<T: Float, D>(candidates: &[Vector3<T>], distance: D) -> (Vector3<T>, T)
+	where D: Fn(Vector3<T>) -> T
+{
+	let mut best = (candidates[0], distance(candidates[0]));
+	for &candidate in &candidates[1..] {
+		let current = distance(candidate);
+		if current > best.1 {
+			best = (candidate, current);
+		}
+	}
+	best
+}
+
+// A tetrahedron spanning the Minkowski difference, starting from the GJK
+// tetrahedron if it has volume. Otherwise the difference is searched for
+// points off the line and plane of the previous ones. A difference without
+// volume gives a touching contact instead.
+fn tetrahedron<T, F>(seed: [Vector3<T>; 4], support: &F) -> Result<[Vector3<T>; 4], Penetration<T>>
+	where T: Float,
+	      F: Fn(Vector3<T>) -> Vector3<T>
+{
+	let scale = seed.iter().fold(T::ZERO, |max, point| max.max(point.length()));
+	let tolerance = T::EPSILON * T::from_f32(100.0) * scale;
+	let [a, b, c, d] = seed;
+	if Vector3::triple(b - a, c - a, d - a).abs() > tolerance * scale * scale {
+		return Ok(seed);
+	}
+	let axes = [Vector3::X, -Vector3::X, Vector3::Y, -Vector3::Y, Vector3::Z, -Vector3::Z];
+	let (b, spread) = farthest_from(&axes.map(support), |point| point.distance(a));
+	if spread <= tolerance {
+		return Err(Penetration::touching(Vector3::X));
+	}
+	let (u, v) = (b - a).any_orthonormal_pair();
+	let around = [u, -u, v, -v].map(support);
+	let line = (b - a).normalized();
+	let (c, spread) = farthest_from(&around, |point| (point - a).cross(line).length());
+	if spread <= tolerance {
+		return Err(Penetration::touching(u));
+	}
+	let normal = (b - a).cross(c - a).normalized();
+	let (d, spread) = farthest_from(&[support(normal), support(-normal)],
+	                                |point| (point - a).dot(normal).abs());
+	if spread <= tolerance {
+		return Err(Penetration::touching(normal));
+	}
+	Ok([a, b, c, d])
+}
+
+// Whether the points span a solid, rather than lying on a plane, a line or
+// a single point, or there being none
+pub fn has_volume<T: Float>(hull: &[Vector3<T>]) -> bool {
+	let a = match hull.first() {
+		Some(&a) => a,
+		None => return false,
+	};
+	let (b, spread) = farthest_from(hull, |point| point.distance(a));
+	if spread <= T::ZERO {
+		return false;
+	}
+	let tolerance = T::EPSILON * T::from_f32(100.0) * spread;
+	let line = (b - a).normalized();
+	let (c, spread) = farthest_from(hull, |point| (point - a).cross(line).length());
+	if spread <= tolerance {
+		return false;
+	}
+	let normal = line.cross(c - a).normalized();
+	farthest_from(hull, |point| (point - a).dot(normal).abs()).1 > tolerance
+}
+
+/// The penetration depth and direction of two intersecting convex hulls
+///
+/// Runs the Expanding Polytope Algorithm from the tetrahedron `bgjk` ends
+/// with, growing it towards the boundary of the Minkowski difference until
+/// the face closest to the origin is on that boundary. Translating the
+/// second hull by `normal * depth` (or the first by the opposite) makes the
+/// hulls touch. Returns `None` when the hulls do not intersect, as for an
+/// empty hull.
+///
+/// Hulls that only touch, or whose Minkowski difference is flat such as two
+/// overlapping squares in the same plane, have a depth of `0.0`. The normal
+/// is then perpendicular to the contact where there is one, but its sign is
+/// arbitrary.
+///
+/// ```
+/// use bgjk::{penetration, Vec3};
+///
+/// let cube = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0),
+///             Vec3::new(1.0, 1.0, 0.0), Vec3::new(0.0, 0.0, 1.0), Vec3::new(1.0, 0.0, 1.0),
+///             Vec3::new(0.0, 1.0, 1.0), Vec3::new(1.0, 1.0, 1.0)];
+/// let sunk: Vec<Vec3> = cube.iter().map(|v| *v + Vec3::new(0.25, 0.0, 0.75)).collect();
+/// let contact = penetration(&cube, &sunk).unwrap();
+/// assert_eq![(contact.normal, contact.depth), (Vec3::Z, 0.25)];
+/// ```
+pub fn penetration<T: Float>(hull1: &[Vector3<T>], hull2: &[Vector3<T>]) -> Option<Penetration<T>> {
+	if either_empty(hull1, hull2) {
+		return None;
+	}
+	let minkowski = |direction| support(hull1, hull2, direction);
+	let seed = gjk_tetrahedron(minkowski, &GjkConfig::default()).ok()?;
+	Some(expand(seed, minkowski))
+}
+
+// The polytope algorithm of `penetration` on the Minkowski difference given
+// by its support function, from points of it around the origin such as a
+// GJK simplex, repeated to fill four where it has fewer
+pub fn expand<T: Float, F: Fn(Vector3<T>) -> Vector3<T>>(seed: [Vector3<T>; 4],
+                                                         minkowski: F)
+                                                         -> Penetration<T> {
+	let start = match tetrahedron(seed, &minkowski) {
+		Ok(start) => start,
+		Err(touching) => return touching,
+	};
+	let mut points = start.to_vec();
+	let mut faces = Vec::with_capacity(16);
+	for &(indices, opposite) in &[([0, 1, 2], 3), ([0, 3, 1], 2), ([0, 2, 3], 1), ([1, 3, 2], 0)] {
+		let face = Face::new(&points, indices);
+		let towards = points[opposite] - points[indices[0]];
+		let inward = face.normal.is_some_and(|normal| normal.dot(towards) > T::ZERO);
+		faces.push(if inward {
+			Face::new(&points, [indices[0], indices[2], indices[1]])
+		} else {
+			face
+		});
+	}
+
+	let mut closest = faces[0];
+	for _ in 0..MAX_ITERATIONS {
+		closest = *faces.iter()
+			.filter(|face| face.normal.is_some())
+			.min_by(|a, b| a.distance.partial_cmp(&b.distance).expect("finite face distances"))
+			.expect("a polytope has faces with normals");
+		let normal = closest.normal.expect("filtered on normals");
+		let point = minkowski(normal);
+		let tolerance = T::EPSILON * T::from_f32(100.0) * point.length();
+		if point.dot(normal) - closest.distance <= tolerance || points.contains(&point) {
+			break;
+		}
+
+		// Replace the faces the new point sees by a fan from the point to
+		// the edges around them
+		let index = points.len();
+		points.push(point);
+		let mut horizon: Vec<(usize, usize)> = Vec::new();
+		faces.retain(|face| {
+			let visible = face.normal
+				.is_none_or(|normal| normal.dot(point - points[face.indices[0]]) > T::ZERO);
+			if visible {
+				for i in 0..3 {
+					let edge = (face.indices[i], face.indices[(i + 1) % 3]);
+					match horizon.iter().position(|&(a, b)| (b, a) == edge) {
+						Some(shared) => {
+							horizon.swap_remove(shared);
+						}
+						None => horizon.push(edge),
+					}
+				}
+			}
+			!visible
+		});
+		for (a, b) in horizon {
+			faces.push(Face::new(&points, [a, b, index]));
+		}
+	}
+	Penetration {
+		normal: closest.normal.expect("the closest face has a normal"),
+		depth: closest.distance.max(T::ZERO),
+	}
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+
+	use std::f32;
+	use super::super::{DVec3, Vec3, bgjk, penetration};
+	use super::super::test_util::{cube_at, shifted, unit_cube};
+	static EPS: f32 = f32::EPSILON;
+
+	#[test]
+	fn overlapping_boxes() {
+		let cube = unit_cube();
+		let contact = penetration(&cube, &shifted(&cube, Vec3(0.8, 0.1, 0.2))).unwrap();
+		assert![contact.normal.approx_eq(Vec3::X, 4.0 * EPS)];
+		assert![(contact.depth - 0.2).abs() <= 4.0 * EPS];
+		let contact = penetration(&cube, &shifted(&cube, Vec3(-0.3, -0.6, 0.1))).unwrap();
+		assert![contact.normal.approx_eq(-Vec3::Y, 4.0 * EPS)];
+		assert![(contact.depth - 0.4).abs() <= 4.0 * EPS];
+		let slab: Vec<Vec3> = cube.iter().map(|v| v.mul_component(Vec3(4.0, 4.0, 0.5))).collect();
+		let contact = penetration(&slab, &shifted(&cube, Vec3(1.0, 1.5, 0.375))).unwrap();
+		assert![contact.normal.approx_eq(Vec3::Z, 4.0 * EPS)];
+		assert![(contact.depth - 0.125).abs() <= 4.0 * EPS];
+	}
+
+	#[test]
+	fn resolves_overlap() {
+		let cube = unit_cube();
+		for &by in &[Vec3(0.8, 0.1, 0.2), Vec3(-0.5, 0.7, -0.6), Vec3(0.0, 0.0, 0.0)] {
+			let other = shifted(&cube, by);
+			let contact = penetration(&cube, &other).unwrap();
+			let resolved = shifted(&other, contact.normal * (contact.depth * 1.01));
+			assert_eq![bgjk(&cube, &resolved), false];
+		}
+	}
+
+	#[test]
+	fn shallow() {
+		let cube = unit_cube();
+		let contact = penetration(&cube, &shifted(&cube, Vec3(0.3, 0.2, 1.0 - 8.0 * EPS))).unwrap();
+		assert![contact.normal.approx_eq(Vec3::Z, 1e-4)];
+		assert![contact.depth <= 16.0 * EPS];
+	}
+
+	#[test]
+	fn exact_overlap() {
+		let square = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let contact = penetration(&square, &square).unwrap();
+		assert_eq![contact.depth, 0.0];
+		assert_eq![contact.normal.abs(), Vec3::Z];
+		let cube = unit_cube();
+		let contact = penetration(&cube, &cube).unwrap();
+		assert![(contact.depth - 1.0).abs() <= 4.0 * EPS];
+	}
+
+	#[test]
+	fn degenerate() {
+		let point = pts![(0.5, 1.0, 0.0)];
+		assert_eq![penetration(&point, &point).unwrap().depth, 0.0];
+		let line1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0)];
+		let line2 = pts![(0.5, 1.0, 0.0), (0.5, -1.0, 0.0)];
+		let contact = penetration(&line1, &line2).unwrap();
+		assert_eq![contact.depth, 0.0];
+		assert_eq![contact.normal.abs(), Vec3::Z];
+	}
+
+	#[test]
+	fn disjoint() {
+		let cube = unit_cube();
+		assert![penetration(&cube, &shifted(&cube, Vec3(1.5, 0.0, 0.0))).is_none()];
+		assert![penetration(&cube, &shifted(&cube, Vec3(0.0, 0.0, -1.0 - 2.0 * EPS))).is_none()];
+	}
+
+	#[test]
+	fn double_precision() {
+		let cube: Vec<DVec3> = unit_cube().iter().map(|&v| v.into()).collect();
+		let lift = DVec3::new(0.1, 0.2, 1.0 - 1e-9);
+		let other: Vec<DVec3> = cube.iter().map(|v| *v + lift).collect();
+		let contact = penetration(&cube, &other).unwrap();
+		assert![contact.normal.approx_eq(DVec3::Z, 1e-9)];
+		assert![(contact.depth - 1e-9).abs() <= 1e-12];
+	}
+
+
+	#[test]
+	fn empty_hull() {
+		let cube = cube_at(Vec3(-0.5, -0.5, -0.5));
+		assert![penetration(&[], &cube).is_none()];
+		assert![penetration(&cube, &[]).is_none()];
+		assert![penetration::<f32>(&[], &[]).is_none()];
+	}
+}
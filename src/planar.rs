@@ -0,0 +1,456 @@
+//! Polygons in the plane, the 2D variants of GJK and EPA.
+use super::Vec2;
+
+// Upper bound on the iterations, matching the default of `GjkConfig`. Each
+// iteration adds a support point, so the search stops long before this
+// unless rounding makes it cycle, which counts as an intersection.
+const MAX_ITERATIONS: usize = 64;
+
+/// The point of `vertices` farthest along `direction`
+///
+/// The first of the farthest points where several tie, and `Vec2::ZERO`
+/// for no points, like the support of the 3D functions.
+///
+/// ```
+/// use bgjk::{farthest2, Vec2};
+///
+/// let triangle = [Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0)];
+/// assert_eq![farthest2(&triangle, Vec2::new(1.0, 0.5)), Vec2::new(1.0, 0.0)];
+/// ```
+pub fn farthest2(vertices: &[Vec2], direction: Vec2) -> Vec2 {
+	let mut max: Option<f32> = None;
+	let mut farthest = Vec2::ZERO;
+	for vertex in vertices {
+		let current = vertex.dot(direction);
+		if max.is_none_or(|value| current > value) {
+			max = Some(current);
+			farthest = *vertex;
+		}
+	}
+	farthest
+}
+
+/// The point of the Minkowski difference `hull1 - hull2` farthest along
+/// `direction`
+///
+/// The hulls intersect exactly when the difference contains the origin,
+/// which is what `bgjk2` searches for. Building on this function runs GJK
+/// against other planar queries, such as a polygon moved by an offset.
+///
+/// ```
+/// use bgjk::{support2, Vec2};
+///
+/// let square = [Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0),
+///               Vec2::new(1.0, 1.0)];
+/// assert_eq![support2(&square, &square, Vec2::X), Vec2::new(1.0, 0.0)];
+/// ```
+pub fn support2(hull1: &[Vec2], hull2: &[Vec2], direction: Vec2) -> Vec2 {
+	farthest2(hull1, direction) - farthest2(hull2, -direction)
+}
+
+/// Whether two convex polygons intersect, the planar counterpart of `bgjk`
+///
+/// Gives the same answer as `bgjk` on the hulls placed in the z = 0 plane,
+/// including polygons that only touch, which intersect. The simplex is at
+/// most a triangle, so the search never has to deal with the flat
+/// tetrahedra planar hulls give in 3D. The points need not be in order and
+/// may repeat or lie inside the hull.
+///
+/// A hull with no points intersects nothing, like for `bgjk`. In debug
+/// builds this panics if any point is infinite or NaN.
+///
+/// ```
+/// use bgjk::{bgjk2, Vec2};
+///
+/// let square = [Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0),
+///               Vec2::new(1.0, 1.0)];
+/// let near: Vec<Vec2> = square.iter().map(|v| *v + Vec2::new(0.5, 0.5)).collect();
+/// let far: Vec<Vec2> = square.iter().map(|v| *v + Vec2::new(1.5, 0.5)).collect();
+/// assert![bgjk2(&square, &near)];
+/// assert![!bgjk2(&square, &far)];
+/// ```
+pub fn bgjk2(hull1: &[Vec2], hull2: &[Vec2]) -> bool {
+	debug_assert![hull1.iter().chain(hull2).all(|v| v.0.is_finite() && v.1.is_finite()),
+	              "bgjk called with a non-finite point in a hull"];
+	if hull1.is_empty() || hull2.is_empty() {
+		return false;
+	}
+	gjk2(|direction| support2(hull1, hull2, direction)).is_some()
+}
+
+// The planar BGJK loop on the Minkowski difference given by its support.
+// Keeps the simplex as the newest point `a` with the segment `b`, `c`
+// before it, and searches along the normal of the side facing the origin.
+// Gives the triangle around the origin, or the segment or point the origin
+// is on repeated to fill it, and `None` when the origin is outside.
+fn gjk2<F: Fn(Vec2) -> Vec2>(support: F) -> Option<[Vec2; 3]> {
+	let mut c = support(Vec2::ONE);
+	let mut direction = -c;
+	if direction == Vec2::ZERO {
+		return Some([c; 3]);
+	}
+	let mut b = support(direction);
+	if b.dot(direction) < 0.0 {
+		return None;
+	}
+	for _ in 0..MAX_ITERATIONS {
+		// The normal of the segment towards the origin, the origin lies on the
+		// segment when there is none, being on both sides of `direction`
+		let bc = c - b;
+		let normal = bc.perp();
+		let side = normal.dot(-b);
+		if side == 0.0 {
+			return Some([b, c, c]);
+		}
+		direction = if side > 0.0 { normal } else { -normal };
+		let a = support(direction);
+		if a.dot(direction) < 0.0 {
+			return None;
+		}
+		// Keep the side of the triangle facing the origin, or stop when no
+		// side does. The outer normals point away from the opposite corner.
+		let (ab, ac) = (b - a, c - a);
+		let turn = if ab.cross(ac) > 0.0 { 1.0 } else { -1.0 };
+		if (ab.perp() * -turn).dot(-a) > 0.0 {
+			c = b;
+			b = a;
+		} else if (ac.perp() * turn).dot(-a) > 0.0 {
+			b = a;
+		} else {
+			return Some([a, b, c]);
+		}
+	}
+	Some([b, c, c])
+}
+
+/// How deep two intersecting polygons overlap, see `penetration2`
+#[derive(Clone, Copy, Debug)]
+pub struct Penetration2 {
+	/// Unit direction pointing from the first hull towards the second
+	pub normal: Vec2,
+	/// The distance to move the second hull along `normal` to separate them
+	pub depth: f32,
+}
+
+impl Penetration2 {
+	fn touching(normal: Vec2) -> Penetration2 {
+		Penetration2 {
+			normal,
+			depth: 0.0,
+		}
+	}
+}
+
+// The candidate with the largest `distance`, and that distance
+fn farthest_from<D: Fn(Vec2) -> f32>(candidates: &[Vec2], distance: D) -> (Vec2, f32) {
+	let mut best = (candidates[0], distance(candidates[0]));
+	for &candidate in &candidates[1..] {
+		let current = distance(candidate);
+		if current > best.1 {
+			best = (candidate, current);
+		}
+	}
+	best
+}
+
+// A triangle spanning the Minkowski difference, starting from the GJK
+// triangle if it has area. Otherwise the difference is searched for points
+// off the previous point and off the line through both. A difference
+// without area gives a touching contact instead.
+fn triangle<F: Fn(Vec2) -> Vec2>(seed: [Vec2; 3], support: &F) -> Result<[Vec2; 3], Penetration2> {
+	let scale = seed.iter().fold(0.0f32, |max, point| max.max(point.length()));
+	let tolerance = f32::EPSILON * 100.0 * scale;
+	let [a, b, c] = seed;
+	if (b - a).cross(c - a).abs() > tolerance * scale {
+		return Ok(seed);
+	}
+	let axes = [Vec2::X, -Vec2::X, Vec2::Y, -Vec2::Y];
+	let (b, spread) = farthest_from(&axes.map(support), |point| (point - a).length());
+	if spread <= tolerance {
+		return Err(Penetration2::touching(Vec2::X));
+	}
+	let across = (b - a).normalized().perp();
+	let (c, spread) = farthest_from(&[support(across), support(-across)],
+	                                |point| (point - a).dot(across).abs());
+	if spread <= tolerance {
+		return Err(Penetration2::touching(across));
+	}
+	Ok([a, b, c])
+}
+
+// An edge of the polygon, from a corner to the next one counter-clockwise,
+// with its outward unit normal and distance from the origin. Edges too
+// short to have a normal have none and are never the closest.
+#[derive(Clone, Copy)]
+struct Edge {
+	index: usize,
+	normal: Option<Vec2>,
+	distance: f32,
+}
+
+impl Edge {
+	fn new(points: &[Vec2], index: usize) -> Edge {
+		let (a, b) = (points[index], points[(index + 1) % points.len()]);
+		let normal = (a - b).perp().try_normalized();
+		Edge {
+			index,
+			normal,
+			distance: normal.map_or(0.0, |normal| normal.dot(a)),
+		}
+	}
+}
+
+/// The penetration depth and direction of two intersecting convex polygons
+///
+/// The planar counterpart of `penetration`. Runs the Expanding Polytope
+/// Algorithm from the triangle `bgjk2` ends with, splitting the edge of the
+/// polygon closest to the origin at the support point beyond it until that
+/// edge is on the boundary of the Minkowski difference. Translating the
+/// second hull by `normal * depth` (or the first by the opposite) makes the
+/// hulls touch. Returns `None` when the hulls do not intersect.
+///
+/// Hulls that only touch have a depth of `0.0` with the normal of the edge
+/// they touch along, pointing towards the second hull. Where their
+/// Minkowski difference has no area, such as for two collinear segments,
+/// the normal is perpendicular to it but its sign is arbitrary.
+///
+/// ```
+/// use bgjk::{penetration2, Vec2};
+///
+/// let square = [Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0),
+///               Vec2::new(1.0, 1.0)];
+/// let sunk: Vec<Vec2> = square.iter().map(|v| *v + Vec2::new(0.25, 0.75)).collect();
+/// let contact = penetration2(&square, &sunk).unwrap();
+/// assert_eq![(contact.normal, contact.depth), (Vec2::Y, 0.25)];
+/// ```
+pub fn penetration2(hull1: &[Vec2], hull2: &[Vec2]) -> Option<Penetration2> {
+	debug_assert![hull1.iter().chain(hull2).all(|v| v.0.is_finite() && v.1.is_finite()),
+	              "bgjk called with a non-finite point in a hull"];
+	let minkowski = |direction| support2(hull1, hull2, direction);
+	let seed = gjk2(minkowski)?;
+	let [a, b, c] = match triangle(seed, &minkowski) {
+		Ok(start) => start,
+		Err(touching) => return Some(touching),
+	};
+	let mut points = if (b - a).cross(c - a) > 0.0 { vec![a, b, c] } else { vec![a, c, b] };
+
+	let mut closest = Edge::new(&points, 0);
+	for _ in 0..MAX_ITERATIONS {
+		closest = (0..points.len())
+			.map(|index| Edge::new(&points, index))
+			.filter(|edge| edge.normal.is_some())
+			.min_by(|a, b| a.distance.partial_cmp(&b.distance).expect("finite edge distances"))
+			.expect("a polygon has edges with normals");
+		let normal = closest.normal.expect("filtered on normals");
+		let point = minkowski(normal);
+		let tolerance = f32::EPSILON * 100.0 * point.length();
+		if point.dot(normal) - closest.distance <= tolerance || points.contains(&point) {
+			break;
+		}
+		points.insert(closest.index + 1, point);
+	}
+	Some(Penetration2 {
+		normal: closest.normal.expect("the closest edge has a normal"),
+		depth: closest.distance.max(0.0),
+	})
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+
+	use std::f32;
+	use super::super::{Vec2, Vec3, bgjk, bgjk2, farthest2, penetration2, support2};
+	static EPS: f32 = f32::EPSILON;
+
+	fn square() -> [Vec2; 4] {
+		[Vec2(0.0, 0.0), Vec2(1.0, 0.0), Vec2(0.0, 1.0), Vec2(1.0, 1.0)]
+	}
+
+	fn shifted(hull: &[Vec2], by: Vec2) -> Vec<Vec2> {
+		hull.iter().map(|v| *v + by).collect()
+	}
+
+	// `bgjk2`, checked against `bgjk` in the z = 0 plane
+	fn hits(hull1: &[Vec2], hull2: &[Vec2]) -> bool {
+		let flat = |hull: &[Vec2]| hull.iter().map(|v| Vec3::from(*v)).collect::<Vec<Vec3>>();
+		let hit = bgjk2(hull1, hull2);
+		assert_eq![hit, bgjk(&flat(hull1), &flat(hull2))];
+		hit
+	}
+
+	#[test]
+	fn overlapping() {
+		let square = square();
+		assert_eq![hits(&square, &shifted(&square, Vec2(0.5, 0.5))), true];
+		assert_eq![hits(&square, &shifted(&square, Vec2(-0.9, 0.3))), true];
+		assert_eq![hits(&square, &square), true];
+		let inner = [Vec2(0.4, 0.4), Vec2(0.6, 0.4), Vec2(0.5, 0.6)];
+		assert_eq![hits(&square, &inner), true];
+		assert_eq![hits(&inner, &square), true];
+	}
+
+	#[test]
+	fn apart() {
+		let square = square();
+		assert_eq![hits(&square, &shifted(&square, Vec2(1.5, 0.5))), false];
+		assert_eq![hits(&square, &shifted(&square, Vec2(-3.0, -3.0))), false];
+		let diagonal = [Vec2(1.2, 0.9), Vec2(0.9, 1.2), Vec2(2.0, 2.0)];
+		assert_eq![hits(&square, &diagonal), false];
+	}
+
+	#[test]
+	fn touching() {
+		let square = square();
+		assert_eq![hits(&square, &shifted(&square, Vec2::X)), true];
+		assert_eq![hits(&square, &shifted(&square, Vec2::ONE)), true];
+		assert_eq![hits(&square, &shifted(&square, Vec2(1.0, 0.5))), true];
+		assert_eq![hits(&square, &shifted(&square, Vec2(1.001, 0.5))), false];
+	}
+
+	#[test]
+	fn degenerate() {
+		let square = square();
+		// Points and segments
+		assert_eq![hits(&square, &[Vec2(0.5, 0.5)]), true];
+		assert_eq![hits(&square, &[Vec2(1.0, 0.5)]), true];
+		assert_eq![hits(&square, &[Vec2(1.5, 0.5)]), false];
+		assert_eq![hits(&square, &[Vec2(-1.0, 0.5), Vec2(2.0, 0.5)]), true];
+		assert_eq![hits(&square, &[Vec2(-1.0, 1.5), Vec2(2.0, 1.5)]), false];
+		let line = [Vec2(0.0, 0.0), Vec2(1.0, 0.0)];
+		assert_eq![hits(&line, &[Vec2(0.5, 1.0), Vec2(0.5, -1.0)]), true];
+		assert_eq![hits(&line, &[Vec2(1.5, 1.0), Vec2(1.5, -1.0)]), false];
+		assert_eq![hits(&line, &[Vec2(0.5, 0.0), Vec2(2.0, 0.0)]), true];
+		assert_eq![hits(&line, &[Vec2(1.5, 0.0), Vec2(2.0, 0.0)]), false];
+		assert_eq![hits(&[Vec2(0.5, 0.5)], &[Vec2(0.5, 0.5)]), true];
+		assert_eq![hits(&[Vec2(0.5, 0.5)], &[Vec2(0.5, 0.6)]), false];
+		// Repeated and interior points
+		let padded = [Vec2(0.0, 0.0), Vec2(0.0, 0.0), Vec2(0.5, 0.5), Vec2(1.0, 0.0),
+		              Vec2(0.0, 1.0)];
+		assert_eq![hits(&padded, &shifted(&square, Vec2(0.4, 0.4))), true];
+		assert_eq![hits(&padded, &shifted(&square, Vec2(0.6, 0.6))), false];
+	}
+
+	#[test]
+	fn sweep() {
+		let hexagon: Vec<Vec2> = (0..6)
+			.map(|i| {
+				let (sin, cos) = (i as f32 * std::f32::consts::PI / 3.0).sin_cos();
+				Vec2(cos, sin)
+			})
+			.collect();
+		let mut count = 0;
+		for i in 0..400 {
+			let (sin, cos) = (i as f32 * 0.61).sin_cos();
+			let radius = (i % 9) as f32 * 0.35;
+			let triangle = [Vec2(0.0, 0.0), Vec2(0.7, 0.1), Vec2(0.2, 0.5)];
+			count += hits(&hexagon, &shifted(&triangle, Vec2(cos, sin) * radius)) as usize;
+		}
+		assert![count > 0 && count < 400];
+	}
+
+	#[test]
+	fn helpers() {
+		let square = square();
+		assert_eq![farthest2(&square, Vec2(1.0, 1.0)), Vec2(1.0, 1.0)];
+		assert_eq![farthest2(&square, Vec2(-1.0, 0.0)), Vec2(0.0, 0.0)];
+		assert_eq![farthest2(&[], Vec2::X), Vec2::ZERO];
+		let other = shifted(&square, Vec2(3.0, 0.0));
+		assert_eq![support2(&square, &other, Vec2::X), Vec2(-2.0, 0.0)];
+		assert_eq![support2(&square, &other, -Vec2::X), Vec2(-4.0, 0.0)];
+	}
+
+	fn near(a: Vec2, b: Vec2, epsilon: f32) -> bool {
+		(a.0 - b.0).abs() <= epsilon && (a.1 - b.1).abs() <= epsilon
+	}
+
+	fn rectangle(min: Vec2, max: Vec2) -> [Vec2; 4] {
+		[min, Vec2(max.0, min.1), Vec2(min.0, max.1), max]
+	}
+
+	// Whether moving the second hull out along the normal just past the
+	// depth parts the hulls
+	fn resolves(hull1: &[Vec2], hull2: &[Vec2]) -> bool {
+		let contact = penetration2(hull1, hull2).unwrap();
+		!bgjk2(hull1, &shifted(hull2, contact.normal * (contact.depth * 1.01 + 1e-4)))
+	}
+
+	#[test]
+	fn overlapping_rectangles() {
+		let wide = rectangle(Vec2(0.0, 0.0), Vec2(2.0, 1.0));
+		let other = rectangle(Vec2(1.7, 0.2), Vec2(3.0, 0.8));
+		let contact = penetration2(&wide, &other).unwrap();
+		assert![near(contact.normal, Vec2::X, 4.0 * EPS)];
+		assert![(contact.depth - 0.3).abs() <= 4.0 * EPS];
+		let other = rectangle(Vec2(-0.5, -0.6), Vec2(1.0, 0.5));
+		let contact = penetration2(&wide, &other).unwrap();
+		assert![near(contact.normal, -Vec2::Y, 4.0 * EPS)];
+		assert![(contact.depth - 0.5).abs() <= 4.0 * EPS];
+		let other = rectangle(Vec2(-0.1, 0.3), Vec2(0.5, 2.0));
+		let contact = penetration2(&wide, &other).unwrap();
+		assert![near(contact.normal, -Vec2::X, 4.0 * EPS)];
+		assert![(contact.depth - 0.5).abs() <= 4.0 * EPS];
+		assert![resolves(&wide, &other)];
+	}
+
+	#[test]
+	fn rotated_squares() {
+		let square = square();
+		let diamond = [Vec2(0.7, 0.5), Vec2(1.2, 0.0), Vec2(1.7, 0.5), Vec2(1.2, 1.0)];
+		let contact = penetration2(&square, &diamond).unwrap();
+		assert![near(contact.normal, Vec2::X, 4.0 * EPS)];
+		assert![(contact.depth - 0.3).abs() <= 4.0 * EPS];
+		// The corner of the square sinks into the side of the diamond
+		let diamond = shifted(&diamond, Vec2(0.1, 0.6));
+		let contact = penetration2(&square, &diamond).unwrap();
+		let side = Vec2(1.0, 1.0).normalized();
+		assert![near(contact.normal, side, 4.0 * EPS)];
+		assert![(contact.depth - 0.1 * side.0).abs() <= 4.0 * EPS];
+		for i in 0..16 {
+			let (sin, cos) = (i as f32 * 0.4).sin_cos();
+			let turned: Vec<Vec2> = square.iter()
+				.map(|v| Vec2(v.0 * cos - v.1 * sin, v.0 * sin + v.1 * cos) + Vec2(0.6, 0.3))
+				.collect();
+			assert![resolves(&square, &turned)];
+		}
+	}
+
+	#[test]
+	fn contained() {
+		let square = square();
+		let small = rectangle(Vec2(0.1, 0.4), Vec2(0.2, 0.6));
+		let contact = penetration2(&square, &small).unwrap();
+		assert![near(contact.normal, -Vec2::X, 4.0 * EPS)];
+		assert![(contact.depth - 0.2).abs() <= 4.0 * EPS];
+		let contact = penetration2(&small, &square).unwrap();
+		assert![near(contact.normal, Vec2::X, 4.0 * EPS)];
+		assert![(contact.depth - 0.2).abs() <= 4.0 * EPS];
+		let contact = penetration2(&square, &square).unwrap();
+		assert![(contact.depth - 1.0).abs() <= 4.0 * EPS];
+	}
+
+	#[test]
+	fn touching_contact() {
+		let square = square();
+		let contact = penetration2(&square, &shifted(&square, Vec2(1.0, 0.5))).unwrap();
+		assert_eq![(contact.normal, contact.depth), (Vec2::X, 0.0)];
+		let contact = penetration2(&square, &shifted(&square, Vec2(-0.3, -1.0))).unwrap();
+		assert_eq![(contact.normal, contact.depth), (-Vec2::Y, 0.0)];
+		let contact = penetration2(&square, &shifted(&square, Vec2::ONE)).unwrap();
+		assert_eq![contact.depth, 0.0];
+		assert![contact.normal == Vec2::X || contact.normal == Vec2::Y];
+		// Collinear segments have no area between them
+		let line = [Vec2(0.0, 0.0), Vec2(1.0, 0.0)];
+		let contact = penetration2(&line, &[Vec2(0.5, 0.0), Vec2(2.0, 0.0)]).unwrap();
+		assert_eq![contact.depth, 0.0];
+		assert![near(contact.normal, Vec2::Y, EPS) || near(contact.normal, -Vec2::Y, EPS)];
+		assert_eq![penetration2(&[Vec2(0.5, 0.5)], &[Vec2(0.5, 0.5)]).unwrap().depth, 0.0];
+	}
+
+	#[test]
+	fn apart_has_none() {
+		let square = square();
+		assert![penetration2(&square, &shifted(&square, Vec2(1.001, 0.5))).is_none()];
+		assert![penetration2(&square, &[Vec2(2.0, 2.0)]).is_none()];
+	}
+}
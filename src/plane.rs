@@ -0,0 +1,162 @@
+//! Hulls against planes and halfspaces, for culling.
+use super::{Float, Vector3, farthest};
+
+/// The plane of the points `p` with `normal.dot(p) == d`
+///
+/// The normal need not be normalized, `d` is then in lengths of it. The
+/// side the normal points to is in front of the plane.
+#[derive(Clone, Copy, Debug)]
+pub struct Plane<T = f32> {
+	/// Perpendicular to the plane, pointing to its front
+	pub normal: Vector3<T>,
+	/// The offset of the plane along `normal`
+	pub d: T,
+}
+
+impl<T: Float> Plane<T> {
+	/// The plane through `point` perpendicular to `normal`
+	pub fn through(point: Vector3<T>, normal: Vector3<T>) -> Plane<T> {
+		Plane {
+			normal,
+			d: normal.dot(point),
+		}
+	}
+
+	/// How far in front of the plane `point` is, negative behind it. In
+	/// lengths of `normal`, so the distance for a unit normal.
+	pub fn signed_distance(&self, point: Vector3<T>) -> T {
+		self.normal.dot(point) - self.d
+	}
+}
+
+/// Which side of a plane a hull is on, see `classify_hull`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PlaneSide {
+	/// No point is behind the plane and some are in front of it
+	InFront,
+	/// No point is in front of the plane and some are behind it
+	Behind,
+	/// Points are on both sides of the plane, or all of them are on it
+	Straddling,
+}
+
+/// Which side of `plane` the hull is on
+///
+/// Only the two points of the hull farthest along and against the normal
+/// are looked at. Points on the plane count for neither side, so a hull
+/// resting on the plane is in front of or behind it, while a flat hull
+/// lying in the plane straddles it, being on both sides at once. So does a
+/// hull with no points, all none of which are on the plane.
+///
+/// ```
+/// use bgjk::{classify_hull, Plane, PlaneSide, Vec3};
+///
+/// let triangle = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// let floor = Plane { normal: Vec3::Z, d: -1.0 };
+/// assert_eq![classify_hull(&floor, &triangle), PlaneSide::InFront];
+/// let wall = Plane { normal: Vec3::X, d: 0.5 };
+/// assert_eq![classify_hull(&wall, &triangle), PlaneSide::Straddling];
+/// ```
+pub fn classify_hull<T: Float>(plane: &Plane<T>, hull: &[Vector3<T>]) -> PlaneSide {
+	let (front, back) = match (farthest(hull, plane.normal), farthest(hull, -plane.normal)) {
+		(Some(front), Some(back)) => (plane.signed_distance(front), plane.signed_distance(back)),
+		_ => return PlaneSide::Straddling,
+	};
+	if back >= T::ZERO && front > T::ZERO {
+		PlaneSide::InFront
+	} else if front <= T::ZERO && back < T::ZERO {
+		PlaneSide::Behind
+	} else {
+		PlaneSide::Straddling
+	}
+}
+
+/// Whether the hull reaches into the halfspace in front of `plane`
+///
+/// The halfspace includes the plane, so a hull touching the plane from
+/// behind intersects it, as touching hulls do for `bgjk`. Culling against
+/// a frustum of inward facing planes drops a hull as soon as it misses the
+/// halfspace of one of them. A hull with no points reaches nowhere.
+///
+/// ```
+/// use bgjk::{hull_intersects_halfspace, Plane, Vec3};
+///
+/// let triangle = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// assert![hull_intersects_halfspace(&Plane { normal: Vec3::X, d: 1.0 }, &triangle)];
+/// assert![!hull_intersects_halfspace(&Plane { normal: Vec3::X, d: 1.5 }, &triangle)];
+/// ```
+pub fn hull_intersects_halfspace<T: Float>(plane: &Plane<T>, hull: &[Vector3<T>]) -> bool {
+	farthest(hull, plane.normal).is_some_and(|point| plane.signed_distance(point) >= T::ZERO)
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+
+	use super::super::{Plane, PlaneSide, Vec3, classify_hull, hull_intersects_halfspace};
+	use super::super::test_util::unit_cube;
+
+	#[test]
+	fn split() {
+		let cube = unit_cube();
+		for &normal in &[Vec3::X, Vec3::Y, Vec3::Z, -Vec3::X, Vec3::ONE] {
+			let plane = Plane::through(Vec3(0.5, 0.5, 0.5), normal);
+			assert_eq![classify_hull(&plane, &cube), PlaneSide::Straddling];
+			assert_eq![hull_intersects_halfspace(&plane, &cube), true];
+			let flipped = Plane::through(Vec3(0.5, 0.5, 0.5), -normal);
+			assert_eq![hull_intersects_halfspace(&flipped, &cube), true];
+		}
+	}
+
+	#[test]
+	fn one_side() {
+		let cube = unit_cube();
+		for &normal in &[Vec3::X, Vec3::Y, Vec3::Z, Vec3(1.0, 2.0, 0.5)] {
+			let behind = Plane::through(Vec3::ONE * 5.0, normal);
+			let front = Plane::through(Vec3::ONE * -5.0, normal);
+			assert_eq![classify_hull(&behind, &cube), PlaneSide::Behind];
+			assert_eq![hull_intersects_halfspace(&behind, &cube), false];
+			assert_eq![classify_hull(&front, &cube), PlaneSide::InFront];
+			assert_eq![hull_intersects_halfspace(&front, &cube), true];
+		}
+	}
+
+	#[test]
+	fn resting() {
+		let cube = unit_cube();
+		let floor = Plane { normal: Vec3::Z, d: 0.0 };
+		assert_eq![classify_hull(&floor, &cube), PlaneSide::InFront];
+		let ceiling = Plane { normal: Vec3::Z, d: 1.0 };
+		assert_eq![classify_hull(&ceiling, &cube), PlaneSide::Behind];
+		assert_eq![hull_intersects_halfspace(&ceiling, &cube), true];
+	}
+
+	#[test]
+	fn lying_in_plane() {
+		let square = pts![(0.0, 0.0, 2.0), (1.0, 0.0, 2.0), (0.0, 1.0, 2.0), (1.0, 1.0, 2.0)];
+		let plane = Plane { normal: Vec3::Z, d: 2.0 };
+		assert_eq![classify_hull(&plane, &square), PlaneSide::Straddling];
+		assert_eq![hull_intersects_halfspace(&plane, &square), true];
+		let flipped = Plane { normal: -Vec3::Z, d: -2.0 };
+		assert_eq![classify_hull(&flipped, &square), PlaneSide::Straddling];
+		assert_eq![hull_intersects_halfspace(&flipped, &square), true];
+		assert_eq![classify_hull(&Plane { normal: Vec3::Z, d: 1.0 }, &square), PlaneSide::InFront];
+	}
+
+	#[test]
+	fn scaled_normal() {
+		let plane = Plane::through(Vec3(0.0, 0.0, 2.0), Vec3::Z * 4.0);
+		assert_eq![plane.signed_distance(Vec3(3.0, 1.0, 3.0)), 4.0];
+		assert_eq![classify_hull(&plane, &unit_cube()), PlaneSide::Behind];
+	}
+
+	#[test]
+	fn empty_hull() {
+		// Whichever side of the plane the origin is on
+		for &d in &[-1.0, 0.0, 1.0] {
+			let plane = Plane { normal: Vec3::Z, d };
+			assert_eq![classify_hull(&plane, &[]), PlaneSide::Straddling];
+			assert_eq![hull_intersects_halfspace(&plane, &[]), false];
+		}
+	}
+}
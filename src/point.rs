@@ -0,0 +1,157 @@
+//! Containment of a single point in a hull.
+use super::{Float, Vector3, farthest, gjk};
+
+/// Whether `point` lies inside or on the boundary of `hull`
+///
+/// Gives the same answer as `bgjk(&[point], hull)`, but the support of a
+/// single point needs no search, and hulls of up to four points are
+/// decided directly by plane tests instead of running the algorithm at
+/// all. Points on a face, an edge or a corner are inside, as they are for
+/// `bgjk`. An empty hull contains no points.
+///
+/// ```
+/// use bgjk::{point_in_hull, Vec3};
+///
+/// let tetrahedron = [Vec3::new(0.0, 0.0, 0.0),
+///                    Vec3::new(1.0, 0.0, 0.0),
+///                    Vec3::new(0.0, 1.0, 0.0),
+///                    Vec3::new(0.0, 0.0, 1.0)];
+/// assert![point_in_hull(Vec3::new(0.2, 0.2, 0.2), &tetrahedron)];
+/// assert![!point_in_hull(Vec3::new(0.5, 0.5, 0.5), &tetrahedron)];
+/// ```
+pub fn point_in_hull<T: Float>(point: Vector3<T>, hull: &[Vector3<T>]) -> bool {
+	debug_assert![point.is_finite() && hull.iter().all(Vector3::is_finite),
+	              "bgjk called with a non-finite point in a hull"];
+	let decided = match *hull {
+		[] => Some(false),
+		[a] => Some(point == a),
+		[a, b] => Some(on_segment(point, a, b)),
+		[a, b, c] => in_triangle(point, a, b, c),
+		[a, b, c, d] => in_tetrahedron(point, a, b, c, d),
+		_ => None,
+	};
+	decided.unwrap_or_else(|| {
+		gjk(|direction| point - farthest(hull, -direction).unwrap_or(Vector3::ZERO))
+	})
+}
+
+fn on_segment<T: Float>(point: Vector3<T>, a: Vector3<T>, b: Vector3<T>) -> bool {
+	let (ab, ap) = (b - a, point - a);
+	if ab.cross(ap) != Vector3::ZERO {
+		return false;
+	}
+	let along = ap.dot(ab);
+	along >= T::ZERO && along <= ab.length_squared() && (ab != Vector3::ZERO || point == a)
+}
+
+// `None` when the triangle is degenerate
+fn in_triangle<T: Float>(point: Vector3<T>,
+                         a: Vector3<T>,
+                         b: Vector3<T>,
+                         c: Vector3<T>) -> Option<bool> {
+	let normal = (b - a).cross(c - a);
+	if normal == Vector3::ZERO {
+		return None;
+	} else if normal.dot(point - a) != T::ZERO {
+		return Some(false);
+	}
+	let within = |from: Vector3<T>, to: Vector3<T>| {
+		normal.dot((to - from).cross(point - from)) >= T::ZERO
+	};
+	Some(within(a, b) && within(b, c) && within(c, a))
+}
+
+// `None` when the tetrahedron is flat
+fn in_tetrahedron<T: Float>(point: Vector3<T>,
+                            a: Vector3<T>,
+                            b: Vector3<T>,
+                            c: Vector3<T>,
+                            d: Vector3<T>)
+                            -> Option<bool> {
+	if (b - a).cross(c - a).dot(d - a) == T::ZERO {
+		return None;
+	}
+	// The point is on the same side of each face as the opposite corner
+	let faces = [(a, b, c, d), (a, b, d, c), (a, c, d, b), (b, c, d, a)];
+	Some(faces.iter().all(|&(p, q, r, opposite)| {
+		let normal = (q - p).cross(r - p);
+		let side = normal.dot(point - p);
+		side == T::ZERO || (side > T::ZERO) == (normal.dot(opposite - p) > T::ZERO)
+	}))
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+
+	use std::f32;
+	use super::super::{Vec3, bgjk, point_in_hull};
+	use super::super::test_util::unit_cube;
+	static EPS: f32 = f32::EPSILON;
+
+	#[test]
+	fn cube() {
+		let cube = unit_cube();
+		assert_eq![point_in_hull(Vec3(0.5, 0.5, 0.5), &cube), true];
+		let boundary = [Vec3(0.0, 0.5, 0.5), // Faces
+		                Vec3(0.5, 1.0, 0.5),
+		                Vec3(0.5, 0.5, 0.0),
+		                Vec3(1.0, 1.0, 0.5), // Edges
+		                Vec3(0.0, 0.5, 1.0),
+		                Vec3(0.0, 0.0, 0.0), // Corners
+		                Vec3(1.0, 1.0, 1.0)];
+		for &point in &boundary {
+			assert_eq![point_in_hull(point, &cube), true];
+		}
+		let outside = [Vec3(1.0 + 2.0 * EPS, 0.5, 0.5),
+		               Vec3(0.5, -EPS, 0.5),
+		               Vec3(1.0, 1.0 + 2.0 * EPS, 0.5),
+		               Vec3(-EPS, -EPS, -EPS),
+		               Vec3(1.0 + 2.0 * EPS, 1.0, 1.0)];
+		for &point in &outside {
+			assert_eq![point_in_hull(point, &cube), false];
+		}
+	}
+
+	#[test]
+	fn planar() {
+		let square = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		assert_eq![point_in_hull(Vec3(0.5, 0.5, 0.0), &square), true];
+		assert_eq![point_in_hull(Vec3(1.0, 0.5, 0.0), &square), true];
+		assert_eq![point_in_hull(Vec3(0.5, 0.5, EPS), &square), false];
+		assert_eq![point_in_hull(Vec3(0.5, 0.5, -EPS), &square), false];
+		assert_eq![point_in_hull(Vec3(1.0 + 2.0 * EPS, 0.5, 0.0), &square), false];
+	}
+
+	#[test]
+	fn small_hulls_match_bgjk() {
+		let tetrahedron = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (0.0, 0.0, 1.0)];
+		let flat = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+		let hulls: [&[Vec3]; 7] = [&pts![(0.5, 0.5, 0.5)],
+		                           &pts![(0.0, 0.0, 0.0), (1.0, 1.0, 0.0)],
+		                           &pts![(0.5, 0.0, 0.5), (0.5, 0.0, 0.5)],
+		                           &pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0)],
+		                           &pts![(0.0, 0.0, 0.0), (0.5, 0.5, 0.5), (1.0, 1.0, 1.0)],
+		                           &tetrahedron,
+		                           &flat];
+		let mut hits = 0;
+		for hull in &hulls {
+			for x in 0..5 {
+				for y in 0..5 {
+					for z in 0..5 {
+						let point = Vec3(x as f32, y as f32, z as f32) * 0.25;
+						let inside = point_in_hull(point, hull);
+						assert_eq![inside, bgjk(&[point], hull)];
+						hits += inside as usize;
+					}
+				}
+			}
+		}
+		assert![hits > hulls.len()];
+	}
+
+	#[test]
+	fn empty() {
+		assert_eq![point_in_hull(Vec3::ZERO, &[]), false];
+	}
+}
@@ -0,0 +1,109 @@
+//! Random `Vec3` and hull generation for property tests.
+use std::f32::consts::PI;
+use proptest::arbitrary::Arbitrary;
+use proptest::collection::{SizeRange, vec};
+use proptest::strategy::{BoxedStrategy, Strategy};
+use super::Vec3;
+
+/// The interval every coordinate of an arbitrary `Vec3` is drawn from
+///
+/// The lower bound is inclusive and the upper bound exclusive. Defaults
+/// to `[-1000, 1000)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CoordinateRange {
+	/// The smallest coordinate
+	pub lo: f32,
+	/// The bound all coordinates stay below
+	pub hi: f32,
+}
+
+impl Default for CoordinateRange {
+	fn default() -> CoordinateRange {
+		CoordinateRange { lo: -1000.0, hi: 1000.0 }
+	}
+}
+
+impl Arbitrary for Vec3 {
+	type Parameters = CoordinateRange;
+	type Strategy = BoxedStrategy<Vec3>;
+
+	fn arbitrary_with(range: CoordinateRange) -> BoxedStrategy<Vec3> {
+		let coordinate = range.lo..range.hi;
+		(coordinate.clone(), coordinate.clone(), coordinate)
+			.prop_map(Vec3::from)
+			.boxed()
+	}
+}
+
+/// Hulls with a number of points in `vertices`, inside the box `range`³
+///
+/// Ask for at least four vertices (e.g. `4..=16`) to get hulls with volume,
+/// fewer give points, segments and triangles. A range including zero also
+/// generates empty hulls.
+pub fn hull_in_box<R: Into<SizeRange>>(range: CoordinateRange,
+                                       vertices: R)
+                                       -> BoxedStrategy<Vec<Vec3>> {
+	vec(Vec3::arbitrary_with(range), vertices).boxed()
+}
+
+/// Hulls with a number of points in `vertices`, on a sphere
+///
+/// Every point is a vertex of the hull, so none of them are interior. See
+/// `hull_in_box` for choosing the vertex count.
+pub fn hull_on_sphere<R: Into<SizeRange>>(center: Vec3,
+                                          radius: f32,
+                                          vertices: R)
+                                          -> BoxedStrategy<Vec<Vec3>> {
+	let point = (0.0..2.0 * PI, -1.0f32..1.0).prop_map(move |(angle, z)| {
+		let ring = (1.0 - z * z).sqrt();
+		center + Vec3(ring * angle.cos(), ring * angle.sin(), z) * radius
+	});
+	vec(point, vertices).boxed()
+}
+
+#[cfg(test)]
+mod tests {
+
+	use proptest::prelude::*;
+	use super::super::{CoordinateRange, Vec3, bgjk, hull_in_box, hull_on_sphere};
+
+	proptest! {
+		#[test]
+		fn coordinates_in_range(v in Vec3::arbitrary_with(CoordinateRange { lo: -2.0, hi: 3.0 })) {
+			for axis in 0..3 {
+				prop_assert![v[axis] >= -2.0 && v[axis] < 3.0];
+			}
+		}
+
+		#[test]
+		fn sphere_points(hull in hull_on_sphere(Vec3(1.0, 2.0, 3.0), 5.0, 1..=16)) {
+			prop_assert![!hull.is_empty() && hull.len() <= 16];
+			for v in &hull {
+				prop_assert![((*v - Vec3(1.0, 2.0, 3.0)).length() - 5.0).abs() < 1e-4];
+			}
+		}
+
+		// Hulls with fewer than four points are flat, and whether the origin lies
+		// inside their flat Minkowski difference is decided by rounding
+		#[test]
+		fn self_intersection(hull in hull_in_box(CoordinateRange::default(), 4..=16)) {
+			prop_assert![bgjk(&hull, &hull)];
+		}
+
+		#[test]
+		fn symmetric(a in hull_on_sphere(Vec3::ZERO, 1.0, 4..=12),
+		             b in hull_on_sphere(Vec3(1.5, 0.0, 0.0), 1.0, 4..=12)) {
+			prop_assert_eq![bgjk(&a, &b), bgjk(&b, &a)];
+		}
+
+		#[test]
+		fn translation_separates(a in hull_in_box(CoordinateRange::default(), 1..=16),
+		                         b in hull_in_box(CoordinateRange::default(), 1..=16),
+		                         direction in Vec3::arbitrary()) {
+			prop_assume![direction.try_normalized().is_some()];
+			let far: Vec<Vec3> = b.iter().map(|v| *v + direction.normalized() * 4000.0).collect();
+			prop_assert![!bgjk(&a, &far)];
+		}
+	}
+
+}
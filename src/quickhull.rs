@@ -0,0 +1,622 @@
+//! Convex hulls of point clouds, keeping only their corners, their volumes,
+//! and checks of whether a set of points is one.
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use super::{Float, Vector3, centroid, penetration};
+
+// A triangle of the hull under construction, its corners counterclockwise
+// seen from outside, with the points above it not yet in the hull
+struct Face<T> {
+	corners: [usize; 3],
+	normal: Vector3<T>,
+	offset: T,
+	outside: Vec<usize>,
+	live: bool,
+}
+
+impl<T: Float> Face<T> {
+	fn new(points: &[Vector3<T>], corners: [usize; 3]) -> Face<T> {
+		let [a, b, c] = corners.map(|index| points[index]);
+		// Sliver faces get no normal, so no point is ever above them
+		let normal = (b - a).cross(c - a).try_normalized().unwrap_or(Vector3::ZERO);
+		Face { corners, normal, offset: normal.dot(a), outside: Vec::new(), live: true }
+	}
+
+	fn height(&self, point: Vector3<T>) -> T {
+		self.normal.dot(point) - self.offset
+	}
+
+	fn edges(&self) -> [(usize, usize); 3] {
+		let [a, b, c] = self.corners;
+		[(a, b), (b, c), (c, a)]
+	}
+}
+
+// The first of the indexed points `along` is the largest for
+fn extreme<T, F>(points: &[Vector3<T>], indices: &[usize], along: F) -> usize
+	where T: Float,
+	      F: Fn(Vector3<T>) -> T
+{
+	let mut best = indices[0];
+	for &index in indices {
+		if along(points[index]) > along(points[best]) {
+			best = index;
+		}
+	}
+	best
+}
+
+/// The corners of the convex hull of `points`
+///
+/// Strips the points inside the hull, on its faces or edges, and repeated,
+/// which `bgjk` would only spend time on, with the quickhull algorithm. The
+/// corners are returned in no particular order. Points within a relative
+/// distance of about a hundred machine epsilons of the hull surface count
+/// as on it.
+///
+/// Flat clouds give the corners of their polygon, points on a line the two
+/// ends, a repeated point that point alone and no points none.
+///
+/// ```
+/// use bgjk::{convex_hull, Vec3};
+///
+/// let mut cloud: Vec<Vec3> = (0..8)
+///     .map(|i| Vec3::new((i & 1) as f32, (i >> 1 & 1) as f32, (i >> 2 & 1) as f32))
+///     .collect();
+/// cloud.push(Vec3::new(0.5, 0.5, 0.5));
+/// cloud.push(Vec3::new(1.0, 0.5, 0.5));
+/// assert_eq![convex_hull(&cloud).len(), 8];
+/// ```
+pub fn convex_hull<T: Float>(points: &[Vector3<T>]) -> Vec<Vector3<T>> {
+	debug_assert![points.iter().all(Vector3::is_finite),
+	              "convex_hull called with a non-finite point"];
+	if points.is_empty() {
+		return Vec::new();
+	}
+	let (span, tolerance) = span(points);
+	match span {
+		Span::Point(a) => vec![points[a]],
+		Span::Line(a, b) => vec![points[a], points[b]],
+		Span::Plane(a, along, normal) => polygon(points, a, along, normal, tolerance),
+		Span::Solid(seed) => solid(points, seed, tolerance),
+	}
+}
+
+/// Whether no point lies inside the hull of the other points by more than
+/// `tolerance`
+///
+/// The vertices of a concave mesh make a hull that `bgjk` answers for as
+/// if the dents were filled, which this catches where the mesh is built.
+/// Points on the surface of the hull of the others, on a face, an edge or
+/// repeated, do not count as inside, they only cost `bgjk` time, see
+/// `convex_hull`. A point inside but within `tolerance` of the surface does
+/// not count either, so rounding does not fail a hull that is convex.
+///
+/// Flat sets are checked within their plane, and points on a line along
+/// it, a point inside a square counting as inside. Takes one penetration
+/// query per point, for checks while authoring rather than every frame.
+///
+/// ```
+/// use bgjk::{is_convex_hull, Vec3};
+///
+/// let mut cloud: Vec<Vec3> = (0..8)
+///     .map(|i| Vec3::new((i & 1) as f32, (i >> 1 & 1) as f32, (i >> 2 & 1) as f32))
+///     .collect();
+/// assert![is_convex_hull(&cloud, 1e-4)];
+/// cloud.push(Vec3::new(0.5, 0.5, 0.5));
+/// assert![!is_convex_hull(&cloud, 1e-4)];
+/// ```
+pub fn is_convex_hull<T: Float>(points: &[Vector3<T>], tolerance: T) -> bool {
+	debug_assert![points.iter().all(Vector3::is_finite),
+	              "is_convex_hull called with a non-finite point"];
+	if points.len() < 2 {
+		return true;
+	}
+	// Flat sets are stretched out of their plane, far enough that no point
+	// is nearer the new faces than the old ones, so that the depth inside
+	// the stretched hull is the depth within the plane
+	let (span, _) = span(points);
+	let (low, high) = bounds(points);
+	let reach = ((high - low).length() + tolerance.abs()) * T::from_f32(2.0);
+	let stretch = match span {
+		Span::Point(_) => return true,
+		Span::Line(a, b) => {
+			let (side, up) = (points[b] - points[a]).any_orthonormal_pair();
+			vec![(side + up) * reach, (side - up) * reach, (up - side) * reach, -(side + up) * reach]
+		}
+		Span::Plane(_, _, normal) => vec![normal * reach, -normal * reach],
+		Span::Solid(_) => vec![Vector3::ZERO],
+	};
+	let mut others = Vec::with_capacity((points.len() - 1) * stretch.len());
+	for (index, &point) in points.iter().enumerate() {
+		others.clear();
+		for (other, &corner) in points.iter().enumerate() {
+			if other != index {
+				others.extend(stretch.iter().map(|&offset| corner + offset));
+			}
+		}
+		if penetration(&[point], &others).is_some_and(|inside| inside.depth > tolerance) {
+			return false;
+		}
+	}
+	true
+}
+
+/// The volume of the convex hull of `points`
+///
+/// Builds the faces of the hull like `convex_hull` does and sums the
+/// volumes of the tetrahedra they make with the `centroid` of the points,
+/// which is inside the hull, so points far from the origin lose no more
+/// precision than their own coordinates have. Flat hulls, lines and points
+/// have no volume.
+///
+/// ```
+/// use bgjk::{hull_volume, Vec3};
+///
+/// let tetrahedron = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0),
+///                    Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 0.0, 3.0)];
+/// assert_eq![hull_volume(&tetrahedron), 0.5];
+/// ```
+pub fn hull_volume<T: Float>(points: &[Vector3<T>]) -> T {
+	debug_assert![points.iter().all(Vector3::is_finite),
+	              "hull_volume called with a non-finite point"];
+	if points.is_empty() {
+		return T::ZERO;
+	}
+	let (seed, tolerance) = match span(points) {
+		(Span::Solid(seed), tolerance) => (seed, tolerance),
+		_ => return T::ZERO,
+	};
+	let center = centroid(points).unwrap_or(Vector3::ZERO);
+	let six_times = faces(points, seed, tolerance).iter().fold(T::ZERO, |sum, face| {
+		let [a, b, c] = face.corners.map(|index| points[index] - center);
+		sum + a.dot(b.cross(c))
+	});
+	six_times / T::from_f32(6.0)
+}
+
+// The corners of each face of the hull of `points`, counterclockwise seen
+// from outside, `None` where the points do not span space
+pub fn triangles<T: Float>(points: &[Vector3<T>]) -> Option<Vec<[usize; 3]>> {
+	if points.is_empty() {
+		return None;
+	}
+	match span(points) {
+		(Span::Solid(seed), tolerance) => {
+			Some(faces(points, seed, tolerance).iter().map(|face| face.corners).collect())
+		}
+		_ => None,
+	}
+}
+
+// The corners of the box around a set of points
+fn bounds<T: Float>(points: &[Vector3<T>]) -> (Vector3<T>, Vector3<T>) {
+	let (mut low, mut high) = (points[0], points[0]);
+	for point in points {
+		low = low.min(*point);
+		high = high.max(*point);
+	}
+	(low, high)
+}
+
+// How many dimensions a set of points spans, with the points found to span
+// them: the widest pair of extremes along the axes, then the point farthest
+// from their line and the point farthest from the plane of the three
+enum Span<T> {
+	Point(usize),
+	Line(usize, usize),
+	// A point of the plane, the unit direction to a second and the normal
+	Plane(usize, Vector3<T>, Vector3<T>),
+	Solid([usize; 4]),
+}
+
+// The span of a nonempty set of points, with the distance below which
+// points count as the same, relative to the size of the coordinates
+fn span<T: Float>(points: &[Vector3<T>]) -> (Span<T>, T) {
+	let all: Vec<usize> = (0..points.len()).collect();
+	let (low, high) = bounds(points);
+	let size = (high - low).abs().max(low.abs()).max(high.abs());
+	let tolerance = size.0.max(size.1).max(size.2) * T::EPSILON * T::from_f32(100.0);
+	let mut first = (0, 0);
+	for axis in &[Vector3::X, Vector3::Y, Vector3::Z] {
+		let (lowest, highest) = (extreme(points, &all, |p| -p.dot(*axis)),
+		                         extreme(points, &all, |p| p.dot(*axis)));
+		let length = (points[highest] - points[lowest]).length();
+		if length > (points[first.1] - points[first.0]).length() {
+			first = (lowest, highest);
+		}
+	}
+	let (a, b) = first;
+	if (points[b] - points[a]).length() <= tolerance {
+		return (Span::Point(a), tolerance);
+	}
+	let along = (points[b] - points[a]).normalized();
+	let c = extreme(points, &all, |p| (p - points[a]).cross(along).length());
+	if (points[c] - points[a]).cross(along).length() <= tolerance {
+		return (Span::Line(a, b), tolerance);
+	}
+	let normal = along.cross(points[c] - points[a]).normalized();
+	let d = extreme(points, &all, |p| (p - points[a]).dot(normal).abs());
+	if (points[d] - points[a]).dot(normal).abs() <= tolerance {
+		return (Span::Plane(a, along, normal), tolerance);
+	}
+	(Span::Solid([a, b, c, d]), tolerance)
+}
+
+// The corners of a flat cloud through `points[origin]`, in the plane of
+// the unit vectors `along` and `normal`, by the monotone chain algorithm
+fn polygon<T: Float>(points: &[Vector3<T>],
+                     origin: usize,
+                     along: Vector3<T>,
+                     normal: Vector3<T>,
+                     tolerance: T)
+                     -> Vec<Vector3<T>> {
+	let across = normal.cross(along);
+	let flat = |index: usize| {
+		let offset = points[index] - points[origin];
+		(offset.dot(along), offset.dot(across))
+	};
+	let mut order: Vec<usize> = (0..points.len()).collect();
+	order.sort_by(|&i, &j| flat(i).partial_cmp(&flat(j)).unwrap_or(Ordering::Equal));
+	// Whether `middle` is not strictly left of the line from `from` to `to`
+	let not_left = |from: usize, middle: usize, to: usize| {
+		let ((x0, y0), (x1, y1), (x2, y2)) = (flat(from), flat(middle), flat(to));
+		let turn = (x1 - x0) * (y2 - y0) - (y1 - y0) * (x2 - x0);
+		let reach = ((x2 - x0) * (x2 - x0) + (y2 - y0) * (y2 - y0)).sqrt();
+		turn <= tolerance * reach
+	};
+	let mut chain: Vec<usize> = Vec::new();
+	for pass in 0..2 {
+		let start = chain.len();
+		let ordered: Vec<usize> = if pass == 0 {
+			order.clone()
+		} else {
+			order.iter().rev().cloned().collect()
+		};
+		for index in ordered {
+			while chain.len() >= start + 2 &&
+			      not_left(chain[chain.len() - 2], chain[chain.len() - 1], index) {
+				chain.pop();
+			}
+			chain.push(index);
+		}
+		// The last point of each half starts the other
+		chain.pop();
+	}
+	chain.iter().map(|&index| points[index]).collect()
+}
+
+// The faces of the hull of a cloud spanning space, grown from the
+// tetrahedron of `seed` by adding the point farthest above a face until none
+// is above any
+fn faces<T: Float>(points: &[Vector3<T>], seed: [usize; 4], tolerance: T) -> Vec<Face<T>> {
+	let [a, b, c, d] = seed;
+	let mut faces = Vec::new();
+	let sides = [(a, b, c, d), (a, c, d, b), (a, d, b, c), (b, d, c, a)];
+	for &(first, second, third, opposite) in &sides {
+		// Turned outward, away from the other corner of the tetrahedron
+		let face = Face::new(points, [first, second, third]);
+		if face.height(points[opposite]) > T::ZERO {
+			faces.push(Face::new(points, [first, third, second]));
+		} else {
+			faces.push(face);
+		}
+	}
+	let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+	for (index, face) in faces.iter().enumerate() {
+		for &edge in &face.edges() {
+			edges.insert(edge, index);
+		}
+	}
+	for (index, point) in points.iter().enumerate() {
+		if seed.contains(&index) {
+			continue;
+		}
+		if let Some(face) = faces.iter_mut().find(|face| face.height(*point) > tolerance) {
+			face.outside.push(index);
+		}
+	}
+
+	while let Some(start) = faces.iter().position(|face| face.live && !face.outside.is_empty()) {
+		let eye = extreme(points, &faces[start].outside, |p| faces[start].height(p));
+		let view = points[eye];
+		// The faces the new point sees, spreading from the first across edges
+		let mut visible = vec![start];
+		let mut next = 0;
+		while next < visible.len() {
+			for &(from, to) in &faces[visible[next]].edges() {
+				let neighbor = edges[&(to, from)];
+				if !visible.contains(&neighbor) && faces[neighbor].height(view) > tolerance {
+					visible.push(neighbor);
+				}
+			}
+			next += 1;
+		}
+		// The edges between seen and unseen faces, which the new faces fan
+		// out from to the new point
+		let mut horizon = Vec::new();
+		for &index in &visible {
+			for &(from, to) in &faces[index].edges() {
+				if !visible.contains(&edges[&(to, from)]) {
+					horizon.push((from, to));
+				}
+			}
+		}
+		let mut orphans = Vec::new();
+		for &index in &visible {
+			for edge in &faces[index].edges() {
+				edges.remove(edge);
+			}
+			let face = &mut faces[index];
+			face.live = false;
+			orphans.append(&mut face.outside);
+		}
+		let first_new = faces.len();
+		for &(from, to) in &horizon {
+			let index = faces.len();
+			faces.push(Face::new(points, [from, to, eye]));
+			for &edge in &faces[index].edges() {
+				edges.insert(edge, index);
+			}
+		}
+		for orphan in orphans {
+			if orphan == eye {
+				continue;
+			}
+			let point = points[orphan];
+			let above = faces[first_new..].iter_mut().find(|face| face.height(point) > tolerance);
+			if let Some(face) = above {
+				face.outside.push(orphan);
+			}
+		}
+	}
+	faces.retain(|face| face.live);
+	faces
+}
+
+// The corners of a cloud spanning space, see `faces`
+fn solid<T: Float>(points: &[Vector3<T>], seed: [usize; 4], tolerance: T) -> Vec<Vector3<T>> {
+	// Corners in the middle of a flat region or along a straight edge are
+	// on fewer than three planes of faces, and not corners of the hull
+	let parallel = T::EPSILON * T::from_f32(100.0);
+	let mut planes: Vec<Vec<Vector3<T>>> = vec![Vec::new(); points.len()];
+	for face in faces(points, seed, tolerance).iter().filter(|face| face.normal != Vector3::ZERO) {
+		for &corner in &face.corners {
+			let seen = &mut planes[corner];
+			if !seen.iter().any(|normal| normal.cross(face.normal).length() <= parallel) {
+				seen.push(face.normal);
+			}
+		}
+	}
+	planes.iter()
+		.enumerate()
+		.filter(|&(_, seen)| seen.len() >= 3)
+		.map(|(index, _)| points[index])
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+	use std::f32::consts::PI;
+	use super::super::{Vec3, bgjk, convex_hull, hull_volume, is_convex_hull};
+	use super::super::test_util::cube_at;
+
+	// Points scattered over the unit cube, never on its surface
+	fn scattered(count: usize) -> Vec<Vec3> {
+		(0..count)
+			.map(|i| {
+				let t = i as f32;
+				Vec3((t * 0.37).sin(), (t * 1.91).sin(), (t * 0.73 + 1.0).sin()) * 0.49 +
+				Vec3::ONE * 0.5
+			})
+			.collect()
+	}
+
+	fn same_points(mut found: Vec<Vec3>, expected: &[Vec3]) -> bool {
+		found.len() == expected.len() &&
+		expected.iter().all(|point| {
+			let index = found.iter().position(|other| other == point);
+			index.map(|index| found.swap_remove(index)).is_some()
+		})
+	}
+
+	#[test]
+	fn cube_with_inside() {
+		let cube = cube_at(Vec3::ZERO);
+		let mut cloud = scattered(1000);
+		for (i, &corner) in cube.iter().enumerate() {
+			cloud.insert(i * 100, corner);
+			cloud.push(corner);
+		}
+		let hull = convex_hull(&cloud);
+		assert![same_points(hull.clone(), &cube)];
+		let others: Vec<Vec<Vec3>> = vec![cube_at(Vec3(0.9, 0.2, 0.1)),
+		                                  cube_at(Vec3(1.0, 1.0, 1.0)),
+		                                  cube_at(Vec3(1.01, 0.0, 0.0)),
+		                                  pts![(0.5, 0.5, 0.5)].to_vec(),
+		                                  pts![(0.5, 0.5, 1.5), (0.5, 0.5, 1.01)].to_vec(),
+		                                  pts![(-1.0, 0.5, 0.5), (2.0, 0.5, 0.5)].to_vec(),
+		                                  pts![(1.5, 0.0, 0.0), (0.0, 1.5, 0.0), (0.0, 0.0, 1.5)]
+			                                  .to_vec()];
+		for other in &others {
+			assert_eq![bgjk(&hull, other), bgjk(&cloud, other)];
+			assert_eq![bgjk(other, &hull), bgjk(other, &cloud)];
+		}
+	}
+
+	#[test]
+	fn on_faces_and_edges() {
+		let cube = cube_at(Vec3::ZERO);
+		let mut cloud = cube.clone();
+		for i in 0..3 {
+			let mut center = Vec3::ONE * 0.5;
+			center[i] = 0.0;
+			cloud.push(center);
+			let mut middle = Vec3::ZERO;
+			middle[i] = 0.5;
+			cloud.push(middle);
+			cloud.push(Vec3::ONE - middle);
+		}
+		// Listed first, a point on the top face is the farthest up at the start
+		cloud.insert(0, Vec3(0.5, 0.5, 1.0));
+		assert![same_points(convex_hull(&cloud), &cube)];
+		let octahedron = pts![(1.0, 0.0, 0.0), (-1.0, 0.0, 0.0), (0.0, 1.0, 0.0),
+		                      (0.0, -1.0, 0.0), (0.0, 0.0, 1.0), (0.0, 0.0, -1.0)];
+		let mut cloud = octahedron.to_vec();
+		cloud.push(Vec3::ZERO);
+		cloud.push(Vec3(0.5, 0.5, 0.0));
+		assert![same_points(convex_hull(&cloud), &octahedron)];
+	}
+
+	#[test]
+	fn irregular() {
+		// Every point of a cloud is inside the hull of the corners, and
+		// every corner is a point of the cloud
+		let cloud: Vec<Vec3> = scattered(500)
+			.iter()
+			.map(|&v| (v - Vec3::ONE * 0.5).mul_component(Vec3(3.0, 1.0, 0.5)))
+			.collect();
+		let hull = convex_hull(&cloud);
+		assert![hull.len() > 4 && hull.len() < 500];
+		assert![hull.iter().all(|corner| cloud.contains(corner))];
+		for point in &cloud {
+			let probe = [*point * 1.0001, *point * 1.0001 + Vec3::Y * 0.0001];
+			assert_eq![bgjk(&hull, &probe), bgjk(&cloud, &probe)];
+		}
+		let tetrahedron = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (0.0, 0.0, 1.0)];
+		let mut cloud = tetrahedron.to_vec();
+		cloud.push(Vec3(0.1, 0.1, 0.1));
+		assert![same_points(convex_hull(&cloud), &tetrahedron)];
+	}
+
+	#[test]
+	fn flat() {
+		// A square grid, turned out of the axes
+		let grid: Vec<Vec3> = (0..25)
+			.map(|i| Vec3((i % 5) as f32, (i / 5) as f32, 0.0))
+			.map(|v| Vec3(v.0, v.1 * 0.6, v.1 * 0.8 + 1.0))
+			.collect();
+		let corners = [grid[0], grid[4], grid[20], grid[24]];
+		assert![same_points(convex_hull(&grid), &corners)];
+		let triangle = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0)];
+		let mut cloud = triangle.to_vec();
+		cloud.push(Vec3(0.5, 0.5, 0.0));
+		cloud.push(Vec3(0.2, 0.2, 0.0));
+		cloud.push(Vec3(0.0, 1.0, 0.0));
+		assert![same_points(convex_hull(&cloud), &triangle)];
+	}
+
+	#[test]
+	fn lines_and_points() {
+		let line: Vec<Vec3> = (0..10).map(|i| Vec3(1.0, 2.0, 3.0) * (i % 7) as f32).collect();
+		assert![same_points(convex_hull(&line), &[Vec3::ZERO, Vec3(6.0, 12.0, 18.0)])];
+		let repeated = vec![Vec3(1.0, -2.0, 0.5); 5];
+		assert_eq![convex_hull(&repeated), [Vec3(1.0, -2.0, 0.5)]];
+		assert![convex_hull::<f32>(&[]).is_empty()];
+	}
+
+	#[test]
+	fn convex_sets() {
+		let cube = cube_at(Vec3::ZERO);
+		assert![is_convex_hull(&cube, 1e-4)];
+		assert![is_convex_hull(&cube, 0.0)];
+		let axis = Vec3(1.0, 2.0, 0.5);
+		let turned: Vec<Vec3> = cube.iter().map(|v| v.rotated_about(axis, 0.7)).collect();
+		assert![is_convex_hull(&turned, 1e-4)];
+		let tetrahedron = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (0.0, 0.0, 1.0)];
+		assert![is_convex_hull(&tetrahedron, 1e-4)];
+		// On the surface is not inside
+		let mut surface = cube.clone();
+		surface.push(Vec3(0.5, 0.5, 1.0));
+		surface.push(Vec3(0.5, 0.0, 0.0));
+		surface.push(cube[3]);
+		assert![is_convex_hull(&surface, 1e-4)];
+		assert![is_convex_hull::<f32>(&[], 1e-4)];
+		assert![is_convex_hull(&pts![(1.0, 2.0, 3.0)], 1e-4)];
+	}
+
+	#[test]
+	fn concave_sets() {
+		let mut centered = cube_at(Vec3::ZERO);
+		centered.push(Vec3::ONE * 0.5);
+		assert![!is_convex_hull(&centered, 1e-4)];
+		assert![is_convex_hull(&centered, 0.6)];
+		// A corner pushed in past the plane of its three neighbors makes a
+		// dent, one pushed in less only makes the cube less of a cube
+		let mut dented = cube_at(Vec3::ZERO);
+		dented[7] = Vec3::ONE * 0.6;
+		assert![!is_convex_hull(&dented, 1e-4)];
+		dented[7] = Vec3::ONE * 0.8;
+		assert![is_convex_hull(&dented, 1e-4)];
+		let mut inside_face = cube_at(Vec3::ZERO);
+		inside_face.push(Vec3(0.5, 0.5, 0.99));
+		assert![!is_convex_hull(&inside_face, 0.001)];
+		assert![is_convex_hull(&inside_face, 0.02)];
+	}
+
+	#[test]
+	fn flat_and_straight_sets() {
+		let square = pts![(0.0, 0.0, 1.0), (1.0, 0.0, 1.0), (0.0, 1.0, 1.0), (1.0, 1.0, 1.0)];
+		assert![is_convex_hull(&square, 1e-4)];
+		let mut centered = square.to_vec();
+		centered.push(Vec3(0.5, 0.5, 1.0));
+		assert![!is_convex_hull(&centered, 1e-4)];
+		assert![is_convex_hull(&centered, 0.6)];
+		let mut on_edge = square.to_vec();
+		on_edge.push(Vec3(0.5, 0.0, 1.0));
+		assert![is_convex_hull(&on_edge, 1e-4)];
+		let line = pts![(0.0, 0.0, 0.0), (1.0, 2.0, 3.0), (0.5, 1.0, 1.5)];
+		assert![!is_convex_hull(&line, 1e-4)];
+		assert![is_convex_hull(&line[..2], 1e-4)];
+		assert![is_convex_hull(&pts![(1.0, 1.0, 1.0), (1.0, 1.0, 1.0)], 1e-4)];
+	}
+
+	#[test]
+	fn volumes() {
+		let cube = cube_at(Vec3::ZERO);
+		assert![(hull_volume(&cube) - 1.0).abs() <= 1e-6];
+		let mut cloud = scattered(200);
+		cloud.extend(&cube);
+		assert![(hull_volume(&cloud) - 1.0).abs() <= 1e-6];
+		let scale = Vec3(2.0, 3.0, 0.5);
+		let stretched: Vec<Vec3> = cube.iter().map(|v| v.mul_component(scale)).collect();
+		assert![(hull_volume(&stretched) - 3.0).abs() <= 1e-5];
+		// A sphere of rings of points, the hull inside the sphere but close
+		let mut ball = vec![Vec3::Z, -Vec3::Z];
+		for ring in 1..32 {
+			let (sin_polar, cos_polar) = (ring as f32 / 32.0 * PI).sin_cos();
+			for step in 0..64 {
+				let (sin, cos) = (step as f32 / 32.0 * PI).sin_cos();
+				ball.push(Vec3(sin_polar * cos, sin_polar * sin, cos_polar));
+			}
+		}
+		let volume = hull_volume(&ball);
+		let exact = 4.0 / 3.0 * PI;
+		assert![volume < exact && volume > exact * 0.99];
+	}
+
+	#[test]
+	fn flat_volumes() {
+		let square = pts![(0.0, 0.0, 1.0), (1.0, 0.0, 1.0), (0.0, 1.0, 1.0), (1.0, 1.0, 1.0)];
+		assert_eq![hull_volume(&square), 0.0];
+		assert_eq![hull_volume(&pts![(0.0, 0.0, 0.0), (1.0, 2.0, 3.0), (2.0, 4.0, 6.0)]), 0.0];
+		assert_eq![hull_volume(&pts![(1.0, 2.0, 3.0)]), 0.0];
+		assert_eq![hull_volume::<f32>(&[]), 0.0];
+	}
+
+	#[test]
+	fn far_volumes() {
+		let axis = Vec3(1.0, 2.0, 0.5);
+		let cube: Vec<Vec3> = cube_at(Vec3::ZERO)
+			.iter()
+			.map(|v| v.rotated_about(axis, 0.7))
+			.collect();
+		let near = hull_volume(&cube);
+		assert![(near - 1.0).abs() <= 1e-5];
+		for &by in &[Vec3(1000.5, -2000.25, 3000.125), Vec3(-1e4, 1e4, 5e3)] {
+			let far: Vec<Vec3> = cube.iter().map(|&v| v + by).collect();
+			assert![(hull_volume(&far) - near).abs() <= 1e-2];
+		}
+	}
+}
@@ -0,0 +1,488 @@
+//! Segments, rays and hulls cast against a hull, the ray cast variant of GJK.
+use super::{Float, Vector3, contact_normal, farthest, gjk, penetration};
+use super::distance::Simplex;
+
+// Upper bound on the iterations, the loop normally stops on convergence
+// after a handful of steps but rounding can make it stall
+const MAX_ITERATIONS: usize = 64;
+
+// Where a cast stopped: how far along the ray, the direction it last
+// advanced across, zero when it never had to, and the point of the second
+// hull it stopped against
+struct Stop<T> {
+	t: T,
+	normal: Vector3<T>,
+	point: Vector3<T>,
+}
+
+// Cast `origin + direction * t` for `t` from zero up to `limit`, if any,
+// against the Minkowski difference `hull2 - hull1` given by its support,
+// a pair of points of the first and the second hull. Follows van den
+// Bergen's GJK ray cast: the point on the ray moves forward whenever the
+// closest point of the difference shows it is still apart, by as far as it
+// can without crossing the plane through that support point, and stops on
+// the boundary. Gives `None` when the ray passes the difference by.
+fn cast<T, F>(origin: Vector3<T>,
+              direction: Vector3<T>,
+              support: F,
+              limit: Option<T>)
+              -> Option<Stop<T>>
+	where T: Float,
+	      F: Fn(Vector3<T>) -> (Vector3<T>, Vector3<T>)
+{
+	let mut t = T::ZERO;
+	let mut at = origin;
+	let mut normal = Vector3::ZERO;
+	let (on1, on2) = support(-direction);
+	let mut closest = at + on1 - on2;
+	let mut simplex: Option<Simplex<T>> = None;
+	let stop = |t, normal, simplex: Option<Simplex<T>>| {
+		Stop {
+			t,
+			normal,
+			point: simplex.map_or(on2, |simplex| simplex.witnesses().1),
+		}
+	};
+	for _ in 0..MAX_ITERATIONS {
+		let scale = simplex.map_or(closest.length_squared(), |simplex| simplex.max_length_squared());
+		if closest.length_squared() <= T::EPSILON * T::EPSILON * scale {
+			return Some(stop(t, normal, simplex));
+		}
+		let (on1, on2) = support(closest);
+		let gap = closest.dot(at + on1 - on2);
+		let advance = gap > T::ZERO;
+		if advance {
+			let approach = closest.dot(direction);
+			if approach >= T::ZERO {
+				return None;
+			}
+			t = t - gap / approach;
+			if limit.is_some_and(|limit| t > limit) {
+				return None;
+			}
+			let next = origin + direction * t;
+			if let Some(ref mut simplex) = simplex {
+				simplex.moved(next - at);
+			}
+			at = next;
+			normal = closest;
+		}
+		let mut next = simplex.unwrap_or_else(|| Simplex::new(at + on1, on2));
+		if simplex.is_some() {
+			if !next.contains(at + on1 - on2) {
+				next.push(at + on1, on2);
+			} else if !advance {
+				// Rounding has stalled the cast on the boundary
+				return Some(stop(t, normal, simplex));
+			}
+		}
+		match next.reduce() {
+			Some(point) => closest = point,
+			None => return Some(stop(t, normal, simplex)),
+		}
+		simplex = Some(next);
+	}
+	Some(stop(t, normal, simplex))
+}
+
+// The support of a single hull, to cast a ray against
+fn hull_support<T: Float>(hull: &[Vector3<T>])
+                          -> impl Fn(Vector3<T>) -> (Vector3<T>, Vector3<T>) + '_ {
+	move |direction| (Vector3::ZERO, farthest(hull, direction).unwrap_or(Vector3::ZERO))
+}
+
+/// Whether the segment from `a` to `b` intersects `hull`
+///
+/// Gives the same answer as `bgjk(&[a, b], hull)`, including segments that
+/// only touch the hull, without gathering the endpoints into a hull. Useful
+/// for line of sight checks. See `segment_entry` for where the segment
+/// enters the hull.
+///
+/// ```
+/// use bgjk::{segment_hits_hull, Vec3};
+///
+/// let triangle = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// assert![segment_hits_hull(Vec3::new(0.2, 0.2, -1.0), Vec3::new(0.2, 0.2, 1.0), &triangle)];
+/// assert![!segment_hits_hull(Vec3::new(0.8, 0.8, -1.0), Vec3::new(0.8, 0.8, 1.0), &triangle)];
+/// ```
+pub fn segment_hits_hull<T: Float>(a: Vector3<T>, b: Vector3<T>, hull: &[Vector3<T>]) -> bool {
+	debug_assert![a.is_finite() && b.is_finite() && hull.iter().all(Vector3::is_finite),
+	              "bgjk called with a non-finite point in a hull"];
+	if hull.is_empty() {
+		return false;
+	}
+	gjk(|direction| {
+		let end = if a.dot(direction) >= b.dot(direction) { a } else { b };
+		end - farthest(hull, -direction).unwrap_or(Vector3::ZERO)
+	})
+}
+
+/// Where the segment from `a` to `b` first enters `hull`
+///
+/// Returns `t` such that `a + (b - a) * t` is the first point of the
+/// segment in the hull, between `0.0` and `1.0`. A segment starting inside
+/// the hull enters at `0.0`. Returns `None` when the segment misses the
+/// hull. Segments only grazing the hull within rounding may go either way.
+///
+/// ```
+/// use bgjk::{segment_entry, Vec3};
+///
+/// let triangle = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// let (a, b) = (Vec3::new(0.2, 0.2, -1.0), Vec3::new(0.2, 0.2, 3.0));
+/// let t: f32 = segment_entry(a, b, &triangle).unwrap();
+/// assert![(t - 0.25).abs() < 1e-6];
+/// ```
+pub fn segment_entry<T: Float>(a: Vector3<T>, b: Vector3<T>, hull: &[Vector3<T>]) -> Option<T> {
+	debug_assert![a.is_finite() && b.is_finite() && hull.iter().all(Vector3::is_finite),
+	              "bgjk called with a non-finite point in a hull"];
+	if hull.is_empty() {
+		return None;
+	}
+	cast(a, b - a, hull_support(hull), Some(T::ONE)).map(|stop| stop.t.clamp(T::ZERO, T::ONE))
+}
+
+/// Where a ray hits a hull, see `raycast_hull`
+#[derive(Clone, Copy, Debug)]
+pub struct RayHit<T = f32> {
+	/// How far along the ray the hit is, in lengths of its direction
+	pub t: T,
+	/// The first point of the hull on the ray, `origin + direction * t`
+	pub point: Vector3<T>,
+}
+
+/// Where the ray from `origin` along `direction` first hits `hull`
+///
+/// Runs van den Bergen's GJK ray cast, which walks along the ray by
+/// support points of the hull instead of testing its faces, so the hull
+/// needs no faces, only points. The direction need not be normalized, `t`
+/// counts in lengths of it and is the distance along the ray only for a
+/// unit direction. A ray starting inside the hull hits at `t == 0.0`. Rays
+/// pointing away from the hull, or passing it by, give `None`.
+///
+/// ```
+/// use bgjk::{raycast_hull, Vec3};
+///
+/// let triangle = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// let origin = Vec3::new(0.2, 0.2, 4.0);
+/// let hit = raycast_hull(origin, Vec3::new(0.0, 0.0, -2.0), &triangle).unwrap();
+/// assert![(hit.t - 2.0f32).abs() < 1e-6];
+/// assert![hit.point.approx_eq(Vec3::new(0.2, 0.2, 0.0), 1e-6)];
+/// assert![raycast_hull(origin, Vec3::Z, &triangle).is_none()];
+/// ```
+pub fn raycast_hull<T: Float>(origin: Vector3<T>,
+                              direction: Vector3<T>,
+                              hull: &[Vector3<T>])
+                              -> Option<RayHit<T>> {
+	debug_assert![origin.is_finite() && direction.is_finite() &&
+	              hull.iter().all(Vector3::is_finite),
+	              "bgjk called with a non-finite point in a hull"];
+	if hull.is_empty() {
+		return None;
+	}
+	cast(origin, direction, hull_support(hull), None).map(|stop| {
+		RayHit {
+			t: stop.t,
+			point: origin + direction * stop.t,
+		}
+	})
+}
+
+/// Where a swept hull hits another, see `shape_cast`
+#[derive(Clone, Copy, Debug)]
+pub struct CastHit<T = f32> {
+	/// How far the first hull travels before it touches the second
+	pub distance: T,
+	/// The point of the second hull it touches
+	pub point: Vector3<T>,
+	/// Unit normal at the contact, pointing from the second hull towards
+	/// the first
+	pub normal: Vector3<T>,
+}
+
+/// How far `hull1` can move along `dir` before it hits `hull2`
+///
+/// Sweeps the first hull up to `max_dist` along `dir`, which need not be
+/// normalized, and returns where it first touches the second hull, or
+/// `None` when it stays clear. Runs the ray cast of `raycast_hull` from the
+/// origin against the Minkowski difference `hull2 - hull1`, so the hulls are
+/// never moved nor their points gathered.
+///
+/// The normal is the last direction the cast advanced across, which is
+/// the normal of the face hit up to rounding and a direction in between at
+/// edges and vertices. Hulls that already touch or overlap hit at
+/// `distance == 0.0`, with the normal of `penetration`, or that of
+/// `contact_normal` where they only touch, reversed to point towards the
+/// first hull. Only where neither has a direction is it `-dir`.
+///
+/// ```
+/// use bgjk::{shape_cast, Vec3};
+///
+/// let wall = [Vec3::new(2.0, -1.0, -1.0), Vec3::new(2.0, 1.0, -1.0),
+///             Vec3::new(2.0, -1.0, 1.0), Vec3::new(2.0, 1.0, 1.0)];
+/// let ball = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.5, 0.0, 0.0)];
+/// let hit = shape_cast(&ball, Vec3::X, 10.0f32, &wall).unwrap();
+/// assert![(hit.distance - 1.5).abs() < 1e-5];
+/// assert![hit.normal.approx_eq(-Vec3::X, 1e-5)];
+/// assert![shape_cast(&ball, Vec3::X, 1.0, &wall).is_none()];
+/// ```
+pub fn shape_cast<T: Float>(hull1: &[Vector3<T>],
+                            dir: Vector3<T>,
+                            max_dist: T,
+                            hull2: &[Vector3<T>])
+                            -> Option<CastHit<T>> {
+	debug_assert![dir.is_finite() && hull1.iter().chain(hull2).all(Vector3::is_finite),
+	              "bgjk called with a non-finite point in a hull"];
+	if hull1.is_empty() || hull2.is_empty() {
+		return None;
+	}
+	let direction = dir.normalized();
+	let at = |hull: &[Vector3<T>], v| farthest(hull, v).unwrap_or(Vector3::ZERO);
+	let support = |v: Vector3<T>| (at(hull1, -v), at(hull2, v));
+	let stop = cast(Vector3::ZERO, direction, support, Some(max_dist))?;
+	let normal = match stop.normal.try_normalized() {
+		Some(normal) => normal,
+		None => {
+			let deep = penetration(hull1, hull2).filter(|contact| contact.depth > T::ZERO);
+			-deep.and_then(|contact| contact.normal.try_normalized())
+				.or_else(|| contact_normal(hull1, hull2))
+				.unwrap_or(direction)
+		}
+	};
+	Some(CastHit {
+		distance: stop.t.max(T::ZERO),
+		point: stop.point,
+		normal,
+	})
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+
+	use std::f32;
+	use super::super::{CastHit, Vec3, bgjk, raycast_hull, segment_entry, segment_hits_hull,
+	                   shape_cast};
+	use super::super::test_util::unit_cube;
+	static EPS: f32 = f32::EPSILON;
+
+	fn entry(a: Vec3, b: Vec3, hull: &[Vec3]) -> Option<f32> {
+		assert_eq![segment_hits_hull(a, b, hull), bgjk(&[a, b], hull)];
+		segment_entry(a, b, hull)
+	}
+
+	fn approx(t: Option<f32>, expected: f32) -> bool {
+		t.is_some_and(|t| (t - expected).abs() < 1e-5)
+	}
+
+	#[test]
+	fn piercing() {
+		let cube = unit_cube();
+		assert_eq![segment_hits_hull(Vec3(-1.0, 0.5, 0.5), Vec3(3.0, 0.5, 0.5), &cube), true];
+		assert![approx(entry(Vec3(-1.0, 0.5, 0.5), Vec3(3.0, 0.5, 0.5), &cube), 0.25)];
+		assert![approx(entry(Vec3(3.0, 0.5, 0.5), Vec3(-1.0, 0.5, 0.5), &cube), 0.5)];
+		assert![approx(entry(Vec3(0.3, 0.6, 2.0), Vec3(0.3, 0.4, -2.0), &cube), 0.25)];
+		assert![approx(entry(Vec3(-1.0, -1.0, -1.0), Vec3(2.0, 2.0, 2.0), &cube), 1.0 / 3.0)];
+	}
+
+	#[test]
+	fn ending_inside() {
+		let cube = unit_cube();
+		assert_eq![segment_hits_hull(Vec3(0.5, -2.0, 0.5), Vec3(0.5, 0.5, 0.5), &cube), true];
+		assert![approx(entry(Vec3(0.5, -2.0, 0.5), Vec3(0.5, 0.5, 0.5), &cube), 0.8)];
+		assert![approx(entry(Vec3(0.5, -1.0, 0.5), Vec3(0.5, 0.0, 0.5), &cube), 1.0)];
+	}
+
+	#[test]
+	fn grazing() {
+		let cube = unit_cube();
+		assert_eq![segment_hits_hull(Vec3(-1.0, 0.5, 1.0), Vec3(2.0, 0.5, 1.0), &cube), true];
+		// Ends on the edge of the top face
+		assert_eq![segment_hits_hull(Vec3(-1.0, 0.5, 2.0), Vec3(0.0, 0.5, 1.0), &cube), true];
+		assert_eq![segment_hits_hull(Vec3(-1.0, 0.5, 2.0), Vec3(-EPS, 0.5, 1.0), &cube), false];
+		assert_eq![segment_hits_hull(Vec3(-1.0, 0.5, 1.001), Vec3(2.0, 0.5, 1.001), &cube), false];
+		assert_eq![entry(Vec3(-1.0, 0.5, 1.001), Vec3(2.0, 0.5, 1.001), &cube), None];
+		assert![approx(entry(Vec3(-1.0, 0.5, 1.0), Vec3(2.0, 0.5, 1.0), &cube), 1.0 / 3.0)];
+	}
+
+	#[test]
+	fn inside() {
+		let cube = unit_cube();
+		assert_eq![segment_hits_hull(Vec3(0.2, 0.3, 0.4), Vec3(0.8, 0.7, 0.6), &cube), true];
+		assert_eq![entry(Vec3(0.2, 0.3, 0.4), Vec3(0.8, 0.7, 0.6), &cube), Some(0.0)];
+		assert_eq![entry(Vec3(0.5, 0.5, 0.5), Vec3(0.5, 0.5, 0.5), &cube), Some(0.0)];
+	}
+
+	#[test]
+	fn missing() {
+		let cube = unit_cube();
+		assert_eq![entry(Vec3(2.0, 0.5, 0.5), Vec3(5.0, 0.5, 0.5), &cube), None];
+		assert_eq![entry(Vec3(-1.0, 0.5, 0.5), Vec3(-0.1, 0.5, 0.5), &cube), None];
+		assert_eq![entry(Vec3(2.0, 2.0, 0.5), Vec3(2.0, 2.0, 0.5), &cube), None];
+		assert_eq![entry(Vec3(-1.0, 2.0, 0.5), Vec3(2.0, 1.1, 0.5), &cube), None];
+		assert_eq![entry(Vec3(0.5, 0.5, 0.5), Vec3(0.5, 0.5, 0.5), &[]), None];
+	}
+
+	#[test]
+	fn line_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0)];
+		let (a, b) = (Vec3(0.5, 1.0, 0.0), Vec3(0.5, -1.0, 0.0));
+		assert_eq![segment_hits_hull(a, b, &shape1), true];
+		assert![approx(entry(a, b, &shape1), 0.5)];
+	}
+
+	#[test]
+	fn line_non_overlap() {
+		let shape1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0)];
+		let (a, b) = (Vec3(1.5, 1.0, 0.0), Vec3(1.5, -1.0, 0.0));
+		assert_eq![segment_hits_hull(a, b, &shape1), false];
+		assert_eq![entry(a, b, &shape1), None];
+	}
+
+	#[test]
+	fn agrees_with_bgjk() {
+		let cube = unit_cube();
+		let offsets = [-1.3, -0.4, 0.6, 1.7];
+		let mut ends = Vec::new();
+		for &x in &offsets {
+			for &y in &offsets {
+				for &z in &offsets {
+					ends.push(Vec3(x, y, z));
+				}
+			}
+		}
+		let mut hits = 0;
+		for &a in &ends {
+			for &b in &ends {
+				let t = entry(a, b, &cube);
+				assert_eq![t.is_some(), bgjk(&[a, b], &cube)];
+				if let Some(t) = t {
+					// The entry point is on the boundary unless the segment starts inside
+					let point = a + (b - a) * t;
+					let outside = point.0.max(point.1).max(point.2).max(1.0) - 1.0;
+					let below = point.0.min(point.1).min(point.2).min(0.0);
+					assert![outside < 1e-5 && below > -1e-5];
+					let on_face = |c: f32| c.abs() < 1e-5 || (c - 1.0).abs() < 1e-5;
+					assert![t == 0.0 || on_face(point.0) || on_face(point.1) || on_face(point.2)];
+					hits += 1;
+				}
+			}
+		}
+		assert![hits > 0 && hits < ends.len() * ends.len()];
+	}
+
+	fn hit_at(origin: Vec3, direction: Vec3, hull: &[Vec3], t: f32, point: Vec3) -> bool {
+		raycast_hull(origin, direction, hull)
+			.is_some_and(|hit| (hit.t - t).abs() < 1e-5 && hit.point.approx_eq(point, 1e-5))
+	}
+
+	#[test]
+	fn ray_axis_aligned() {
+		let cube = unit_cube();
+		assert![hit_at(Vec3(-2.0, 0.5, 0.5), Vec3::X, &cube, 2.0, Vec3(0.0, 0.5, 0.5))];
+		assert![hit_at(Vec3(-2.0, 0.5, 0.5), Vec3::X * 4.0, &cube, 0.5, Vec3(0.0, 0.5, 0.5))];
+		assert![hit_at(Vec3(0.25, 3.0, 0.75), -Vec3::Y, &cube, 2.0, Vec3(0.25, 1.0, 0.75))];
+		assert![hit_at(Vec3(0.25, 0.5, -0.5), Vec3::Z * 0.5, &cube, 1.0, Vec3(0.25, 0.5, 0.0))];
+	}
+
+	#[test]
+	fn ray_diagonal() {
+		let cube = unit_cube();
+		assert![hit_at(Vec3(-1.0, -1.0, -1.0), Vec3::ONE, &cube, 1.0, Vec3::ZERO)];
+		assert![hit_at(Vec3(2.0, 2.0, 2.0), -Vec3::ONE, &cube, 1.0, Vec3::ONE)];
+		assert![hit_at(Vec3(-1.0, 0.5, 0.5), Vec3(1.0, 0.2, -0.2), &cube, 1.0, Vec3(0.0, 0.7, 0.3))];
+	}
+
+	#[test]
+	fn ray_grazing() {
+		let cube = unit_cube();
+		// Along the edge between the top and the front face
+		assert![hit_at(Vec3(-1.0, 1.0, 1.0), Vec3::X, &cube, 1.0, Vec3(0.0, 1.0, 1.0))];
+		assert![raycast_hull(Vec3(-1.0, 1.0 + 1e-4, 1.0), Vec3::X, &cube).is_none()];
+		assert![raycast_hull(Vec3(-1.0, 1.0, 1.0 + 1e-4), Vec3::X, &cube).is_none()];
+		// Across the edge
+		assert![hit_at(Vec3(-1.0, 0.0, 0.5), Vec3(1.0, 1.0, 0.0), &cube, 1.0, Vec3(0.0, 1.0, 0.5))];
+		assert![raycast_hull(Vec3(-1.0, 1e-4, 0.5), Vec3(1.0, 1.0, 0.0), &cube).is_none()];
+	}
+
+	#[test]
+	fn ray_inside() {
+		let cube = unit_cube();
+		let origin = Vec3(0.3, 0.6, 0.9);
+		for &direction in &[Vec3::X, -Vec3::ONE, Vec3(0.0, 0.0, 5.0), Vec3::ZERO] {
+			assert![hit_at(origin, direction, &cube, 0.0, origin)];
+		}
+		assert![hit_at(Vec3(1.0, 0.5, 0.5), Vec3::X, &cube, 0.0, Vec3(1.0, 0.5, 0.5))];
+	}
+
+	#[test]
+	fn ray_away() {
+		let cube = unit_cube();
+		assert![raycast_hull(Vec3(-2.0, 0.5, 0.5), -Vec3::X, &cube).is_none()];
+		assert![raycast_hull(Vec3(2.0, 2.0, 2.0), Vec3(1.0, -0.1, 0.0), &cube).is_none()];
+		assert![raycast_hull(Vec3(-2.0, 0.5, 0.5), Vec3::ZERO, &cube).is_none()];
+		assert![raycast_hull(Vec3(-2.0, 0.5, 0.5), Vec3::Y, &cube).is_none()];
+		assert![raycast_hull(Vec3(-2.0, 0.5, 0.5), Vec3::X, &[]).is_none()];
+	}
+
+	fn cuboid(min: Vec3, max: Vec3) -> [Vec3; 8] {
+		pts![(min.0, min.1, min.2),
+		     (max.0, min.1, min.2),
+		     (min.0, max.1, min.2),
+		     (max.0, max.1, min.2),
+		     (min.0, min.1, max.2),
+		     (max.0, min.1, max.2),
+		     (min.0, max.1, max.2),
+		     (max.0, max.1, max.2)]
+	}
+
+	fn stops(hit: Option<CastHit>, distance: f32, normal: Vec3) -> bool {
+		hit.is_some_and(|hit| {
+			(hit.distance - distance).abs() < 1e-5 && hit.normal.approx_eq(normal, 1e-5)
+		})
+	}
+
+	#[test]
+	fn cast_into_wall() {
+		let cube = unit_cube();
+		let wall = cuboid(Vec3(3.0, -5.0, -5.0), Vec3(3.5, 5.0, 5.0));
+		let hit = shape_cast(&cube, Vec3::X, 10.0, &wall);
+		assert![stops(hit, 2.0, -Vec3::X)];
+		assert![hit.is_some_and(|hit| (hit.point.0 - 3.0).abs() < 1e-5)];
+		// The length of the direction does not matter
+		assert![stops(shape_cast(&cube, Vec3::X * 5.0, 10.0, &wall), 2.0, -Vec3::X)];
+		assert![stops(shape_cast(&cube, Vec3::X, 2.0, &wall), 2.0, -Vec3::X)];
+		assert![shape_cast(&cube, Vec3::X, 1.9, &wall).is_none()];
+		// Coming in at an angle travels farther to the same face
+		let slanted = Vec3(1.0, 1.0, 0.0);
+		assert![stops(shape_cast(&cube, slanted, 10.0, &wall), 2.0 * 2f32.sqrt(), -Vec3::X)];
+		// Swapping the hulls and the direction gives the same distance
+		assert![stops(shape_cast(&wall, -Vec3::X, 10.0, &cube), 2.0, Vec3::X)];
+	}
+
+	#[test]
+	fn cast_along_wall() {
+		let cube = unit_cube();
+		let wall = cuboid(Vec3(1.5, -5.0, -5.0), Vec3(2.0, 5.0, 5.0));
+		assert![shape_cast(&cube, Vec3::Y, 100.0, &wall).is_none()];
+		assert![shape_cast(&cube, Vec3(0.0, 1.0, -1.0), 100.0, &wall).is_none()];
+		assert![shape_cast(&cube, -Vec3::X, 100.0, &wall).is_none()];
+		assert![shape_cast(&cube, Vec3::ZERO, 100.0, &wall).is_none()];
+		assert![shape_cast(&cube, Vec3::X, 100.0, &[]).is_none()];
+		assert![shape_cast(&[], Vec3::X, 100.0, &cube).is_none()];
+	}
+
+	#[test]
+	fn cast_starting_in_contact() {
+		let cube = unit_cube();
+		let touching = cuboid(Vec3(1.0, -5.0, -5.0), Vec3(1.5, 5.0, 5.0));
+		assert![stops(shape_cast(&cube, Vec3::X, 10.0, &touching), 0.0, -Vec3::X)];
+		assert![stops(shape_cast(&cube, Vec3::Y, 10.0, &touching), 0.0, -Vec3::X)];
+		let overlapping = cuboid(Vec3(0.8, -5.0, -5.0), Vec3(1.5, 5.0, 5.0));
+		assert![stops(shape_cast(&cube, Vec3::X, 10.0, &overlapping), 0.0, -Vec3::X)];
+		assert![stops(shape_cast(&cube, Vec3::Z, 0.0, &overlapping), 0.0, -Vec3::X)];
+		let inside = cuboid(Vec3::ONE * 0.25, Vec3::ONE * 0.75);
+		let hit = shape_cast(&cube, Vec3::X, 10.0, &inside).unwrap();
+		assert_eq![hit.distance, 0.0];
+		assert![(hit.normal.length() - 1.0).abs() < 1e-5];
+	}
+}
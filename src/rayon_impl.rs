@@ -0,0 +1,123 @@
+//! Batches of hull pairs tested across threads with `rayon`.
+use rayon::prelude::*;
+use super::{Float, HullPair, Vector3, bgjk, bgjk_batch_into};
+
+// Pairs per task. Testing a pair of small hulls takes well under a
+// microsecond, so tasks of single pairs would spend more time on scheduling
+// than on testing.
+const CHUNK: usize = 64;
+
+/// Like `bgjk_batch_into`, spreading the pairs over the `rayon` thread pool
+///
+/// The pairs are split into chunks of a fixed size, each tested in order on
+/// one thread and written to its own part of `out`. Every entry is what
+/// `bgjk` gives for its pair, so the results are the same as those of the
+/// serial functions whatever the number of threads. Panics unless `out` has
+/// one entry per pair.
+///
+/// ```
+/// use bgjk::{bgjk_batch_par, HullPair, Vec3};
+///
+/// let point = [Vec3::new(0.0, 0.0, 0.0)];
+/// let other = [Vec3::new(1.0, 0.0, 0.0)];
+/// let pairs: [HullPair; 2] = [(&point, &point), (&point, &other)];
+/// let mut out = [false; 2];
+/// bgjk_batch_par(&pairs, &mut out);
+/// assert_eq![out, [true, false]];
+/// ```
+pub fn bgjk_batch_par<T: Float + Sync>(pairs: &[HullPair<T>], out: &mut [bool]) {
+	assert_eq![pairs.len(),
+	           out.len(),
+	           "bgjk_batch_par needs one output per pair"];
+	out.par_chunks_mut(CHUNK)
+		.zip(pairs.par_chunks(CHUNK))
+		.for_each(|(out, pairs)| bgjk_batch_into(pairs, out));
+}
+
+/// Like `bgjk_batch_par`, with the pairs given as indices into `hulls`
+///
+/// Each pair `(i, j)` tests `hulls[i]` against `hulls[j]`. The hulls are
+/// only ever borrowed shared, so scenes keeping them in one list need not
+/// build a pair of slices for each test. Panics unless `out` has one entry
+/// per pair, or when an index is out of bounds.
+///
+/// ```
+/// use bgjk::{bgjk_batch_indexed_par, Vec3};
+///
+/// let point = [Vec3::new(0.0, 0.0, 0.0)];
+/// let other = [Vec3::new(1.0, 0.0, 0.0)];
+/// let hulls: [&[Vec3]; 2] = [&point, &other];
+/// let mut out = [false; 3];
+/// bgjk_batch_indexed_par(&hulls, &[(0, 0), (0, 1), (1, 1)], &mut out);
+/// assert_eq![out, [true, false, true]];
+/// ```
+pub fn bgjk_batch_indexed_par<T: Float + Sync>(hulls: &[&[Vector3<T>]],
+                                               pairs: &[(usize, usize)],
+                                               out: &mut [bool]) {
+	assert_eq![pairs.len(),
+	           out.len(),
+	           "bgjk_batch_indexed_par needs one output per pair"];
+	out.par_chunks_mut(CHUNK).zip(pairs.par_chunks(CHUNK)).for_each(|(out, pairs)| {
+		for (hit, &(i, j)) in out.iter_mut().zip(pairs) {
+			*hit = bgjk(hulls[i], hulls[j]);
+		}
+	});
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::super::{HullPair, Vec3, bgjk_batch, bgjk_batch_indexed_par, bgjk_batch_par};
+
+	// Hulls of a few points each, spread over a small region so that about
+	// half of the pairs intersect
+	fn hulls() -> Vec<Vec<Vec3>> {
+		(0..100)
+			.map(|i| {
+				let (sin, cos) = (i as f32 * 0.83).sin_cos();
+				let center = Vec3(cos, sin, (i % 5) as f32 * 0.3) * (i % 7) as f32 * 0.4;
+				(0..1 + i % 6)
+					.map(|j| {
+						let (sin, cos) = (j as f32 * 1.7 + i as f32).sin_cos();
+						center + Vec3(cos, sin, (j % 3) as f32 - 1.0) * 0.6
+					})
+					.collect()
+			})
+			.collect()
+	}
+
+	#[test]
+	fn matches_serial() {
+		let hulls = hulls();
+		let slices: Vec<&[Vec3]> = hulls.iter().map(Vec::as_slice).collect();
+		let indices: Vec<(usize, usize)> = (0..hulls.len())
+			.flat_map(|i| (i..hulls.len()).step_by(2).map(move |j| (i, j)))
+			.collect();
+		assert![indices.len() > 2000];
+		let pairs: Vec<HullPair> = indices.iter().map(|&(i, j)| (slices[i], slices[j])).collect();
+		let serial = bgjk_batch(&pairs);
+		let hits = serial.iter().filter(|&&hit| hit).count();
+		assert![hits > 0 && hits < pairs.len()];
+		let mut parallel = vec![false; pairs.len()];
+		bgjk_batch_par(&pairs, &mut parallel);
+		assert_eq![parallel, serial];
+		let mut indexed = vec![false; pairs.len()];
+		bgjk_batch_indexed_par(&slices, &indices, &mut indexed);
+		assert_eq![indexed, serial];
+	}
+
+	#[test]
+	fn empty() {
+		let pairs: [HullPair; 0] = [];
+		bgjk_batch_par(&pairs, &mut []);
+		let hulls: [&[Vec3]; 0] = [];
+		bgjk_batch_indexed_par(&hulls, &[], &mut []);
+	}
+
+	#[test]
+	#[should_panic(expected = "bgjk_batch_par needs one output per pair")]
+	fn output_length() {
+		let point = [Vec3::ZERO];
+		bgjk_batch_par(&[(&point[..], &point[..])], &mut []);
+	}
+}
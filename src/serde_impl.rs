@@ -0,0 +1,87 @@
+//! Serialization of `Vec3` as a three element sequence `[x, y, z]`.
+use std::fmt;
+use serde::de::{self, Deserialize, Deserializer, IgnoredAny, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+use super::Vec3;
+
+impl Serialize for Vec3 {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut tuple = serializer.serialize_tuple(3)?;
+		tuple.serialize_element(&self.0)?;
+		tuple.serialize_element(&self.1)?;
+		tuple.serialize_element(&self.2)?;
+		tuple.end()
+	}
+}
+
+struct Vec3Visitor;
+
+impl<'de> Visitor<'de> for Vec3Visitor {
+	type Value = Vec3;
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "an array of exactly 3 numbers")
+	}
+
+	fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec3, A::Error> {
+		let x = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+		let y = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+		let z = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(2, &self))?;
+		let mut length = 3;
+		while seq.next_element::<IgnoredAny>()?.is_some() {
+			length += 1;
+		}
+		if length != 3 {
+			return Err(de::Error::invalid_length(length, &self));
+		}
+		Ok(Vec3(x, y, z))
+	}
+}
+
+impl<'de> Deserialize<'de> for Vec3 {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Vec3, D::Error> {
+		deserializer.deserialize_tuple(3, Vec3Visitor)
+	}
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+
+	use serde_json;
+	use super::super::{Vec3, bgjk};
+
+	#[test]
+	fn round_trip() {
+		let v = Vec3(1.0, -2.5, 0.125);
+		let json = serde_json::to_string(&v).unwrap();
+		assert_eq![json, "[1.0,-2.5,0.125]"];
+		assert_eq![serde_json::from_str::<Vec3>(&json).unwrap(), v];
+	}
+
+	#[test]
+	fn wrong_length() {
+		let short = serde_json::from_str::<Vec3>("[1.0, 2.0]").unwrap_err();
+		assert_eq![short.to_string(),
+		           "invalid length 2, expected an array of exactly 3 numbers at line 1 column 10"];
+		let long = serde_json::from_str::<Vec3>("[1.0, 2.0, 3.0, 4.0]").unwrap_err();
+		assert_eq![long.to_string(),
+		           "invalid length 4, expected an array of exactly 3 numbers at line 1 column 20"];
+		assert![serde_json::from_str::<Vec3>("{\"x\": 1.0}").is_err()];
+	}
+
+	#[test]
+	fn hull_round_trip() {
+		let scenario = "[[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [1.0, 1.0, 0.0]], \
+		                [[1.0, 0.0, 0.0], [2.0, 0.0, 0.0], [1.0, 1.0, 0.0], [2.0, 1.0, 0.0]]]";
+		let (hull1, hull2): (Vec<Vec3>, Vec<Vec3>) = serde_json::from_str(scenario).unwrap();
+		assert_eq![hull1.len(), 4];
+		assert_eq![bgjk(&hull1, &hull2), true];
+		let json = serde_json::to_string(&hull2).unwrap();
+		let hull3: Vec<Vec3> = serde_json::from_str(&json).unwrap();
+		assert_eq![hull3, hull2];
+		let away: Vec<Vec3> = hull3.iter().map(|v| v + Vec3(0.5, 0.0, 0.0)).collect();
+		assert_eq![bgjk(&hull1, &away), false];
+	}
+
+}
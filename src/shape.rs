@@ -0,0 +1,1142 @@
+//! Convex shapes given by their support function, hulls or implicit.
+use super::{Float, Vector3, farthest, gjk};
+
+/// A convex shape given by its support function, see `bgjk_support`
+///
+/// Spheres, capsules and other smooth shapes have support functions of
+/// their own, which describe them exactly where a hull of points can only
+/// approximate them. Hulls of points are support maps through `farthest`.
+///
+/// ```
+/// use bgjk::{bgjk_support, SupportMap, Vec3};
+///
+/// // The points within `self.0` of the origin by the sum of the coordinates
+/// struct Octahedron(f32);
+///
+/// impl SupportMap for Octahedron {
+///     fn support(&self, direction: Vec3) -> Vec3 {
+///         let size = direction.abs();
+///         let axis = if size.x() >= size.y() && size.x() >= size.z() {
+///             Vec3::X
+///         } else if size.y() >= size.z() {
+///             Vec3::Y
+///         } else {
+///             Vec3::Z
+///         };
+///         axis.mul_component(direction.signum()) * self.0
+///     }
+/// }
+///
+/// let octahedron = Octahedron(1.0);
+/// assert![bgjk_support(&octahedron, &[Vec3::new(0.3, -0.3, 0.3)])];
+/// assert![!bgjk_support(&octahedron, &[Vec3::new(0.4, -0.4, 0.4)])];
+/// ```
+pub trait SupportMap<T: Float = f32> {
+	/// The point of the shape farthest along `direction`, any one of them
+	/// where several are. `direction` need not be normalized and may be
+	/// zero, in which case any point of the shape will do.
+	fn support(&self, direction: Vector3<T>) -> Vector3<T>;
+
+	/// Whether the shape has no points at all, like a hull of none, which
+	/// `bgjk_support` then finds to intersect nothing. Its support points
+	/// are never looked at.
+	fn is_empty(&self) -> bool {
+		false
+	}
+}
+
+impl<T: Float> SupportMap<T> for [Vector3<T>] {
+	/// The first of the farthest points, any point for no points, which
+	/// make the slice empty instead
+	fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+		farthest(self, direction).unwrap_or(Vector3::ZERO)
+	}
+
+	fn is_empty(&self) -> bool {
+		<[Vector3<T>]>::is_empty(self)
+	}
+}
+
+impl<T: Float> SupportMap<T> for Vec<Vector3<T>> {
+	fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+		farthest(self, direction).unwrap_or(Vector3::ZERO)
+	}
+
+	fn is_empty(&self) -> bool {
+		<[Vector3<T>]>::is_empty(self)
+	}
+}
+
+impl<T: Float, const N: usize> SupportMap<T> for [Vector3<T>; N] {
+	fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+		farthest(self, direction).unwrap_or(Vector3::ZERO)
+	}
+
+	fn is_empty(&self) -> bool {
+		N == 0
+	}
+}
+
+impl<T: Float, S: SupportMap<T> + ?Sized> SupportMap<T> for &S {
+	fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+		(**self).support(direction)
+	}
+
+	fn is_empty(&self) -> bool {
+		(**self).is_empty()
+	}
+}
+
+/// A single point, the smallest support map
+///
+/// ```
+/// use bgjk::{bgjk_support, Point, Vec3};
+///
+/// let triangle = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// assert![bgjk_support(&Point(Vec3::new(0.2, 0.2, 0.0)), &triangle)];
+/// assert![!bgjk_support(&Point(Vec3::new(0.2, 0.2, 0.1)), &triangle)];
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Point<T = f32>(pub Vector3<T>);
+
+impl<T: Float> SupportMap<T> for Point<T> {
+	fn support(&self, _: Vector3<T>) -> Vector3<T> {
+		self.0
+	}
+}
+
+/// The segment between two points, for ray-like queries
+///
+/// ```
+/// use bgjk::{bgjk_support, Segment, Vec3};
+///
+/// let triangle = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// let through = Segment(Vec3::new(0.2, 0.2, -1.0), Vec3::new(0.2, 0.2, 1.0));
+/// let past = Segment(Vec3::new(0.8, 0.8, -1.0), Vec3::new(0.8, 0.8, 1.0));
+/// assert![bgjk_support(&through, &triangle)];
+/// assert![!bgjk_support(&past, &triangle)];
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Segment<T = f32>(pub Vector3<T>, pub Vector3<T>);
+
+impl<T: Float> SupportMap<T> for Segment<T> {
+	/// The end farther along `direction`, the first where they tie
+	fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+		if self.0.dot(direction) >= self.1.dot(direction) { self.0 } else { self.1 }
+	}
+}
+
+/// The solid triangle with three corners, a face of a mesh
+///
+/// Repeated or collinear corners make it the segment or the point they
+/// span.
+///
+/// ```
+/// use bgjk::{bgjk_support, Triangle, Vec3};
+///
+/// let floor = Triangle(Vec3::new(-1.0, 0.0, -1.0), Vec3::new(1.0, 0.0, -1.0), Vec3::Z);
+/// let wall = Triangle(Vec3::new(0.0, -1.0, 0.0), Vec3::new(0.0, 1.0, 0.0), Vec3::Z);
+/// assert![bgjk_support(&floor, &wall)];
+/// let raised = Triangle(Vec3::new(0.0, 0.5, 0.0), Vec3::Y, Vec3::new(0.0, 1.0, 1.0));
+/// assert![!bgjk_support(&floor, &raised)];
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Triangle<T = f32>(pub Vector3<T>, pub Vector3<T>, pub Vector3<T>);
+
+impl<T: Float> SupportMap<T> for Triangle<T> {
+	/// The corner farthest along `direction`, the first where they tie
+	fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+		let Triangle(a, b, c) = *self;
+		let (along_a, along_b, along_c) = (a.dot(direction), b.dot(direction), c.dot(direction));
+		if along_a >= along_b && along_a >= along_c {
+			a
+		} else if along_b >= along_c {
+			b
+		} else {
+			c
+		}
+	}
+}
+
+/// A solid ball around `center`, an exact sphere where a tessellation is not
+///
+/// A radius of zero makes it the single point `center`. The radius must not
+/// be negative.
+///
+/// ```
+/// use bgjk::{bgjk_support, Sphere, Vec3};
+///
+/// let ball = Sphere { center: Vec3::new(0.0, 0.0, 0.0), radius: 1.0 };
+/// let other = Sphere { center: Vec3::new(1.2, 1.2, 0.0), radius: 1.0 };
+/// assert![bgjk_support(&ball, &other)];
+/// assert![!bgjk_support(&ball, &[Vec3::new(1.0, 1.0, 0.0)])];
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Sphere<T = f32> {
+	/// The center of the ball
+	pub center: Vector3<T>,
+	/// The distance from the center to the surface
+	pub radius: T,
+}
+
+impl<T: Float> SupportMap<T> for Sphere<T> {
+	/// The point of the surface along `direction` from the center, the center
+	/// itself for a zero direction, along which every point is as far
+	fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+		debug_assert![self.radius >= T::ZERO,
+		              "Sphere used with a negative radius: {}",
+		              self.radius];
+		direction.try_normalized().map_or(self.center, |unit| self.center + unit * self.radius)
+	}
+}
+
+/// The points within `radius` of the segment from `a` to `b`, a sphere swept
+/// along the segment
+///
+/// Equal ends make it a `Sphere` and a radius of zero the segment itself.
+/// The radius must not be negative.
+///
+/// ```
+/// use bgjk::{bgjk_support, Capsule, Vec3};
+///
+/// let (a, b) = (Vec3::new(0.0, 0.5, 0.0), Vec3::new(0.0, 1.5, 0.0));
+/// let standing = Capsule { a, b, radius: 0.5 };
+/// let floor = [Vec3::new(-1.0, 0.0, -1.0), Vec3::new(1.0, 0.0, -1.0), Vec3::new(0.0, 0.0, 1.0)];
+/// assert![bgjk_support(&standing, &floor)];
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Capsule<T = f32> {
+	/// One end of the segment
+	pub a: Vector3<T>,
+	/// The other end of the segment
+	pub b: Vector3<T>,
+	/// The distance from the segment to the surface
+	pub radius: T,
+}
+
+impl<T: Float> SupportMap<T> for Capsule<T> {
+	/// The end farther along `direction`, `a` where they tie, moved out by the
+	/// radius along it
+	fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+		debug_assert![self.radius >= T::ZERO,
+		              "Capsule used with a negative radius: {}",
+		              self.radius];
+		let end = if self.b.dot(direction) > self.a.dot(direction) { self.b } else { self.a };
+		direction.try_normalized().map_or(end, |unit| end + unit * self.radius)
+	}
+}
+
+/// A box centered on the origin, along the axes, reaching `half_extents` out
+/// along each of them
+///
+/// Has the support of the hull of its eight corners without building them,
+/// the corner farthest along a direction is picked component by component.
+/// Zero half extents flatten it into a rectangle, a segment or a point. The
+/// half extents must not be negative. To place or turn the box, wrap
+/// it in `Transformed`.
+///
+/// ```
+/// use bgjk::{bgjk_support, Cuboid, Vec3};
+///
+/// let crate_ = Cuboid { half_extents: Vec3::new(1.0, 0.5, 0.5) };
+/// assert![bgjk_support(&crate_, &[Vec3::new(1.0, 0.5, -0.5)])];
+/// assert![!bgjk_support(&crate_, &[Vec3::new(1.0, 0.6, 0.0)])];
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Cuboid<T = f32> {
+	/// Half the width, height and depth of the box
+	pub half_extents: Vector3<T>,
+}
+
+impl<T: Float> SupportMap<T> for Cuboid<T> {
+	/// The corner farthest along `direction`, the lower one along axes the
+	/// direction is perpendicular to, as `farthest` picks among the corners
+	/// listed from the lowest
+	fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+		let extents = self.half_extents;
+		debug_assert![extents.0 >= T::ZERO && extents.1 >= T::ZERO && extents.2 >= T::ZERO,
+		              "Cuboid used with a negative half extent: {:?}",
+		              extents];
+		let pick = |toward: T, extent: T| if toward > T::ZERO { extent } else { -extent };
+		Vector3(pick(direction.0, extents.0),
+		        pick(direction.1, extents.1),
+		        pick(direction.2, extents.2))
+	}
+}
+
+/// A solid cylinder centered on the origin, along the Y axis
+///
+/// Reaches `half_height` up and down from the origin to its caps, disks of
+/// `radius`. Zero sizes flatten it into a disk, a segment or a point. The
+/// sizes must not be negative.
+///
+/// ```
+/// use bgjk::{bgjk_support, Cylinder, Vec3};
+///
+/// let barrel = Cylinder { half_height: 1.0, radius: 0.5 };
+/// assert![bgjk_support(&barrel, &[Vec3::new(0.3, 1.0, 0.3)])];
+/// assert![!bgjk_support(&barrel, &[Vec3::new(0.4, 1.0, 0.4)])];
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Cylinder<T = f32> {
+	/// The distance from the origin to each cap
+	pub half_height: T,
+	/// The radius of the caps
+	pub radius: T,
+}
+
+impl<T: Float> SupportMap<T> for Cylinder<T> {
+	/// The point of the rim farthest along `direction`, on the lower cap
+	/// where the direction has no Y component. Directions along the axis have
+	/// a whole cap farthest along them and give its center.
+	fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+		debug_assert![self.half_height >= T::ZERO && self.radius >= T::ZERO,
+		              "Cylinder used with a negative size: {}, {}",
+		              self.half_height,
+		              self.radius];
+		let across = Vector3(direction.0, T::ZERO, direction.2);
+		let rim = across.try_normalized().map_or(Vector3::ZERO, |unit| unit * self.radius);
+		let cap = if direction.1 > T::ZERO { self.half_height } else { -self.half_height };
+		rim + Vector3(T::ZERO, cap, T::ZERO)
+	}
+}
+
+/// A solid cone centered on the origin, with its apex up the Y axis
+///
+/// The apex is `half_height` above the origin and the base, a disk of
+/// `radius`, the same distance below it. The sizes must not be negative.
+///
+/// ```
+/// use bgjk::{bgjk_support, Cone, Vec3};
+///
+/// let cone = Cone { half_height: 1.0, radius: 1.0 };
+/// assert![bgjk_support(&cone, &[Vec3::new(0.0, 1.0, 0.0)])];
+/// assert![bgjk_support(&cone, &[Vec3::new(0.2, 0.5, 0.0)])];
+/// assert![!bgjk_support(&cone, &[Vec3::new(0.3, 0.5, 0.0)])];
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Cone<T = f32> {
+	/// The distance from the origin to the apex and to the base
+	pub half_height: T,
+	/// The radius of the base
+	pub radius: T,
+}
+
+impl<T: Float> SupportMap<T> for Cone<T> {
+	/// The apex, or the point of the rim of the base farthest along
+	/// `direction` when that is farther. The apex wins ties, as do directions
+	/// up the axis, down it the center of the base is farthest.
+	fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+		debug_assert![self.half_height >= T::ZERO && self.radius >= T::ZERO,
+		              "Cone used with a negative size: {}, {}",
+		              self.half_height,
+		              self.radius];
+		let apex = Vector3(T::ZERO, self.half_height, T::ZERO);
+		let across = Vector3(direction.0, T::ZERO, direction.2);
+		// The apex is `half_height * direction.1` along the direction and the
+		// rim `radius * across.length() - half_height * direction.1`. Comparing
+		// those rather than the angle of the direction with the half-angle of
+		// the cone keeps directions nearly along the side, where both are
+		// almost as far, from flipping on a rounded arccosine.
+		let rise = T::from_f32(2.0) * self.half_height * direction.1;
+		if rise >= self.radius * across.length() {
+			return apex;
+		}
+		let rim = across.try_normalized().map_or(Vector3::ZERO, |unit| unit * self.radius);
+		rim - apex
+	}
+}
+
+/// A solid ellipsoid centered on the origin, along the axes, reaching
+/// `radii` out along each of them
+///
+/// A sphere stretched along the axes. Zero radii flatten it into an
+/// elliptic disk, a segment or a point. The radii must not be negative.
+///
+/// ```
+/// use bgjk::{bgjk_support, Ellipsoid, Vec3};
+///
+/// let egg = Ellipsoid { radii: Vec3::new(2.0, 1.0, 0.5) };
+/// assert![bgjk_support(&egg, &[Vec3::new(1.9, 0.0, 0.0)])];
+/// assert![bgjk_support(&egg, &[Vec3::new(1.0, 0.5, 0.25)])];
+/// assert![!bgjk_support(&egg, &[Vec3::new(0.0, 0.0, 0.6)])];
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Ellipsoid<T = f32> {
+	/// The distances from the center to the surface along the axes
+	pub radii: Vector3<T>,
+}
+
+impl<T: Float> SupportMap<T> for Ellipsoid<T> {
+	/// The point of the surface farthest along `direction`, the center where
+	/// the direction only has components along zero radii
+	fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+		let radii = self.radii;
+		debug_assert![radii.0 >= T::ZERO && radii.1 >= T::ZERO && radii.2 >= T::ZERO,
+		              "Ellipsoid used with a negative radius: {:?}",
+		              radii];
+		// The ellipsoid is the unit ball scaled by the radii, its support the
+		// scaled support of the ball along the direction scaled the same way
+		let scaled = radii.mul_component(direction);
+		scaled.try_normalized().map_or(Vector3::ZERO, |unit| radii.mul_component(unit))
+	}
+}
+
+/// A shape placed in the world by a rotation and a translation
+///
+/// The rotation matrix is given as its three columns, the directions the
+/// local X, Y and Z axes of the shape point in the world, like the rotations
+/// of `bgjk_transformed`. A point `p` of the shape is at
+/// `rotation[0] * p.x() + rotation[1] * p.y() + rotation[2] * p.z() + translation`.
+///
+/// Only the support points the search visits are transformed, by turning
+/// each direction into the local frame, so a hull can be moved every frame
+/// without copying its points, as `Transformed { shape: &hull[..], .. }`.
+/// The columns may hold any linear map, a scale or shear included, since the
+/// direction is turned with the transpose. Placed shapes can be placed
+/// again, the outer transform applying after the inner one.
+///
+/// ```
+/// use bgjk::{bgjk_support, Cuboid, Transformed, Vec3};
+///
+/// let plank = Cuboid { half_extents: Vec3::new(2.0, 0.1, 0.1) };
+/// let upright = Transformed {
+///     shape: plank,
+///     rotation: [Vec3::Y, -Vec3::X, Vec3::Z],
+///     translation: Vec3::new(5.0, 0.0, 0.0),
+/// };
+/// assert![bgjk_support(&upright, &[Vec3::new(5.0, 1.5, 0.0)])];
+/// assert![!bgjk_support(&upright, &[Vec3::new(6.5, 0.0, 0.0)])];
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Transformed<S, T = f32> {
+	/// The shape in its local frame
+	pub shape: S,
+	/// The columns of the rotation, the local axes in the world
+	pub rotation: [Vector3<T>; 3],
+	/// Where the local origin of the shape is in the world
+	pub translation: Vector3<T>,
+}
+
+impl<T: Float, S: SupportMap<T>> SupportMap<T> for Transformed<S, T> {
+	/// The support point of the shape along the direction turned into its
+	/// local frame, placed in the world
+	fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+		let columns = &self.rotation;
+		let local = Vector3(columns[0].dot(direction),
+		                    columns[1].dot(direction),
+		                    columns[2].dot(direction));
+		let point = self.shape.support(local);
+		columns[0] * point.0 + columns[1] * point.1 + columns[2] * point.2 + self.translation
+	}
+
+	fn is_empty(&self) -> bool {
+		self.shape.is_empty()
+	}
+}
+
+/// The BGJK algorithm on any two support maps, see `bgjk`
+///
+/// Runs the same search as `bgjk`, which is this function on two hulls of
+/// points, so shapes of any kind can be tested against each other without
+/// tessellating them. Touching shapes intersect, and empty ones, such as a
+/// slice of no points, intersect nothing, like in `bgjk`.
+///
+/// ```
+/// use bgjk::{bgjk, bgjk_support, Vec3};
+///
+/// let triangle = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// let point = [Vec3::new(0.2, 0.2, 0.0)];
+/// assert_eq![bgjk_support(&triangle, &point), bgjk(&triangle, &point)];
+/// ```
+pub fn bgjk_support<T, A, B>(shape1: &A, shape2: &B) -> bool
+	where T: Float,
+	      A: SupportMap<T> + ?Sized,
+	      B: SupportMap<T> + ?Sized
+{
+	if shape1.is_empty() || shape2.is_empty() {
+		return false;
+	}
+	gjk(|direction| shape1.support(direction) - shape2.support(-direction))
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+
+	use std::f32::consts::PI;
+	use super::super::{Capsule, Cone, ConvexHull, Cuboid, Cylinder, Ellipsoid, Point, Segment,
+	                   Sphere, SupportMap, TopoHull, Transformed, Triangle, Vec3, bgjk,
+	                   bgjk_support, gjk_distance};
+	use super::super::test_util::{cube_at, placed, rotation};
+
+	// Rings of latitude with their vertices on the surface of the ball
+	fn tessellated(ball: &Sphere, rings: usize) -> Vec<Vec3> {
+		let (center, radius) = (ball.center, ball.radius);
+		let mut points = vec![center + Vec3::Z * radius, center - Vec3::Z * radius];
+		for ring in 1..rings {
+			let (sin_polar, cos_polar) = (ring as f32 / rings as f32 * PI).sin_cos();
+			for step in 0..2 * rings {
+				let (sin, cos) = (step as f32 / rings as f32 * PI).sin_cos();
+				points.push(center + Vec3(sin_polar * cos, sin_polar * sin, cos_polar) * radius);
+			}
+		}
+		points
+	}
+
+	// A shape moved by an offset
+	struct Moved<S>(S, Vec3);
+
+	impl<S: SupportMap> SupportMap for Moved<S> {
+		fn support(&self, direction: Vec3) -> Vec3 {
+			self.0.support(direction) + self.1
+		}
+	}
+
+	#[test]
+	fn slices_match_bgjk() {
+		let cube = cube_at(Vec3::ZERO);
+		for i in 0..200 {
+			let (sin, cos) = (i as f32 * 0.53).sin_cos();
+			let by = Vec3(cos, sin, (i % 11) as f32 * 0.2 - 1.0) * (i % 6) as f32 * 0.4;
+			let other: Vec<Vec3> = cube.iter().map(|&v| v * 0.6 + by).take(1 + i % 8).collect();
+			let expected = bgjk(&cube, &other);
+			assert_eq![bgjk_support(&cube[..], &other[..]), expected];
+			assert_eq![bgjk_support(&cube, &other), expected];
+		}
+		let point = [Vec3::ONE];
+		assert_eq![bgjk_support(&point, &cube_at(Vec3::ZERO)), true];
+		assert_eq![bgjk_support(&point, &[Vec3::ZERO; 0]), false];
+	}
+
+	#[test]
+	fn ball_against_hulls() {
+		let cube = cube_at(Vec3::ZERO);
+		let touching = Sphere { center: Vec3(2.0, 0.5, 0.5), radius: 1.0 };
+		assert_eq![bgjk_support(&touching, &cube), true];
+		// Reaching just past a corner, which a coarse tessellation misses
+		let corner = Sphere { center: Vec3(1.55, 1.55, 1.55), radius: 0.96 };
+		assert_eq![bgjk_support(&corner, &cube), true];
+		assert_eq![bgjk(&tessellated(&corner, 8), &cube), false];
+		let clear = Sphere { center: Vec3(1.6, 1.6, 1.6), radius: 1.0 };
+		assert_eq![bgjk_support(&clear, &cube), false];
+		assert_eq![bgjk_support(&cube, &clear), false];
+	}
+
+	#[test]
+	fn sphere_tangent_to_cube() {
+		let cube = cube_at(Vec3::ZERO);
+		for &(center, radius) in &[(Vec3(2.0, 0.5, 0.5), 1.0),
+		                           (Vec3(0.5, -0.25, 0.5), 0.25),
+		                           (Vec3(0.3, 0.6, 3.0), 2.0),
+		                           (Vec3(-1.5, 0.0, 1.0), 1.5)] {
+			assert_eq![bgjk_support(&Sphere { center, radius }, &cube), true];
+			assert_eq![bgjk_support(&cube, &Sphere { center, radius }), true];
+			let short = Sphere { center, radius: radius * 0.999 };
+			assert_eq![bgjk_support(&short, &cube), false];
+		}
+	}
+
+	#[test]
+	fn sphere_inside_hull() {
+		let cube = cube_at(Vec3::ZERO);
+		let inner = Sphere { center: Vec3(0.5, 0.5, 0.5), radius: 0.25 };
+		assert_eq![bgjk_support(&inner, &cube), true];
+		assert_eq![bgjk_support(&cube, &inner), true];
+		let point = Sphere { center: Vec3(0.1, 0.9, 0.5), radius: 0.0 };
+		assert_eq![bgjk_support(&point, &cube), true];
+		// A hull inside the sphere intersects it all the same
+		let around = Sphere { center: Vec3(0.5, 0.5, 0.5), radius: 5.0 };
+		assert_eq![bgjk_support(&around, &cube), true];
+	}
+
+	#[test]
+	fn spheres() {
+		let ball = Sphere { center: Vec3::ZERO, radius: 1.0 };
+		for &(center, radius) in &[(Vec3(3.0, 0.0, 0.0), 2.0),
+		                           (Vec3(0.0, -1.5, 0.0), 0.5),
+		                           (Vec3(0.0, 0.0, 4.0), 3.0),
+		                           (Vec3(0.0, 0.0, 0.0), 0.5)] {
+			assert_eq![bgjk_support(&ball, &Sphere { center, radius }), true];
+			let apart = Sphere { center: center * 1.01, radius };
+			assert_eq![bgjk_support(&ball, &apart), center == Vec3::ZERO];
+		}
+		let diagonal = Sphere { center: Vec3(1.0, 1.0, 1.0), radius: 0.8 };
+		assert_eq![bgjk_support(&ball, &diagonal), true];
+		let diagonal = Sphere { center: Vec3(1.0, 1.0, 1.0), radius: 0.7 };
+		assert_eq![bgjk_support(&ball, &diagonal), false];
+	}
+
+	#[test]
+	fn zero_direction() {
+		let ball = Sphere { center: Vec3(1.0, 2.0, 3.0), radius: 2.0 };
+		assert_eq![ball.support(Vec3::ZERO), ball.center];
+		assert_eq![ball.support(Vec3(0.0, 0.0, -5.0)), Vec3(1.0, 2.0, 1.0)];
+	}
+
+	#[test]
+	fn ball_matches_tessellation() {
+		// A fine tessellation lies within the ball and reaches nearly out to
+		// it, so the two agree everywhere but close to tangency
+		let cube = cube_at(Vec3::ZERO);
+		let mut compared = 0;
+		for i in 0..300 {
+			let (sin, cos) = (i as f32 * 0.71).sin_cos();
+			let center = Vec3(0.5, 0.5, 0.5) + Vec3(cos, sin, (i % 13) as f32 * 0.2 - 1.2) * 1.3;
+			let ball = Sphere { center, radius: 0.3 + (i % 5) as f32 * 0.2 };
+			let tessellation = tessellated(&ball, 24);
+			let margin = ball.radius * 0.02;
+			let inner = Sphere { center, radius: ball.radius - margin };
+			let outer = Sphere { center, radius: ball.radius + margin };
+			if bgjk_support(&inner, &cube) == bgjk_support(&outer, &cube) {
+				assert_eq![bgjk_support(&ball, &cube), bgjk(&tessellation, &cube)];
+				compared += 1;
+			}
+		}
+		assert![compared > 250];
+	}
+
+	#[test]
+	fn capsule_on_floor() {
+		let floor = cube_at(Vec3(-0.5, -1.0, -0.5));
+		let standing = Capsule { a: Vec3(0.0, 0.5, 0.0), b: Vec3(0.0, 2.0, 0.0), radius: 0.5 };
+		assert_eq![bgjk_support(&standing, &floor), true];
+		let raised = Capsule { a: standing.a + Vec3::Y * 0.01, b: standing.b, ..standing };
+		assert_eq![bgjk_support(&raised, &floor), false];
+		let upside_down = Capsule { a: standing.b, b: standing.a, ..standing };
+		assert_eq![bgjk_support(&floor, &upside_down), true];
+	}
+
+	#[test]
+	fn capsules() {
+		// Segments with the distance between them, parallel, perpendicular
+		// and skewed in every axis
+		let parallel = pts![(0.0, 0.0, 0.0), (0.0, 2.0, 0.0), (3.0, 0.0, 0.0), (3.0, 2.0, 0.0)];
+		let across = pts![(-1.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, -1.0, 2.0), (0.0, 1.0, 2.0)];
+		let skew = pts![(0.0, 0.0, 0.0), (1.0, 2.0, 0.5), (2.0, 0.0, 1.0), (-1.0, 1.5, 2.0)];
+		let segments = [parallel, across, skew];
+		let distances = [3.0, 2.0, gjk_distance(&skew[..2], &skew[2..])];
+		assert![distances[2] > 0.5 && distances[2] < 1.5];
+		for (&[a1, b1, a2, b2], &distance) in segments.iter().zip(&distances) {
+			for &(share, expected) in &[(1.001, true), (0.999, false)] {
+				let radius = distance * share * 0.5;
+				let first = Capsule { a: a1, b: b1, radius };
+				let second = Capsule { a: a2, b: b2, radius };
+				assert_eq![bgjk_support(&first, &second), expected];
+				assert_eq![bgjk_support(&second, &first), expected];
+			}
+		}
+	}
+
+	#[test]
+	fn capsule_through_ring() {
+		// Small cubes in a ring around the vertical axis, a capsule standing
+		// in it reaches the ones on its side
+		let ring: Vec<Vec<Vec3>> = (0..8)
+			.map(|i| {
+				let (sin, cos) = (i as f32 * PI / 4.0).sin_cos();
+				let center = Vec3(cos, 0.0, sin) * 2.0 - Vec3::ONE * 0.25;
+				cube_at(Vec3::ZERO).iter().map(|&v| v * 0.5 + center).collect()
+			})
+			.collect();
+		let centered = Capsule { a: Vec3(0.0, -2.0, 0.0), b: Vec3(0.0, 2.0, 0.0), radius: 1.3 };
+		let by = Vec3::X * 0.8;
+		let aside = Capsule { a: centered.a + by, b: centered.b + by, ..centered };
+		let hits = |capsule: &Capsule| -> Vec<usize> {
+			(0..ring.len()).filter(|&i| bgjk_support(capsule, &ring[i])).collect()
+		};
+		assert![hits(&centered).is_empty()];
+		let beside = hits(&aside);
+		assert_eq![beside, [0, 1, 7]];
+		for (i, cube) in ring.iter().enumerate() {
+			let reach = gjk_distance(&[aside.a, aside.b], cube);
+			assert_eq![reach <= aside.radius, beside.contains(&i)];
+		}
+	}
+
+	#[test]
+	fn degenerate_capsules() {
+		let cube = cube_at(Vec3::ZERO);
+		for i in 0..100 {
+			let (sin, cos) = (i as f32 * 0.47).sin_cos();
+			let center = Vec3(0.5, 0.5, 0.5) + Vec3(cos, sin, (i % 9) as f32 * 0.3 - 1.2) * 1.4;
+			let radius = 0.2 + (i % 4) as f32 * 0.25;
+			let ball = Capsule { a: center, b: center, radius };
+			assert_eq![bgjk_support(&ball, &cube), bgjk_support(&Sphere { center, radius }, &cube)];
+			let segment = Capsule { a: center, b: center * 0.25, radius: 0.0 };
+			assert_eq![bgjk_support(&segment, &cube), bgjk(&[segment.a, segment.b], &cube)];
+		}
+	}
+
+	// The corners of the cuboid, listed from the lowest like `cube_at`
+	fn corners(cuboid: &Cuboid) -> Vec<Vec3> {
+		let unit = cube_at(Vec3::ONE * -0.5);
+		unit.iter().map(|&v| (v * 2.0).mul_component(cuboid.half_extents)).collect()
+	}
+
+	#[test]
+	fn cuboid_matches_corners() {
+		let extents = pts![(0.5, 0.5, 0.5), (1.0, 0.5, 0.25), (0.5, 0.5, 0.0), (0.0, 2.0, 0.0)];
+		for &half_extents in extents.iter().chain(&[Vec3::ZERO]) {
+			let cuboid = Cuboid { half_extents };
+			let hull = corners(&cuboid);
+			for i in 0..27 {
+				let direction = Vec3((i % 3) as f32, (i / 3 % 3) as f32, (i / 9) as f32) - Vec3::ONE;
+				assert_eq![cuboid.support(direction), hull.support(direction)];
+			}
+			// Where the unit cubes of the crate tests go, about the center
+			let placements = pts![(0.5, 0.5, 0.5),
+			                      (0.9, -0.3, 0.2),
+			                      (-0.2, 0.7, -0.6),
+			                      (0.0, 0.0, 0.0),
+			                      (1.5, 0.0, 0.0),
+			                      (1.1, 1.1, 1.1),
+			                      (-0.3, 0.4, -2.0),
+			                      (0.2, -1.01, 0.5),
+			                      (1.0, 0.0, 0.0),
+			                      (1.0, 0.3, -0.2),
+			                      (1.0, 1.0, 0.0)];
+			for &by in &placements {
+				let other = cube_at(by - Vec3::ONE * 0.5);
+				assert_eq![bgjk_support(&cuboid, &other), bgjk(&hull, &other)];
+				assert_eq![bgjk_support(&other, &cuboid), bgjk(&other, &hull)];
+				let point = [by * 0.6];
+				assert_eq![bgjk_support(&cuboid, &point), bgjk(&hull, &point)];
+			}
+		}
+	}
+
+	#[test]
+	fn flat_cuboids() {
+		// A square, hit by segments through it and missed by ones beside it
+		let square = Cuboid { half_extents: Vec3(0.5, 0.5, 0.0) };
+		assert_eq![bgjk_support(&square, &pts![(0.2, -0.3, -1.0), (0.2, -0.3, 1.0)]), true];
+		assert_eq![bgjk_support(&square, &pts![(0.2, 0.6, -1.0), (0.2, 0.6, 1.0)]), false];
+		assert_eq![bgjk_support(&square, &pts![(0.5, 0.5, 0.0)]), true];
+		assert_eq![bgjk_support(&square, &pts![(0.5, 0.5, 0.01)]), false];
+		// A segment, crossed by another and touched end to end
+		let segment = Cuboid { half_extents: Vec3(0.0, 2.0, 0.0) };
+		assert_eq![bgjk_support(&segment, &pts![(-1.0, 1.0, 0.0), (1.0, 1.0, 0.0)]), true];
+		assert_eq![bgjk_support(&segment, &pts![(-1.0, 1.0, 0.1), (1.0, 1.0, 0.1)]), false];
+		assert_eq![bgjk_support(&segment, &pts![(0.0, 2.0, 0.0), (0.0, 3.0, 0.0)]), true];
+		assert_eq![bgjk_support(&segment, &pts![(0.0, 2.1, 0.0), (0.0, 3.0, 0.0)]), false];
+		let point = Cuboid { half_extents: Vec3::ZERO };
+		let resting = Sphere { center: Vec3(0.0, 0.0, 1.0), radius: 1.0 };
+		assert_eq![bgjk_support(&point, &resting), true];
+	}
+
+	// The prism with `sides` corners on each cap, inscribed in the cylinder
+	fn prism(cylinder: &Cylinder, sides: usize) -> Vec<Vec3> {
+		(0..sides)
+			.flat_map(|i| {
+				let (sin, cos) = (i as f32 / sides as f32 * 2.0 * PI).sin_cos();
+				let rim = Vec3(cos, 0.0, sin) * cylinder.radius;
+				vec![rim + Vec3::Y * cylinder.half_height, rim - Vec3::Y * cylinder.half_height]
+			})
+			.collect()
+	}
+
+	#[test]
+	fn cylinder_on_box() {
+		let barrel = Cylinder { half_height: 1.0, radius: 0.5 };
+		let floor = cube_at(Vec3(-0.25, -2.0, -0.75));
+		assert_eq![bgjk_support(&barrel, &floor), true];
+		let lowered = cube_at(Vec3(-0.25, -2.01, -0.75));
+		assert_eq![bgjk_support(&barrel, &lowered), false];
+		// Under the rim only by its corner
+		let corner = cube_at(Vec3(0.35, -2.0, 0.35));
+		assert_eq![bgjk_support(&barrel, &corner), true];
+		let corner = cube_at(Vec3(0.36, -2.0, 0.36));
+		assert_eq![bgjk_support(&barrel, &corner), false];
+	}
+
+	#[test]
+	fn cylinder_on_its_side() {
+		// A wheel lying against a wall is, about its own axis, a wall along
+		// the axis touching its curved side
+		let wheel = Cylinder { half_height: 0.25, radius: 1.0 };
+		for &(x, expected) in &[(1.0, true), (1.01, false)] {
+			let wall = pts![(x, -2.0, -2.0), (x, 2.0, -2.0), (x, -2.0, 2.0), (x, 2.0, 2.0)];
+			assert_eq![bgjk_support(&wheel, &wall), expected];
+			let turned: Vec<Vec3> = wall.iter().map(|&v| Vec3(v.2, v.1, -v.0)).collect();
+			assert_eq![bgjk_support(&wheel, &turned), expected];
+		}
+	}
+
+	#[test]
+	fn cylinders_rim_to_rim() {
+		let barrel = Cylinder { half_height: 1.0, radius: 0.5 };
+		// Stacked with the rims meeting in a point, and not quite
+		for &across in &[Vec3::X, -Vec3::Z] {
+			for &(apart, expected) in &[(1.0, true), (1.01, false)] {
+				let stacked = Moved(barrel, across * apart + Vec3::Y * 2.0);
+				assert_eq![bgjk_support(&barrel, &stacked), expected];
+				assert_eq![bgjk_support(&stacked, &barrel), expected];
+			}
+		}
+		let (above, beside) = (Moved(barrel, Vec3::Y * 2.0), Moved(barrel, Vec3::Z));
+		assert_eq![bgjk_support(&barrel, &above), true];
+		assert_eq![bgjk_support(&barrel, &beside), true];
+		let higher = Moved(barrel, Vec3::Y * 2.01);
+		assert_eq![bgjk_support(&barrel, &higher), false];
+	}
+
+	#[test]
+	fn cylinder_along_axis() {
+		let barrel = Cylinder { half_height: 1.0, radius: 0.5 };
+		assert_eq![barrel.support(Vec3::Y), Vec3(0.0, 1.0, 0.0)];
+		assert_eq![barrel.support(-Vec3::Y * 3.0), Vec3(0.0, -1.0, 0.0)];
+		assert_eq![barrel.support(Vec3::X), Vec3(0.5, -1.0, 0.0)];
+		assert_eq![barrel.support(Vec3::ZERO), Vec3(0.0, -1.0, 0.0)];
+	}
+
+	#[test]
+	fn cylinder_matches_prism() {
+		let cube = cube_at(Vec3::ZERO);
+		let mut compared = 0;
+		for i in 0..300 {
+			let (sin, cos) = (i as f32 * 0.71).sin_cos();
+			let by = Vec3(0.5, 0.5, 0.5) + Vec3(cos, (i % 13) as f32 * 0.25 - 1.5, sin) * 1.3;
+			let (half_height, radius) = (0.2 + (i % 3) as f32 * 0.3, 0.3 + (i % 5) as f32 * 0.2);
+			let cylinder = Cylinder { half_height, radius };
+			let moved = |cylinder: Cylinder| Moved(cylinder, by);
+			let thin = Cylinder { radius: cylinder.radius * 0.98, ..cylinder };
+			let wide = Cylinder { radius: cylinder.radius * 1.02, ..cylinder };
+			if bgjk_support(&moved(thin), &cube) == bgjk_support(&moved(wide), &cube) {
+				let hull: Vec<Vec3> = prism(&cylinder, 64).iter().map(|&v| v + by).collect();
+				assert_eq![bgjk_support(&moved(cylinder), &cube), bgjk(&hull, &cube)];
+				compared += 1;
+			}
+		}
+		assert![compared > 250];
+	}
+
+	fn pyramid(cone: &Cone, sides: usize) -> Vec<Vec3> {
+		(0..sides)
+			.map(|i| {
+				let (sin, cos) = (i as f32 / sides as f32 * 2.0 * PI).sin_cos();
+				Vec3(cos, 0.0, sin) * cone.radius - Vec3::Y * cone.half_height
+			})
+			.chain(Some(Vec3::Y * cone.half_height))
+			.collect()
+	}
+
+	#[test]
+	fn cone_support() {
+		let cone = Cone { half_height: 1.0, radius: 0.5 };
+		assert_eq![cone.support(Vec3::Y), Vec3::Y];
+		assert_eq![cone.support(-Vec3::Y), -Vec3::Y];
+		assert_eq![cone.support(Vec3::ZERO), Vec3::Y];
+		assert_eq![cone.support(Vec3::X), Vec3(0.5, -1.0, 0.0)];
+		assert_eq![cone.support(Vec3(0.0, 0.1, -1.0)), Vec3(0.0, -1.0, -0.5)];
+		// Either side of the normal of the side, along which the apex and the
+		// rim are equally far
+		assert_eq![cone.support(Vec3(2.0, 0.5, 0.0)), Vec3::Y];
+		assert_eq![cone.support(Vec3(2.0, 0.5001, 0.0)), Vec3::Y];
+		assert_eq![cone.support(Vec3(2.0, 0.4999, 0.0)), Vec3(0.5, -1.0, 0.0)];
+		assert_eq![cone.support(Vec3(2e-15, 0.4999e-15, 0.0)), Vec3(0.5, -1.0, 0.0)];
+	}
+
+	#[test]
+	fn cone_under_ceiling() {
+		let cone = Cone { half_height: 1.0, radius: 0.5 };
+		let ceiling = cube_at(Vec3(-0.5, 1.0, -0.5));
+		assert_eq![bgjk_support(&cone, &ceiling), true];
+		let raised = cube_at(Vec3(-0.5, 1.01, -0.5));
+		assert_eq![bgjk_support(&cone, &raised), false];
+		// Only the apex reaches up there
+		let aside = cube_at(Vec3(0.05, 0.9, -0.5));
+		assert_eq![bgjk_support(&cone, &aside), false];
+	}
+
+	#[test]
+	fn cone_on_floor() {
+		let cone = Cone { half_height: 1.0, radius: 0.5 };
+		let floor = cube_at(Vec3(-0.25, -2.0, -0.75));
+		assert_eq![bgjk_support(&cone, &floor), true];
+		let lowered = cube_at(Vec3(-0.25, -2.01, -0.75));
+		assert_eq![bgjk_support(&cone, &lowered), false];
+		// Under the rim only by its corner
+		let corner = cube_at(Vec3(0.35, -2.0, 0.35));
+		assert_eq![bgjk_support(&cone, &corner), true];
+		let corner = cube_at(Vec3(0.36, -2.0, 0.36));
+		assert_eq![bgjk_support(&cone, &corner), false];
+	}
+
+	#[test]
+	fn cone_on_its_side() {
+		// A box lying along the side of the cone, which runs from the rim at
+		// (0.5, -1) to the apex at (0, 1), its face extending past both
+		let cone = Cone { half_height: 1.0, radius: 0.5 };
+		let slab = |by: f32| -> Vec<Vec3> {
+			let face = pts![(1.0, -3.0, -1.0), (1.0, -3.0, 1.0),
+			                (-0.5, 3.0, -1.0), (-0.5, 3.0, 1.0)];
+			let outward = Vec3(2.0, 0.5, 0.0);
+			face.iter()
+				.flat_map(|&v| vec![v + Vec3::X * by, v + Vec3::X * by + outward])
+				.collect()
+		};
+		assert_eq![bgjk_support(&cone, &slab(0.0)), true];
+		assert_eq![bgjk_support(&cone, &slab(-0.01)), true];
+		assert_eq![bgjk_support(&cone, &slab(0.01)), false];
+	}
+
+	#[test]
+	fn cone_matches_pyramid() {
+		let cube = cube_at(Vec3::ZERO);
+		let mut compared = 0;
+		for i in 0..300 {
+			let (sin, cos) = (i as f32 * 0.71).sin_cos();
+			let by = Vec3(0.5, 0.5, 0.5) + Vec3(cos, (i % 13) as f32 * 0.25 - 1.5, sin) * 1.3;
+			let (half_height, radius) = (0.2 + (i % 3) as f32 * 0.3, 0.3 + (i % 5) as f32 * 0.2);
+			let cone = Cone { half_height, radius };
+			let moved = |cone: Cone| Moved(cone, by);
+			let thin = Cone { radius: cone.radius * 0.98, ..cone };
+			let wide = Cone { radius: cone.radius * 1.02, ..cone };
+			if bgjk_support(&moved(thin), &cube) == bgjk_support(&moved(wide), &cube) {
+				let hull: Vec<Vec3> = pyramid(&cone, 64).iter().map(|&v| v + by).collect();
+				assert_eq![bgjk_support(&moved(cone), &cube), bgjk(&hull, &cube)];
+				compared += 1;
+			}
+		}
+		assert![compared > 250];
+	}
+
+	#[test]
+	fn ellipsoid_tangent_along_axes() {
+		let egg = Ellipsoid { radii: Vec3(1.5, 0.5, 1.0) };
+		for &(touching, apart) in &[(Vec3(1.5, -0.5, -0.5), Vec3(1.51, -0.5, -0.5)),
+		                            (Vec3(-2.5, -0.5, -0.5), Vec3(-2.51, -0.5, -0.5)),
+		                            (Vec3(-0.5, 0.5, -0.5), Vec3(-0.5, 0.51, -0.5)),
+		                            (Vec3(-0.5, -1.5, -0.5), Vec3(-0.5, -1.51, -0.5)),
+		                            (Vec3(-0.5, -0.5, 1.0), Vec3(-0.5, -0.5, 1.01)),
+		                            (Vec3(-0.5, -0.5, -2.0), Vec3(-0.5, -0.5, -2.01))] {
+			assert_eq![bgjk_support(&egg, &cube_at(touching)), true];
+			assert_eq![bgjk_support(&cube_at(touching), &egg), true];
+			assert_eq![bgjk_support(&egg, &cube_at(apart)), false];
+		}
+	}
+
+	#[test]
+	fn pancake_through_gap() {
+		// Two slabs leaving a gap 0.4 high around the origin
+		let slab = Cuboid { half_extents: Vec3(2.0, 0.5, 2.0) };
+		let (below, above) = (Moved(slab, Vec3(0.0, -0.7, 0.0)), Moved(slab, Vec3(0.0, 0.7, 0.0)));
+		let pancake = Ellipsoid { radii: Vec3(1.0, 0.15, 1.0) };
+		assert_eq![bgjk_support(&pancake, &below), false];
+		assert_eq![bgjk_support(&pancake, &above), false];
+		let ball = Sphere { center: Vec3::ZERO, radius: 1.0 };
+		assert_eq![bgjk_support(&ball, &below), true];
+		assert_eq![bgjk_support(&ball, &above), true];
+		let upright = Ellipsoid { radii: Vec3(1.0, 1.0, 0.15) };
+		assert_eq![bgjk_support(&upright, &below), true];
+		let thick = Ellipsoid { radii: Vec3(1.0, 0.2, 1.0) };
+		assert_eq![bgjk_support(&thick, &above), true];
+	}
+
+	#[test]
+	fn degenerate_ellipsoids() {
+		let disk = Ellipsoid { radii: Vec3(1.0, 0.0, 2.0) };
+		assert_eq![disk.support(Vec3::Y), Vec3::ZERO];
+		assert_eq![disk.support(Vec3(1.0, 1.0, 0.0)), Vec3::X];
+		assert_eq![disk.support(Vec3(0.0, -1.0, -1.0)), Vec3(0.0, 0.0, -2.0)];
+		assert_eq![bgjk_support(&disk, &cube_at(Vec3(-0.5, -1.0, -0.5))), true];
+		assert_eq![bgjk_support(&disk, &cube_at(Vec3(-0.5, 0.01, -0.5))), false];
+		assert_eq![bgjk_support(&disk, &cube_at(Vec3(1.0, -0.5, -0.5))), true];
+		assert_eq![bgjk_support(&disk, &cube_at(Vec3(1.01, -0.5, -0.5))), false];
+		let segment = Ellipsoid { radii: Vec3(0.0, 0.0, 2.0) };
+		assert_eq![segment.support(Vec3(1.0, 1.0, 1.0)), Vec3(0.0, 0.0, 2.0)];
+		assert_eq![segment.support(Vec3::X), Vec3::ZERO];
+		assert_eq![bgjk_support(&segment, &cube_at(Vec3(-0.5, -0.5, 2.0))), true];
+		assert_eq![bgjk_support(&segment, &cube_at(Vec3(0.01, -0.5, -0.5))), false];
+		let point = Ellipsoid { radii: Vec3::ZERO };
+		assert_eq![point.support(Vec3(1.0, -2.0, 3.0)), Vec3::ZERO];
+		assert_eq![bgjk_support(&point, &cube_at(Vec3::ZERO)), true];
+	}
+
+	#[test]
+	fn ellipsoid_matches_sampled() {
+		let cube = cube_at(Vec3::ZERO);
+		let ball = tessellated(&Sphere { center: Vec3::ZERO, radius: 1.0 }, 24);
+		let mut compared = 0;
+		for i in 0..300 {
+			let (sin, cos) = (i as f32 * 0.71).sin_cos();
+			let by = Vec3(0.5, 0.5, 0.5) + Vec3(cos, (i % 13) as f32 * 0.25 - 1.5, sin) * 1.3;
+			let radii = Vec3(0.2 + (i % 3) as f32 * 0.4,
+			                 0.3 + (i % 5) as f32 * 0.2,
+			                 0.1 + (i % 4) as f32 * 0.3);
+			let hits = |radii: Vec3| bgjk_support(&Moved(Ellipsoid { radii }, by), &cube);
+			if hits(radii * 0.98) == hits(radii * 1.02) {
+				let hull: Vec<Vec3> = ball.iter().map(|&v| v.mul_component(radii) + by).collect();
+				assert_eq![hits(radii), bgjk(&hull, &cube)];
+				compared += 1;
+			}
+		}
+		assert![compared > 250];
+	}
+
+	fn rotations() -> [[Vec3; 3]; 4] {
+		[rotation(Vec3::Z, 0.0),
+		 rotation(Vec3::Z, PI / 4.0),
+		 rotation(Vec3(1.0, 1.0, 0.0), PI / 3.0),
+		 rotation(Vec3(0.3, -0.8, 0.5), 2.0)]
+	}
+
+	#[test]
+	fn transformed_matches_placed() {
+		let cube = cube_at(Vec3::ZERO);
+		let positions = [Vec3(0.0, 0.0, 0.0),
+		                 Vec3(0.9, 0.2, 0.1),
+		                 Vec3(1.2, 0.0, 0.0),
+		                 Vec3(-0.5, 1.3, 0.4),
+		                 Vec3(0.0, 0.0, 3.0)];
+		let mut hits = 0;
+		for &rot1 in &rotations() {
+			for &rot2 in &rotations() {
+				for &pos in &positions {
+					let pos1 = pos * -0.5;
+					let expected = bgjk(&placed(&cube, &rot1, pos1), &placed(&cube, &rot2, pos));
+					let moved = Transformed { shape: &cube[..], rotation: rot1, translation: pos1 };
+					let other = Transformed { shape: &cube, rotation: rot2, translation: pos };
+					assert_eq![bgjk_support(&moved, &other), expected];
+					assert_eq![bgjk_support(&moved, &placed(&cube, &rot2, pos)), expected];
+					hits += expected as usize;
+				}
+			}
+		}
+		assert![hits > 0 && hits < 4 * 4 * positions.len()];
+	}
+
+	#[test]
+	fn transformed_shapes() {
+		let cube = cube_at(Vec3::ZERO);
+		let crate_ = Cuboid { half_extents: Vec3(0.6, 0.3, 0.2) };
+		for (i, &rotation) in rotations().iter().enumerate() {
+			for j in 0..20 {
+				let (sin, cos) = ((i * 20 + j) as f32 * 0.43).sin_cos();
+				let translation = Vec3(0.5, 0.5, 0.5) + Vec3(cos, sin, (j % 5) as f32 * 0.4 - 0.8);
+				let turned = Transformed { shape: crate_, rotation, translation };
+				let hull = placed(&corners(&crate_), &rotation, translation);
+				assert_eq![bgjk_support(&turned, &cube), bgjk(&hull, &cube)];
+				// Turning a ball changes nothing
+				let ball = Sphere { center: Vec3::ZERO, radius: 0.4 };
+				let turned = Transformed { shape: ball, rotation, translation };
+				let moved = Sphere { center: translation, radius: 0.4 };
+				assert_eq![bgjk_support(&turned, &cube), bgjk_support(&moved, &cube)];
+			}
+		}
+		// A plank stood up reaches the top of a tower only when upright
+		let plank = Cuboid { half_extents: Vec3(2.0, 0.1, 0.1) };
+		let rotation = rotation(Vec3::Z, PI / 2.0);
+		let upright = Transformed { shape: plank, rotation, translation: Vec3::ZERO };
+		assert_eq![bgjk_support(&upright, &cube_at(Vec3(-0.5, 1.9, -0.5))), true];
+		assert_eq![bgjk_support(&plank, &cube_at(Vec3(-0.5, 1.9, -0.5))), false];
+	}
+
+	#[test]
+	fn nested_transforms() {
+		let cube = cube_at(Vec3(-0.5, -0.5, -0.5));
+		let target = cube_at(Vec3::ZERO);
+		for &inner in &rotations() {
+			for &outer in &rotations() {
+				let (shift, by) = (Vec3(0.3, -0.2, 0.1), Vec3(0.8, 1.1, 0.4));
+				let nested = Transformed {
+					shape: Transformed { shape: &cube[..], rotation: inner, translation: shift },
+					rotation: outer,
+					translation: by,
+				};
+				let twice = placed(&placed(&cube, &inner, shift), &outer, by);
+				// The composed rotation turns each inner column by the outer one
+				let composed = placed(&inner, &outer, Vec3::ZERO);
+				let combined = placed(&[shift], &outer, by)[0];
+				let once = Transformed {
+					shape: &cube[..],
+					rotation: [composed[0], composed[1], composed[2]],
+					translation: combined,
+				};
+				for i in 0..50 {
+					let (sin, cos) = (i as f32 * 0.9).sin_cos();
+					let direction = Vec3(cos, sin, (i % 7) as f32 * 0.5 - 1.5);
+					let support = nested.support(direction);
+					assert![support.approx_eq(once.support(direction), 1e-5)];
+					let farthest = twice.support(direction).dot(direction);
+					assert![(support.dot(direction) - farthest).abs() <= 1e-5];
+				}
+				assert_eq![bgjk_support(&nested, &target), bgjk(&twice, &target)];
+			}
+		}
+	}
+
+	#[test]
+	fn points_and_segments() {
+		let line = Segment(Vec3(0.0, 0.0, 0.0), Vec3(1.0, 0.0, 0.0));
+		let cases = [(line, Segment(Vec3(0.5, 1.0, 0.0), Vec3(0.5, -1.0, 0.0)), true),
+		             (line, Segment(Vec3(1.5, 1.0, 0.0), Vec3(1.5, -1.0, 0.0)), false),
+		             (line, Segment(Vec3(1.0, 1.0, 0.0), Vec3(1.0, -1.0, 0.0)), true),
+		             (line, Segment(Vec3(2.0, 0.0, 0.0), Vec3(1.0, 0.0, 0.0)), true)];
+		for &(a, b, expected) in &cases {
+			assert_eq![bgjk_support(&a, &b), expected];
+			assert_eq![bgjk_support(&b, &a), expected];
+			assert_eq![bgjk(&[a.0, a.1], &[b.0, b.1]), expected];
+		}
+		let small = Segment(Vec3(0.0, 0.0, 0.0), Vec3(0.01, 0.0, 0.0));
+		assert_eq![bgjk_support(&small, &Point(Vec3(0.005, 0.0, 0.1))), false];
+		assert_eq![bgjk_support(&small, &Point(Vec3(0.005, 0.0, 0.0))), true];
+		assert_eq![bgjk_support(&line, &Point(Vec3(0.5, 0.0, 0.1))), false];
+		let point = Point(Vec3(0.5, 1.0, 0.0));
+		assert_eq![bgjk_support(&point, &point), true];
+		assert_eq![bgjk_support(&point, &Point(Vec3(1.0, 1.0, 0.0))), false];
+		assert_eq![bgjk_support(&Point(Vec3(0.5, 0.5, 0.5)), &cube_at(Vec3::ZERO)), true];
+		assert_eq![Segment(Vec3::X, Vec3::Y).support(Vec3(1.0, 1.0, 0.0)), Vec3::X];
+	}
+
+	#[test]
+	fn triangles() {
+		let floor = Triangle(Vec3(0.0, 0.0, 0.0), Vec3(2.0, 0.0, 0.0), Vec3(0.0, 0.0, 2.0));
+		let hull = [floor.0, floor.1, floor.2];
+		let flipped = |t: Triangle| Triangle(t.2, t.0, t.1);
+		let check = |other: Triangle, expected: bool| {
+			assert_eq![bgjk_support(&floor, &other), expected];
+			assert_eq![bgjk_support(&other, &floor), expected];
+			assert_eq![bgjk_support(&flipped(floor), &flipped(other)), expected];
+			assert_eq![bgjk(&hull, &[other.0, other.1, other.2]), expected];
+		};
+		// Coplanar: overlapping, sharing an edge, sharing a corner and apart
+		check(Triangle(Vec3(0.5, 0.0, 0.5), Vec3(3.0, 0.0, 0.5), Vec3(0.5, 0.0, 3.0)), true);
+		check(Triangle(Vec3(2.0, 0.0, 0.0), Vec3(0.0, 0.0, 2.0), Vec3(2.0, 0.0, 2.0)), true);
+		check(Triangle(Vec3(2.0, 0.0, 0.0), Vec3(3.0, 0.0, 0.0), Vec3(3.0, 0.0, 1.0)), true);
+		check(Triangle(Vec3(1.5, 0.0, 1.5), Vec3(3.0, 0.0, 1.5), Vec3(1.5, 0.0, 3.0)), false);
+		// Crossing: piercing the inside, through an edge, and stopping short
+		check(Triangle(Vec3(0.5, -1.0, 0.5), Vec3(0.5, 1.0, 0.5), Vec3(0.5, 1.0, -1.0)), true);
+		check(Triangle(Vec3(1.0, -1.0, 1.0), Vec3(1.0, 1.0, 1.0), Vec3(3.0, 0.0, 3.0)), true);
+		check(Triangle(Vec3(0.5, 0.01, 0.5), Vec3(0.5, 1.0, 0.5), Vec3(0.5, 1.0, -1.0)), false);
+		// Separated: parallel above, and turned beside it
+		check(Triangle(Vec3(0.0, 0.5, 0.0), Vec3(2.0, 0.5, 0.0), Vec3(0.0, 0.5, 2.0)), false);
+		check(Triangle(Vec3(1.1, -1.0, 1.1), Vec3(1.1, 1.0, 1.1), Vec3(3.0, 0.0, 3.0)), false);
+		// Degenerate triangles are their segment or point
+		check(Triangle(Vec3(0.5, -1.0, 0.5), Vec3(0.5, 1.0, 0.5), Vec3(0.5, 1.0, 0.5)), true);
+		check(Triangle(Vec3(1.0, 0.0, 1.0), Vec3(1.0, 0.0, 1.0), Vec3(1.0, 0.0, 1.0)), true);
+	}
+
+	#[test]
+	fn empty_hulls() {
+		// Around the origin, where the support of an empty hull is
+		let cube = cube_at(Vec3(-0.5, -0.5, -0.5));
+		let sphere = Sphere { center: Vec3::ZERO, radius: 1.0 };
+		let empty: Vec<Vec3> = Vec::new();
+		let none: [Vec3; 0] = [];
+		assert_eq![bgjk_support(&&empty[..], &cube), false];
+		assert_eq![bgjk_support(&cube, &empty), false];
+		assert_eq![bgjk_support(&none, &sphere), false];
+		assert_eq![bgjk_support(&sphere, &&none), false];
+		assert_eq![bgjk_support(&ConvexHull::from_points_unchecked(Vec::new()), &sphere), false];
+		assert_eq![bgjk_support(&TopoHull::new(&none), &sphere), false];
+		let moved = Transformed {
+			shape: none,
+			rotation: rotation(Vec3::Z, 1.0),
+			translation: Vec3::X,
+		};
+		assert_eq![bgjk_support(&moved, &sphere), false];
+		assert![moved.is_empty() && !sphere.is_empty()];
+	}
+}
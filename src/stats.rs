@@ -0,0 +1,108 @@
+//! BGJK with counts of the work it did, for tuning and bug reports.
+use super::{Float, GjkConfig, Vector3, either_empty, gjk_search, hull_support};
+
+/// How much work `bgjk` did on a pair of hulls, see `bgjk_stats`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GjkStats<T = f32> {
+	/// Passes of the main loop, each adding a support point to the segment
+	/// the search starts with. Hulls decided by the first two support points
+	/// take none.
+	pub iterations: u32,
+	/// Support points computed, each a pass over both hulls
+	pub support_calls: u32,
+	/// The length of the last search direction. It is not normalized, so
+	/// only its order of magnitude means anything, a tiny one being a sign
+	/// of a nearly degenerate simplex.
+	pub direction_length: T,
+}
+
+/// The BGJK algorithm, also counting the work it did, see `bgjk`
+///
+/// Runs the very search `bgjk` runs, so the answer is always the same. A
+/// pair that takes many more iterations than its neighbors is worth a bug
+/// report, well-behaved hulls rarely take more than a handful. A hull with
+/// no points takes no work at all.
+///
+/// ```
+/// use bgjk::{bgjk, bgjk_stats, Vec3};
+///
+/// let hull1 = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// let hull2: Vec<Vec3> = hull1.iter().map(|v| *v + Vec3::new(0.2, 0.2, 0.0)).collect();
+/// let (hit, stats) = bgjk_stats(&hull1, &hull2);
+/// assert_eq![hit, bgjk(&hull1, &hull2)];
+/// assert![stats.iterations < 20];
+/// ```
+pub fn bgjk_stats<T: Float>(hull1: &[Vector3<T>], hull2: &[Vector3<T>]) -> (bool, GjkStats<T>) {
+	debug_assert![hull1.iter().chain(hull2).all(Vector3::is_finite),
+	              "bgjk called with a non-finite point in a hull"];
+	if either_empty(hull1, hull2) {
+		return (false, GjkStats { iterations: 0, support_calls: 0, direction_length: T::ZERO });
+	}
+	let config = GjkConfig::default();
+	let search = gjk_search(|direction| hull_support(hull1, hull2, direction), &config);
+	let stats = GjkStats {
+		iterations: search.support_calls.saturating_sub(2),
+		support_calls: search.support_calls,
+		direction_length: search.direction.length(),
+	};
+	(search.hit(&config), stats)
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::super::{GjkConfig, Vec3, bgjk, bgjk_config, bgjk_stats};
+	use super::super::test_util::cube_at;
+
+	#[test]
+	fn decided_early() {
+		// The second support point already falls short of the origin
+		let point = pts![(0.0, 0.0, 0.0)];
+		let away = pts![(3.0, 3.0, 3.0)];
+		let (hit, stats) = bgjk_stats(&point, &away);
+		assert![!hit];
+		assert_eq![stats.iterations, 0];
+		assert_eq![stats.support_calls, 2];
+		assert![stats.direction_length > 0.0];
+	}
+
+	#[test]
+	fn within_bounds() {
+		let cube: Vec<Vec3> = (0..8)
+			.map(|i| Vec3((i & 1) as f32, (i >> 1 & 1) as f32, (i >> 2 & 1) as f32))
+			.collect();
+		let mut hits = 0;
+		for i in 0..200 {
+			let (sin, cos) = (i as f32 * 0.37).sin_cos();
+			let by = Vec3(cos, sin, (i % 9) as f32 * 0.25 - 1.0) * (i % 5) as f32 * 0.6;
+			let other: Vec<Vec3> = cube.iter().map(|&v| v * 0.7 + by).collect();
+			let (hit, stats) = bgjk_stats(&cube, &other);
+			assert_eq![hit, bgjk(&cube, &other)];
+			assert_eq![hit, bgjk_config(&cube, &other, &GjkConfig::default())];
+			assert![stats.iterations < 20];
+			assert![stats.direction_length.is_finite()];
+			if hit {
+				hits += 1;
+			}
+		}
+		assert![hits > 0 && hits < 200];
+	}
+
+	#[test]
+	fn coincident_points() {
+		// A Minkowski difference of only the origin, every simplex flat
+		let hull = pts![(0.0, 0.0, 0.0)];
+		let (hit, stats) = bgjk_stats(&hull, &hull);
+		assert![hit];
+		assert![stats.iterations < 20];
+		assert_eq![stats.support_calls, stats.iterations + 2];
+	}
+
+	#[test]
+	fn empty_hull() {
+		let cube = cube_at(Vec3(-0.5, -0.5, -0.5));
+		let (hit, stats) = bgjk_stats(&[], &cube);
+		assert![!hit && stats.iterations == 0];
+		assert![!bgjk_stats(&cube, &[]).0];
+	}
+}
@@ -0,0 +1,150 @@
+//! Hulls and helpers shared by the tests of several modules.
+use std::f32::consts::PI;
+use super::Vec3;
+
+const EPS: f32 = f32::EPSILON;
+
+// An array of `Vec3` from `(x, y, z)` tuples
+macro_rules! pts {
+	($($e:expr),*) => {
+		[$(
+			$crate::Vec3($e.0, $e.1, $e.2)
+		),*]
+	};
+}
+
+// The cube from the origin to (1, 1, 1), corners in binary order of z, y, x
+pub fn unit_cube() -> [Vec3; 8] {
+	pts![(0.0, 0.0, 0.0),
+	     (1.0, 0.0, 0.0),
+	     (0.0, 1.0, 0.0),
+	     (1.0, 1.0, 0.0),
+	     (0.0, 0.0, 1.0),
+	     (1.0, 0.0, 1.0),
+	     (0.0, 1.0, 1.0),
+	     (1.0, 1.0, 1.0)]
+}
+
+// Every point of `hull` moved by `by`
+pub fn shifted(hull: &[Vec3], by: Vec3) -> Vec<Vec3> {
+	hull.iter().map(|v| *v + by).collect()
+}
+
+// The unit cube moved by `by`
+pub fn cube_at(by: Vec3) -> Vec<Vec3> {
+	shifted(&unit_cube(), by)
+}
+
+// The images of the axes under a rotation of `angle` about `axis`
+pub fn rotation(axis: Vec3, angle: f32) -> [Vec3; 3] {
+	[Vec3::X.rotated_about(axis, angle),
+	 Vec3::Y.rotated_about(axis, angle),
+	 Vec3::Z.rotated_about(axis, angle)]
+}
+
+// Every point of `hull` rotated into the axes of `rotation`, then moved by `position`
+pub fn placed(hull: &[Vec3], rotation: &[Vec3; 3], position: Vec3) -> Vec<Vec3> {
+	hull.iter()
+		.map(|v| rotation[0] * v.0 + rotation[1] * v.1 + rotation[2] * v.2 + position)
+		.collect()
+}
+
+// A circle of radius one in the plane z = `by.2`, centred on `by`
+fn ring(by: Vec3) -> Vec<Vec3> {
+	let units = 100;
+	(0..units)
+		.map(|i| {
+			let radian = i as f32 / units as f32 * 2.0 * PI;
+			Vec3(radian.cos(), radian.sin(), 0.0) + by
+		})
+		.collect()
+}
+
+// The hull pairs of the `bgjk` tests in lib.rs by name, and whether each
+// pair intersects, for the checks that every query agrees on them
+pub fn cases() -> Vec<(&'static str, Vec<Vec3>, Vec<Vec3>, bool)> {
+	let square = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+	let line = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0)];
+	let slanted = pts![(0.0, 0.0, 0.0),
+	                   (1.0, 0.0, 0.0),
+	                   (0.0, 1.0, 0.0),
+	                   (1.0, 1.0, 0.0),
+	                   (1.0, 0.0, 1.0),
+	                   (2.0, 0.0, 1.0),
+	                   (1.0, 1.0, 1.0),
+	                   (2.0, 1.0, 1.0)];
+	let tetrahedron =
+		pts![(-1.0, -1.0, -1.0), (1.0, -1.0, -1.0), (0.0, 1.0, -1.0), (0.0, 0.0, 1.0)];
+	let around = pts![(-1.0, -1.0, 0.0), (1.0, -1.0, 0.0), (-1.0, 1.0, 0.0), (1.0, 1.0, 0.0)];
+	vec![("square1",
+	      square.to_vec(),
+	      shifted(&square, Vec3(-3.0, 0.0, 0.0)),
+	      false),
+	     ("exact_overlap", square.to_vec(), square.to_vec(), true),
+	     ("line_overlap", line.to_vec(), pts![(0.5, 1.0, 0.0), (0.5, -1.0, 0.0)].to_vec(), true),
+	     ("line_non_overlap",
+	      line.to_vec(),
+	      pts![(1.5, 1.0, 0.0), (1.5, -1.0, 0.0)].to_vec(),
+	      false),
+	     ("small_line_point_overlap",
+	      pts![(0.0, 0.0, 0.0), (0.01, 0.0, 0.0)].to_vec(),
+	      pts![(0.005, 0.0, 0.1)].to_vec(),
+	      false),
+	     ("line_point_non_overlap", line.to_vec(), pts![(0.5, 0.0, 0.1)].to_vec(), false),
+	     ("point_overlap", pts![(0.5, 1.0, 0.0)].to_vec(), pts![(0.5, 1.0, 0.0)].to_vec(), true),
+	     ("point_no_overlap",
+	      pts![(0.5, 1.0, 0.0)].to_vec(),
+	      pts![(1.0, 1.0, 0.0)].to_vec(),
+	      false),
+	     ("empty_no_overlap", vec![], pts![(1.0, 1.0, 1.0)].to_vec(), false),
+	     ("empty_around_origin", vec![], tetrahedron.to_vec(), false),
+	     ("empty_around_origin", around.to_vec(), vec![], false),
+	     ("empty_empty", vec![], vec![], false),
+	     ("side_by_side_squares", square.to_vec(), shifted(&square, Vec3::X), true),
+	     ("side_by_side_squares_offset",
+	      square.to_vec(),
+	      pts![(1.0 + EPS, 0.0, 0.0),
+	           (2.0, 0.0, 0.0),
+	           (1.0 + EPS, 1.0, 0.0),
+	           (2.0, 1.0, 0.0)]
+	      .to_vec(),
+	      false),
+	     ("single_point_square_overlap",
+	      square.to_vec(),
+	      shifted(&square, Vec3(1.0, 1.0, 0.0)),
+	      true),
+	     ("single_point_shape_overlap", unit_cube().to_vec(), cube_at(Vec3::ONE), true),
+	     ("single_point_shape_non_overlap",
+	      unit_cube().to_vec(),
+	      pts![(1.0, 1.0, 1.0 + EPS),
+	           (2.0, 1.0, 1.0 + EPS),
+	           (1.0, 2.0, 1.0 + EPS),
+	           (2.0, 2.0, 1.0 + EPS),
+	           (1.0, 1.0, 2.0),
+	           (2.0, 1.0, 2.0),
+	           (1.0, 2.0, 2.0),
+	           (2.0, 2.0, 2.0)]
+	      .to_vec(),
+	      false),
+	     ("single_line_shape_overlap", unit_cube().to_vec(), cube_at(Vec3(1.0, 1.0, 0.0)), true),
+	     ("shape_projective_non_overlap",
+	      slanted.to_vec(),
+	      shifted(&slanted, Vec3(1.1, 1.0, 0.0)),
+	      false),
+	     ("shape_projective_overlap",
+	      slanted.to_vec(),
+	      pts![(1.1, 1.0, 0.0),
+	           (2.1, 1.0, 0.0),
+	           (1.1, 2.0, 0.0),
+	           (2.1, 2.0, 0.0),
+	           (2.0, 1.0, 1.0),
+	           (3.1, 1.0, 1.0),
+	           (2.0, 2.0, 1.0),
+	           (3.1, 2.0, 1.0)]
+	      .to_vec(),
+	      true),
+	     ("shape_non_overlap", ring(Vec3::ZERO), ring(Vec3(0.0, 0.0, EPS)), false),
+	     ("shape_overlap", ring(Vec3::ZERO), ring(Vec3::ZERO), true),
+	     ("shape_section", ring(Vec3::ZERO), ring(Vec3(0.5, 0.0, 0.0)), true),
+	     ("shape_away", ring(Vec3::ZERO), ring(Vec3(2.0 + 2.0 * EPS, 0.0, 0.0)), false)]
+}
@@ -0,0 +1,157 @@
+//! Time of impact of hulls moving along straight lines.
+use super::{Float, Vector3, closest_points, either_empty};
+
+// Upper bound on the advancement steps, each step covers the whole gap
+// along the closest direction so few are needed unless the hulls graze
+const MAX_ITERATIONS: usize = 64;
+
+/// The earliest time at which two translating hulls touch
+///
+/// Over the frame `hull1` moves by `disp1` and `hull2` by `disp2`, both at
+/// constant speed. Returns the fraction `t` of the frame, between `0.0` and
+/// `1.0`, at which the hulls first touch, so fast hulls cannot tunnel
+/// through thin ones between frames. Hulls that already touch or overlap
+/// give `Some(0.0)`, hulls that stay apart throughout give `None`, as do
+/// empty hulls, which never touch anything.
+///
+/// Runs conservative advancement: the closest points of the hulls, see
+/// `closest_points`, give a gap and a direction across it, and the hulls
+/// are advanced by the time their relative motion takes to close that gap
+/// along that direction, which never overshoots the contact. It stops once
+/// the gap is below a hundred thousandth of the relative motion.
+///
+/// ```
+/// use bgjk::{toi_linear, Vec3};
+///
+/// let wall = [Vec3::new(0.5, -1.0, -1.0), Vec3::new(0.5, 1.0, -1.0),
+///             Vec3::new(0.5, -1.0, 1.0), Vec3::new(0.5, 1.0, 1.0)];
+/// let bullet = [Vec3::new(0.0, 0.0, 0.0)];
+/// let t: f32 = toi_linear(&bullet, Vec3::new(2.0, 0.0, 0.0), &wall, Vec3::ZERO).unwrap();
+/// assert![(t - 0.25).abs() < 1e-4];
+/// assert![toi_linear(&bullet, Vec3::new(-2.0, 0.0, 0.0), &wall, Vec3::ZERO).is_none()];
+/// ```
+pub fn toi_linear<T: Float>(hull1: &[Vector3<T>],
+                            disp1: Vector3<T>,
+                            hull2: &[Vector3<T>],
+                            disp2: Vector3<T>)
+                            -> Option<T> {
+	debug_assert![disp1.is_finite() && disp2.is_finite(),
+	              "toi_linear called with a non-finite displacement"];
+	if either_empty(hull1, hull2) {
+		return None;
+	}
+	// Only the motion of the second hull relative to the first matters
+	let motion = disp2 - disp1;
+	let tolerance = motion.length() * T::from_f32(1e-5);
+	let mut t = T::ZERO;
+	let mut moved = hull2.to_vec();
+	for _ in 0..MAX_ITERATIONS {
+		let (on1, on2) = match closest_points(hull1, &moved) {
+			Some(pair) => pair,
+			None => return Some(t),
+		};
+		let across = on2 - on1;
+		let gap = across.length();
+		if gap <= tolerance {
+			return Some(t);
+		}
+		let approach = -motion.dot(across) / gap;
+		if approach <= T::ZERO {
+			return None;
+		}
+		t = t + gap / approach;
+		if t > T::ONE {
+			return None;
+		}
+		for (point, original) in moved.iter_mut().zip(hull2) {
+			*point = *original + motion * t;
+		}
+	}
+	Some(t)
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::super::{Vec3, bgjk, toi_linear};
+	use super::super::test_util::{cube_at, shifted};
+
+	fn cuboid(min: Vec3, max: Vec3) -> [Vec3; 8] {
+		pts![(min.0, min.1, min.2),
+		     (max.0, min.1, min.2),
+		     (min.0, max.1, min.2),
+		     (max.0, max.1, min.2),
+		     (min.0, min.1, max.2),
+		     (max.0, min.1, max.2),
+		     (min.0, max.1, max.2),
+		     (max.0, max.1, max.2)]
+	}
+
+	fn near(t: Option<f32>, expected: f32) -> bool {
+		t.is_some_and(|t| (t - expected).abs() < 1e-4)
+	}
+
+	#[test]
+	fn tunneling() {
+		let wall = cuboid(Vec3(0.5, -1.0, -1.0), Vec3(0.55, 1.0, 1.0));
+		let bullet = cuboid(Vec3(-0.1, -0.05, -0.05), Vec3(0.0, 0.05, 0.05));
+		let disp = Vec3(1.0, 0.0, 0.0);
+		assert![!bgjk(&bullet, &wall)];
+		assert![!bgjk(&shifted(&bullet, disp), &wall)];
+		assert![near(toi_linear(&bullet, disp, &wall, Vec3::ZERO), 0.5)];
+		assert![near(toi_linear(&wall, Vec3::ZERO, &bullet, disp), 0.5)];
+		// Only the relative motion matters
+		assert![near(toi_linear(&bullet, disp * 0.5, &wall, disp * -0.5), 0.5)];
+		assert![near(toi_linear(&bullet, disp * 2.0, &wall, disp), 0.5)];
+	}
+
+	#[test]
+	fn diagonal() {
+		let cube = cuboid(Vec3::ZERO, Vec3::ONE);
+		let other = shifted(&cube, Vec3(3.0, 2.0, 0.5));
+		// The faces at x meet at t = 0.5 while the y ranges already overlap
+		assert![near(toi_linear(&cube, Vec3(4.0, 2.0, 0.0), &other, Vec3::ZERO), 0.5)];
+	}
+
+	#[test]
+	fn glancing() {
+		let wall = cuboid(Vec3(0.5, -1.0, -1.0), Vec3(0.55, 1.0, 1.0));
+		let bullet = cuboid(Vec3(-0.1, 1.01, -0.05), Vec3(0.0, 1.11, 0.05));
+		assert_eq![toi_linear(&bullet, Vec3(1.0, 0.0, 0.0), &wall, Vec3::ZERO), None];
+		assert_eq![toi_linear(&bullet, Vec3(1.0, -0.001, 0.0), &wall, Vec3::ZERO), None];
+		// Too slow to get there
+		let below = shifted(&bullet, Vec3(0.0, -1.0, 0.0));
+		assert_eq![toi_linear(&below, Vec3(0.4, 0.0, 0.0), &wall, Vec3::ZERO), None];
+		// Moving away
+		assert_eq![toi_linear(&below, Vec3(-1.0, 0.0, 0.0), &wall, Vec3::ZERO), None];
+		assert_eq![toi_linear(&below, Vec3::ZERO, &wall, Vec3::ZERO), None];
+	}
+
+	#[test]
+	fn grazing() {
+		let wall = cuboid(Vec3(0.5, -1.0, -1.0), Vec3(0.55, 1.0, 1.0));
+		// Slides along the plane of the top of the wall, the edges meet halfway
+		let bullet = cuboid(Vec3(-0.1, 1.0, -0.05), Vec3(0.0, 1.1, 0.05));
+		assert![near(toi_linear(&bullet, Vec3(1.0, 0.0, 0.0), &wall, Vec3::ZERO), 0.5)];
+		// Corner to corner
+		let corner = cuboid(Vec3(-0.1, 1.0, 1.0), Vec3(0.0, 1.1, 1.1));
+		assert![near(toi_linear(&corner, Vec3(1.0, 0.0, 0.0), &wall, Vec3::ZERO), 0.5)];
+	}
+
+	#[test]
+	fn overlapping() {
+		let cube = cuboid(Vec3::ZERO, Vec3::ONE);
+		let other = shifted(&cube, Vec3(0.5, 0.5, 0.5));
+		assert_eq![toi_linear(&cube, Vec3::X, &other, Vec3::ZERO), Some(0.0)];
+		assert_eq![toi_linear(&cube, Vec3::ZERO, &other, Vec3::ZERO), Some(0.0)];
+		let touching = shifted(&cube, Vec3::X);
+		assert_eq![toi_linear(&cube, -Vec3::X, &touching, Vec3::ZERO), Some(0.0)];
+	}
+
+	#[test]
+	fn empty_hull() {
+		let cube = cube_at(Vec3(-0.5, -0.5, -0.5));
+		assert_eq![toi_linear(&[], Vec3::X, &cube, Vec3::ZERO), None];
+		assert_eq![toi_linear(&cube, Vec3::ZERO, &[], -Vec3::X), None];
+	}
+}
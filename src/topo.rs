@@ -0,0 +1,240 @@
+//! Hulls that find their support by climbing from corner to corner.
+use std::sync::atomic::{AtomicUsize, Ordering};
+use super::{Float, SupportMap, Vector3, convex_hull, farthest};
+use super::quickhull::triangles;
+
+/// The corners of a convex hull with the edges between them, for support
+/// searches that walk the hull instead of scanning it
+///
+/// The faces of the hull are found once when built, with the quickhull
+/// algorithm of `convex_hull`. Each support search then starts at the
+/// corner the previous one ended at and steps to whichever neighbor is
+/// farthest along the direction until none is farther, which on a convex
+/// hull is the farthest corner of all. That visits about the square root of
+/// the corners for a direction out of the blue, and only a few for a
+/// direction near the previous one, as `bgjk` asks for between iterations
+/// and frames, where a slice is scanned whole every time.
+///
+/// Hulls that do not span space, being flat, straight or a single point,
+/// have no faces to walk, and are scanned like slices. The corner to start
+/// from is kept atomically, so a hull can be shared between threads, which
+/// only costs the searches of each their head start.
+///
+/// ```
+/// use bgjk::{bgjk_support, SupportMap, TopoHull, Vec3};
+///
+/// let sphere: Vec<Vec3> = (0..500)
+///     .map(|i| {
+///         let (z, turn) = (1.0 - (i as f32 + 0.5) / 250.0, i as f32 * 2.4);
+///         let ring = (1.0 - z * z).sqrt();
+///         Vec3::new(ring * turn.cos(), ring * turn.sin(), z)
+///     })
+///     .collect();
+/// let hull = TopoHull::new(&sphere);
+/// assert_eq![hull.vertices().len(), 500];
+/// assert![hull.support(Vec3::Z).approx_eq(sphere[0], 1e-6)];
+/// assert![!bgjk_support(&hull, &[Vec3::new(0.0, 0.0, 1.1)])];
+/// ```
+#[derive(Debug)]
+pub struct TopoHull<T = f32> {
+	vertices: Vec<Vector3<T>>,
+	neighbors: Vec<Vec<usize>>,
+	start: AtomicUsize,
+}
+
+impl<T: Float> TopoHull<T> {
+	/// The hull of `points`, keeping only its corners
+	///
+	/// Takes the time of `convex_hull`. The points must be finite.
+	pub fn new(points: &[Vector3<T>]) -> TopoHull<T> {
+		debug_assert![points.iter().all(Vector3::is_finite),
+		              "TopoHull::new called with a non-finite point"];
+		let faces = match triangles(points) {
+			Some(faces) => faces,
+			None => {
+				return TopoHull {
+					vertices: convex_hull(points),
+					neighbors: Vec::new(),
+					start: AtomicUsize::new(0),
+				};
+			}
+		};
+		// Corners numbered in the order of the points
+		let mut slots = vec![None; points.len()];
+		for face in &faces {
+			for &corner in face {
+				slots[corner] = Some(0);
+			}
+		}
+		let mut vertices = Vec::new();
+		for (index, slot) in slots.iter_mut().enumerate() {
+			if slot.is_some() {
+				*slot = Some(vertices.len());
+				vertices.push(points[index]);
+			}
+		}
+		let mut neighbors = vec![Vec::new(); vertices.len()];
+		for face in &faces {
+			let [a, b, c] = face.map(|corner| slots[corner].unwrap_or(0));
+			// Each edge is on two faces, which go along it both ways
+			for &(from, to) in &[(a, b), (b, c), (c, a)] {
+				if !neighbors[from].contains(&to) {
+					neighbors[from].push(to);
+				}
+			}
+		}
+		TopoHull { vertices, neighbors, start: AtomicUsize::new(0) }
+	}
+
+	/// The corners of the hull
+	pub fn vertices(&self) -> &[Vector3<T>] {
+		&self.vertices
+	}
+
+	// The farthest corner along `direction` and how many corners were
+	// measured finding it, climbing from where the last search ended
+	fn climb(&self, direction: Vector3<T>) -> (Vector3<T>, usize) {
+		if self.neighbors.is_empty() {
+			let corner = farthest(&self.vertices, direction).unwrap_or(Vector3::ZERO);
+			return (corner, self.vertices.len());
+		}
+		let mut at = self.start.load(Ordering::Relaxed).min(self.vertices.len() - 1);
+		let mut best = self.vertices[at].dot(direction);
+		let mut measured = 1;
+		loop {
+			let mut next = at;
+			for &neighbor in &self.neighbors[at] {
+				let along = self.vertices[neighbor].dot(direction);
+				if along > best {
+					best = along;
+					next = neighbor;
+				}
+			}
+			measured += self.neighbors[at].len();
+			if next == at {
+				break;
+			}
+			at = next;
+		}
+		self.start.store(at, Ordering::Relaxed);
+		(self.vertices[at], measured)
+	}
+}
+
+impl<T: Clone> Clone for TopoHull<T> {
+	fn clone(&self) -> TopoHull<T> {
+		TopoHull {
+			vertices: self.vertices.clone(),
+			neighbors: self.neighbors.clone(),
+			start: AtomicUsize::new(self.start.load(Ordering::Relaxed)),
+		}
+	}
+}
+
+impl<T: Float> SupportMap<T> for TopoHull<T> {
+	fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+		self.climb(direction).0
+	}
+
+	fn is_empty(&self) -> bool {
+		self.vertices.is_empty()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::super::{SupportMap, TopoHull, Vec3, bgjk, bgjk_support, farthest};
+	use super::super::test_util::cube_at;
+
+	// `count` points spread over an ellipsoid, with as many inside it
+	fn ellipsoid(count: usize) -> Vec<Vec3> {
+		(0..count * 2)
+			.map(|i| {
+				let t = (i % count) as f32;
+				let z = 1.0 - (t + 0.5) * 2.0 / count as f32;
+				let ring = (1.0 - z * z).sqrt();
+				let (sin, cos) = (t * 2.399_963).sin_cos();
+				let radius = if i < count { 1.0 } else { 0.5 };
+				Vec3(ring * cos, ring * sin * 0.8, z * 1.3) * radius
+			})
+			.collect()
+	}
+
+	fn direction(i: usize) -> Vec3 {
+		let t = i as f32;
+		Vec3((t * 0.37).sin(), (t * 1.91).cos(), (t * 0.73).sin() - 0.2)
+	}
+
+	#[test]
+	fn same_as_slices() {
+		let cloud = ellipsoid(1500);
+		let hull = TopoHull::new(&cloud);
+		assert_eq![hull.vertices().len(), 1500];
+		for i in 0..2000 {
+			assert_eq![Some(hull.support(direction(i))), farthest(&cloud, direction(i))];
+		}
+		let mut hits = 0;
+		for i in 0..200 {
+			let other = cube_at(direction(i) * 1.2 - Vec3::ONE * 0.5);
+			let expected = bgjk(&cloud, &other);
+			assert_eq![bgjk_support(&hull, &other), expected];
+			assert_eq![bgjk_support(&other, &hull), expected];
+			hits += expected as usize;
+		}
+		assert![hits > 0 && hits < 200];
+	}
+
+	#[test]
+	fn measures_fewer() {
+		// Directions out of the blue each time, every search measuring a
+		// few dozen corners where a scan of the cloud measures three thousand
+		let cloud = ellipsoid(1500);
+		let hull = TopoHull::new(&cloud);
+		let measured: usize = (0..1000).map(|i| hull.climb(direction(i * 7)).1).sum();
+		assert![measured / 1000 < cloud.len() / 20];
+	}
+
+	#[test]
+	fn coherent() {
+		let hull = TopoHull::new(&ellipsoid(1500));
+		let turned = |angle: f32| Vec3(angle.sin(), 0.2, angle.cos());
+		hull.climb(-turned(0.0));
+		// From the far side of the hull
+		let cold = hull.climb(turned(0.0)).1;
+		// Turning a little at a time, each search starting at the corner
+		// the last ended at
+		for i in 1..100 {
+			let (corner, measured) = hull.climb(turned(i as f32 * 0.01));
+			assert![measured <= 20 && measured * 10 < cold];
+			assert_eq![Some(corner), farthest(hull.vertices(), turned(i as f32 * 0.01))];
+		}
+		// A clone starts where the original left off
+		let clone = hull.clone();
+		assert![clone.climb(turned(1.0)).1 <= 20];
+	}
+
+	#[test]
+	fn degenerate() {
+		let empty = TopoHull::<f32>::new(&[]);
+		assert_eq![empty.support(Vec3::X), Vec3::ZERO];
+		let point = TopoHull::new(&pts![(1.0, 2.0, 3.0), (1.0, 2.0, 3.0)]);
+		assert_eq![point.vertices(), &[Vec3(1.0, 2.0, 3.0)]];
+		let line = TopoHull::new(&pts![(0.0, 0.0, 0.0), (0.5, 0.0, 0.0), (2.0, 0.0, 0.0)]);
+		assert_eq![line.support(Vec3(1.0, 1.0, 0.0)), Vec3(2.0, 0.0, 0.0)];
+		assert_eq![line.support(-Vec3::X), Vec3::ZERO];
+		// A square with points on its edges and inside, and a cube with
+		// points on its faces, where the faces found are coplanar triangles
+		let mut square: Vec<Vec3> = cube_at(Vec3::ZERO)[..4].to_vec();
+		square.extend(pts![(0.5, 0.0, 0.0), (0.5, 0.5, 0.0)]);
+		let mut cube = cube_at(Vec3::ZERO);
+		cube.extend(pts![(0.5, 0.5, 0.0), (1.0, 0.5, 0.5), (0.5, 0.5, 0.5)]);
+		for cloud in &[square, cube] {
+			let hull = TopoHull::new(cloud);
+			for i in 0..200 {
+				let d = direction(i);
+				assert_eq![Some(hull.support(d).dot(d)), farthest(cloud, d).map(|v| v.dot(d))];
+			}
+		}
+	}
+}
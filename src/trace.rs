@@ -0,0 +1,156 @@
+//! BGJK showing every step of its search, for debugging surprising answers.
+use std::cell::Cell;
+use super::{Ending, Float, GjkConfig, Vector3, either_empty, gjk_steps, hull_support};
+
+/// Why the search of `bgjk_trace` stopped
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GjkEnd {
+	/// The simplex became a tetrahedron around the origin, the hulls intersect
+	ContainsOrigin,
+	/// The new support point did not get past the origin along the search
+	/// direction, which then separates the hulls
+	SupportBehindOrigin,
+	/// The iterations ran out, answered like `bgjk` does
+	OutOfIterations,
+}
+
+/// A step of the search of `bgjk_trace`
+///
+/// All points are in the Minkowski difference `hull1 - hull2`.
+#[derive(Clone, Copy, Debug)]
+pub struct GjkStep<T = f32> {
+	/// The simplex, of which the first `w` points are set, the one found
+	/// last first
+	pub simplex: [Vector3<T>; 4],
+	/// How many points the simplex has, two for a segment, three for a
+	/// triangle and four for the tetrahedron the search ends with
+	pub w: usize,
+	/// The search direction the support point was found along, not normalized
+	pub direction: Vector3<T>,
+	/// The support point along `direction`, about to be added to the simplex
+	pub support: Vector3<T>,
+	/// Why the search stopped on the last step, `None` before it
+	pub end: Option<GjkEnd>,
+}
+
+/// The BGJK algorithm, calling `on_step` on every step of the search, see `bgjk`
+///
+/// `on_step` sees the simplex and the new support point before each update
+/// of the simplex, then once more when the search stops, with the reason
+/// in `end`. On that last step the simplex is the tetrahedron around the
+/// origin, or the simplex the support point fell short of. Runs the very
+/// search `bgjk` runs, so the answer is always the same. A hull with no
+/// points intersects nothing without a search, and gives no steps.
+///
+/// ```
+/// use bgjk::{bgjk_trace, GjkEnd, Vec3};
+///
+/// let hull1 = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// let hull2: Vec<Vec3> = hull1.iter().map(|v| *v + Vec3::new(0.0, 0.0, 2.0)).collect();
+/// let mut ends = Vec::new();
+/// assert![!bgjk_trace(&hull1, &hull2, |step| ends.push(step.end))];
+/// assert_eq![ends.last(), Some(&Some(GjkEnd::SupportBehindOrigin))];
+/// ```
+pub fn bgjk_trace<T, F>(hull1: &[Vector3<T>], hull2: &[Vector3<T>], mut on_step: F) -> bool
+	where T: Float,
+	      F: FnMut(&GjkStep<T>)
+{
+	debug_assert![hull1.iter().chain(hull2).all(Vector3::is_finite),
+	              "bgjk called with a non-finite point in a hull"];
+	if either_empty(hull1, hull2) {
+		return false;
+	}
+	let config = GjkConfig::default();
+	let last = Cell::new(Vector3::ZERO);
+	let support = |direction| {
+		let corner = hull_support(hull1, hull2, direction);
+		last.set(corner.point);
+		corner
+	};
+	let search = gjk_steps(support, &config, &mut on_step);
+	let end = match search.ending {
+		// Touching needs a tolerance, which `bgjk` has none of
+		Ending::Enclosed | Ending::Touching => GjkEnd::ContainsOrigin,
+		Ending::Separated => GjkEnd::SupportBehindOrigin,
+		Ending::OutOfIterations => GjkEnd::OutOfIterations,
+	};
+	on_step(&GjkStep {
+		simplex: search.corners.map(|corner| corner.point),
+		w: search.len,
+		direction: search.direction,
+		support: last.get(),
+		end: Some(end),
+	});
+	search.hit(&config)
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::super::{GjkEnd, GjkStep, Vec3, bgjk, bgjk_trace};
+	use super::super::test_util::cube_at;
+
+	fn steps(hull1: &[Vec3], hull2: &[Vec3]) -> (bool, Vec<GjkStep>) {
+		let mut steps = Vec::new();
+		let hit = bgjk_trace(hull1, hull2, |step| steps.push(*step));
+		(hit, steps)
+	}
+
+	#[test]
+	fn enclosing() {
+		let tetrahedron = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (0.0, 0.0, 1.0)];
+		let inside = pts![(0.2, 0.2, 0.2)];
+		let (hit, steps) = steps(&tetrahedron, &inside);
+		assert![hit];
+		let dimensions: Vec<usize> = steps.iter().map(|step| step.w).collect();
+		assert_eq![dimensions, [2, 3, 4]];
+		let ends: Vec<Option<GjkEnd>> = steps.iter().map(|step| step.end).collect();
+		assert_eq![ends, [None, None, Some(GjkEnd::ContainsOrigin)]];
+		for step in &steps[..2] {
+			assert![step.support.dot(step.direction) >= 0.0];
+		}
+		// The final tetrahedron is made of the support points
+		let last = steps[2];
+		assert_eq![last.simplex[0], last.support];
+		for point in &last.simplex[1..] {
+			assert![steps[..2].iter().any(|step| step.simplex[..step.w].contains(point))];
+		}
+	}
+
+	#[test]
+	fn separated() {
+		let hull1 = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0)];
+		let hull2 = pts![(0.0, 0.0, 2.0), (1.0, 0.0, 2.0), (0.0, 1.0, 2.0)];
+		let (hit, steps) = steps(&hull1, &hull2);
+		assert![!hit];
+		let last = steps.last().unwrap();
+		assert_eq![last.end, Some(GjkEnd::SupportBehindOrigin)];
+		assert![last.support.dot(last.direction) < 0.0];
+		assert![steps[..steps.len() - 1].iter().all(|step| step.end.is_none())];
+	}
+
+	#[test]
+	fn agrees_with_bgjk() {
+		let cube: Vec<Vec3> = (0..8)
+			.map(|i| Vec3((i & 1) as f32, (i >> 1 & 1) as f32, (i >> 2 & 1) as f32))
+			.collect();
+		for i in 0..100 {
+			let (sin, cos) = (i as f32 * 0.61).sin_cos();
+			let by = Vec3(cos, sin, (i % 7) as f32 * 0.3 - 1.0) * (i % 4) as f32 * 0.7;
+			let other: Vec<Vec3> = cube.iter().map(|&v| v * 0.5 + by).collect();
+			let (hit, steps) = steps(&cube, &other);
+			assert_eq![hit, bgjk(&cube, &other)];
+			assert_eq![steps.iter().filter(|step| step.end.is_some()).count(), 1];
+			assert![steps.last().unwrap().end.is_some()];
+		}
+	}
+
+	#[test]
+	fn empty_hull() {
+		let cube = cube_at(Vec3(-0.5, -0.5, -0.5));
+		let mut steps = 0;
+		assert![!bgjk_trace(&[], &cube, |_| steps += 1)];
+		assert![!bgjk_trace(&cube, &[], |_| steps += 1)];
+		assert_eq![steps, 0];
+	}
+}
@@ -0,0 +1,94 @@
+//! BGJK on hulls placed in the world by a rotation and a translation.
+use super::{Float, Transformed, Vector3, bgjk_support};
+
+/// The BGJK algorithm on hulls placed in the world by rigid transforms, see `bgjk`
+///
+/// Each hull is given in its local frame, with a rotation matrix as its
+/// three columns, the directions the local X, Y and Z axes point in the
+/// world, and the world position of its local origin. A point `p` of the
+/// hull is at `rot[0] * p.x() + rot[1] * p.y() + rot[2] * p.z() + pos`.
+///
+/// Equivalent to transforming every point of each hull and calling
+/// `bgjk`, but only the support points the algorithm visits are
+/// transformed, by turning each search direction into the local frame,
+/// so nothing is allocated. The columns may hold any linear map, a scale
+/// or shear included, since the direction is turned with the transpose.
+/// The same as `bgjk_support` on the hulls placed with `Transformed`.
+///
+/// ```
+/// use bgjk::{bgjk_transformed, Vec3};
+///
+/// let hull = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+/// let identity = [Vec3::X, Vec3::Y, Vec3::Z];
+/// let quarter_turn = [Vec3::Y, -Vec3::X, Vec3::Z];
+/// assert![bgjk_transformed(&hull, &identity, Vec3::ZERO,
+///                          &hull, &quarter_turn, Vec3::new(0.5, 0.0, 0.0))];
+/// assert![!bgjk_transformed(&hull, &identity, Vec3::ZERO,
+///                           &hull, &quarter_turn, Vec3::new(-0.5, 0.0, 0.0))];
+/// ```
+pub fn bgjk_transformed<T: Float>(hull1: &[Vector3<T>],
+                                  rot1: &[Vector3<T>; 3],
+                                  pos1: Vector3<T>,
+                                  hull2: &[Vector3<T>],
+                                  rot2: &[Vector3<T>; 3],
+                                  pos2: Vector3<T>)
+                                  -> bool {
+	debug_assert![hull1.iter().chain(hull2).chain(rot1).chain(rot2).all(Vector3::is_finite),
+	              "bgjk called with a non-finite point in a hull"];
+	let placed1 = Transformed { shape: hull1, rotation: *rot1, translation: pos1 };
+	let placed2 = Transformed { shape: hull2, rotation: *rot2, translation: pos2 };
+	bgjk_support(&placed1, &placed2)
+}
+
+#[cfg(test)]
+mod tests {
+
+	use std::f32::consts::PI;
+	use super::super::{Vec3, bgjk, bgjk_transformed};
+	use super::super::test_util::{placed, rotation, unit_cube};
+
+	#[test]
+	fn matches_copied_hulls() {
+		let cube = unit_cube();
+		let rotations = [rotation(Vec3::Z, 0.0),
+		                 rotation(Vec3::Z, PI / 4.0),
+		                 rotation(Vec3(1.0, 1.0, 0.0), PI / 3.0),
+		                 rotation(Vec3(0.3, -0.8, 0.5), 2.0)];
+		let positions = [Vec3(0.0, 0.0, 0.0),
+		                 Vec3(0.9, 0.2, 0.1),
+		                 Vec3(1.2, 0.0, 0.0),
+		                 Vec3(-0.5, 1.3, 0.4),
+		                 Vec3(0.0, 0.0, 3.0)];
+		let mut hits = 0;
+		for rot1 in &rotations {
+			for rot2 in &rotations {
+				for &pos in &positions {
+					let pos1 = pos * -0.5;
+					let expected = bgjk(&placed(&cube, rot1, pos1), &placed(&cube, rot2, pos));
+					assert_eq![bgjk_transformed(&cube, rot1, pos1, &cube, rot2, pos), expected];
+					hits += expected as usize;
+				}
+			}
+		}
+		assert![hits > 0 && hits < rotations.len() * rotations.len() * positions.len()];
+	}
+
+	#[test]
+	fn scaled() {
+		let cube = unit_cube();
+		let stretched = [Vec3::X * 3.0, Vec3::Y, Vec3::Z];
+		let identity = rotation(Vec3::Z, 0.0);
+		let at = |x| bgjk_transformed(&cube, &stretched, Vec3::ZERO, &cube, &identity, Vec3(x, 0.0, 0.0));
+		assert![at(2.5)];
+		assert![!at(3.5)];
+	}
+
+	#[test]
+	fn empty_hull() {
+		let cube = unit_cube();
+		let turn = rotation(Vec3::Z, PI / 4.0);
+		let around = -Vec3(0.5, 0.5, 0.5);
+		assert![!bgjk_transformed(&[], &turn, Vec3::ZERO, &cube, &turn, around)];
+		assert![!bgjk_transformed(&cube, &turn, around, &[], &turn, Vec3::ZERO)];
+	}
+}
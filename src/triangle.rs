@@ -0,0 +1,129 @@
+//! Single triangles against hulls, for triangle mesh geometry.
+use super::{Float, Triangle, Vector3, bgjk_support};
+
+/// Whether the triangle with corners `a`, `b` and `c` intersects `hull`
+///
+/// Gives the same answer as `bgjk(&[a, b, c], hull)`, picking the corner
+/// farthest along each search direction without gathering the corners
+/// into a hull, so testing every triangle of a mesh allocates nothing.
+/// Triangles with collinear or repeated corners act as the segment or the
+/// point they span. The same as `bgjk_support` on a `Triangle` and the hull.
+///
+/// ```
+/// use bgjk::{triangle_hits_hull, Vec3};
+///
+/// let segment = [Vec3::new(0.2, 0.2, -1.0), Vec3::new(0.2, 0.2, 1.0)];
+/// let (a, b, c) = (Vec3::new(0.0, 0.0, 0.0), Vec3::X, Vec3::Y);
+/// assert![triangle_hits_hull(a, b, c, &segment)];
+/// assert![!triangle_hits_hull(a + Vec3::X, b + Vec3::X, c + Vec3::X, &segment)];
+/// ```
+pub fn triangle_hits_hull<T: Float>(a: Vector3<T>,
+                                    b: Vector3<T>,
+                                    c: Vector3<T>,
+                                    hull: &[Vector3<T>])
+                                    -> bool {
+	debug_assert![a.is_finite() && b.is_finite() && c.is_finite() &&
+	              hull.iter().all(Vector3::is_finite),
+	              "bgjk called with a non-finite point in a hull"];
+	bgjk_support(&Triangle(a, b, c), hull)
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+
+	use std::alloc::{GlobalAlloc, Layout, System};
+	use std::cell::Cell;
+	use super::super::{Vec3, bgjk, triangle_hits_hull};
+	use super::super::test_util::unit_cube;
+
+	// Counts the allocations of each thread, so tests running in parallel
+	// do not count each other's
+	struct Counting;
+
+	thread_local! {
+		static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+	}
+
+	unsafe impl GlobalAlloc for Counting {
+		unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+			ALLOCATIONS.with(|count| count.set(count.get() + 1));
+			// Safety: forwarded as is
+			unsafe { System.alloc(layout) }
+		}
+
+		unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+			// Safety: forwarded as is
+			unsafe { System.dealloc(ptr, layout) }
+		}
+	}
+
+	#[global_allocator]
+	static ALLOCATOR: Counting = Counting;
+
+	fn hits(triangle: [Vec3; 3], hull: &[Vec3]) -> bool {
+		let hit = triangle_hits_hull(triangle[0], triangle[1], triangle[2], hull);
+		assert_eq![hit, bgjk(&triangle, hull)];
+		hit
+	}
+
+	#[test]
+	fn piercing() {
+		let cube = unit_cube();
+		assert_eq![hits(pts![(-1.0, -1.0, 0.5), (3.0, -1.0, 0.5), (-1.0, 3.0, 0.5)], &cube), true];
+		assert_eq![hits(pts![(0.5, 0.5, -1.0), (0.6, 0.5, 2.0), (0.5, 0.6, 2.0)], &cube), true];
+		assert_eq![hits(pts![(2.0, -1.0, 0.5), (3.0, -1.0, 0.5), (2.0, 3.0, 0.5)], &cube), false];
+	}
+
+	#[test]
+	fn coplanar_with_face() {
+		let cube = unit_cube();
+		assert_eq![hits(pts![(0.2, 0.2, 1.0), (2.0, 0.2, 1.0), (0.2, 2.0, 1.0)], &cube), true];
+		assert_eq![hits(pts![(1.0, 1.0, 1.0), (2.0, 1.0, 1.0), (1.0, 2.0, 1.0)], &cube), true];
+		assert_eq![hits(pts![(0.2, 0.2, 1.01), (2.0, 0.2, 1.01), (0.2, 2.0, 1.01)], &cube), false];
+		assert_eq![hits(pts![(1.1, 0.2, 1.0), (2.0, 0.2, 1.0), (1.1, 2.0, 1.0)], &cube), false];
+	}
+
+	#[test]
+	fn inside() {
+		let cube = unit_cube();
+		assert_eq![hits(pts![(0.1, 0.1, 0.1), (0.9, 0.2, 0.3), (0.4, 0.8, 0.7)], &cube), true];
+	}
+
+	#[test]
+	fn slivers() {
+		let cube = unit_cube();
+		// Collinear corners act as a segment
+		assert_eq![hits(pts![(-1.0, 0.5, 0.5), (2.0, 0.5, 0.5), (0.5, 0.5, 0.5)], &cube), true];
+		assert_eq![hits(pts![(-1.0, 1.5, 0.5), (2.0, 1.5, 0.5), (0.5, 1.5, 0.5)], &cube), false];
+		// Repeated corners act as a segment or a point
+		assert_eq![hits(pts![(-1.0, 0.5, 0.5), (-1.0, 0.5, 0.5), (0.0, 0.5, 0.5)], &cube), true];
+		assert_eq![hits(pts![(-1.0, 0.5, 0.5), (-1.0, 0.5, 0.5), (-0.1, 0.5, 0.5)], &cube), false];
+		let point = Vec3(0.5, 0.5, 0.5);
+		assert_eq![hits([point; 3], &cube), true];
+		assert_eq![hits([point * 3.0; 3], &cube), false];
+		// Nearly collinear
+		assert_eq![hits(pts![(-1.0, 1.0, 0.5), (2.0, 1.0, 0.5), (0.5, 1.0 + 1e-6, 0.5)], &cube), true];
+	}
+
+	#[test]
+	fn mesh_allocates_nothing() {
+		let cube = unit_cube();
+		let before = ALLOCATIONS.with(Cell::get);
+		let mut count = 0;
+		for i in 0..4000 {
+			let (sin, cos) = (i as f32 * 0.37).sin_cos();
+			let radius = (i % 5) as f32 * 0.4;
+			let a = Vec3(cos, sin, (i % 7) as f32 * 0.3 - 0.9) * radius + Vec3::ONE * 0.5;
+			let (b, c) = (a + Vec3(0.3, -0.2, 0.1), a + Vec3(-0.1, 0.25, 0.3));
+			count += triangle_hits_hull(a, b, c, &cube) as usize;
+		}
+		assert_eq![ALLOCATIONS.with(Cell::get), before];
+		assert![count > 0 && count < 4000];
+	}
+
+	#[test]
+	fn empty_hull() {
+		assert![!triangle_hits_hull(-Vec3::ONE, Vec3(2.0, -1.0, 0.0), Vec3(-1.0, 2.0, 0.0), &[])];
+	}
+}
@@ -0,0 +1,302 @@
+//! Planar counterpart of `Vec3`.
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub,
+               SubAssign};
+use super::Vec3;
+
+/// Vector in the plane
+///
+/// Uses cartesian spatial dimensions in the order x, y. Converts into a
+/// `Vec3` in the z = 0 plane.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct Vec2(pub f32, pub f32);
+
+impl Add for Vec2 {
+	type Output = Vec2;
+	fn add(self, right: Vec2) -> Self::Output {
+		Vec2(self.0 + right.0, self.1 + right.1)
+	}
+}
+
+impl Sub for Vec2 {
+	type Output = Vec2;
+	fn sub(self, right: Vec2) -> Self::Output {
+		Vec2(self.0 - right.0, self.1 - right.1)
+	}
+}
+
+impl Mul<f32> for Vec2 {
+	type Output = Vec2;
+	fn mul(self, right: f32) -> Self::Output {
+		Vec2(self.0 * right, self.1 * right)
+	}
+}
+
+impl Mul<Vec2> for f32 {
+	type Output = Vec2;
+	fn mul(self, right: Vec2) -> Self::Output {
+		right * self
+	}
+}
+
+/// Divides every component by the scalar, following `f32` semantics
+impl Div<f32> for Vec2 {
+	type Output = Vec2;
+	fn div(self, right: f32) -> Self::Output {
+		Vec2(self.0 / right, self.1 / right)
+	}
+}
+
+impl Neg for Vec2 {
+	type Output = Vec2;
+	fn neg(self) -> Self::Output {
+		Vec2(-self.0, -self.1)
+	}
+}
+
+impl AddAssign for Vec2 {
+	fn add_assign(&mut self, right: Vec2) {
+		*self = *self + right;
+	}
+}
+
+impl SubAssign for Vec2 {
+	fn sub_assign(&mut self, right: Vec2) {
+		*self = *self - right;
+	}
+}
+
+impl MulAssign<f32> for Vec2 {
+	fn mul_assign(&mut self, right: f32) {
+		*self = *self * right;
+	}
+}
+
+impl DivAssign<f32> for Vec2 {
+	fn div_assign(&mut self, right: f32) {
+		*self = *self / right;
+	}
+}
+
+/// Indexes the x and y components by 0 and 1 respectively
+///
+/// Panics for any other index.
+impl Index<usize> for Vec2 {
+	type Output = f32;
+	fn index(&self, index: usize) -> &f32 {
+		match index {
+			0 => &self.0,
+			1 => &self.1,
+			_ => panic!("Vec2 index out of range: the index is {} but must be 0 or 1", index),
+		}
+	}
+}
+
+impl IndexMut<usize> for Vec2 {
+	fn index_mut(&mut self, index: usize) -> &mut f32 {
+		match index {
+			0 => &mut self.0,
+			1 => &mut self.1,
+			_ => panic!("Vec2 index out of range: the index is {} but must be 0 or 1", index),
+		}
+	}
+}
+
+/// Formats as `(x, y)`, see the `Display` implementation of `Vec3`
+impl fmt::Display for Vec2 {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "(")?;
+		fmt::Display::fmt(&self.0, f)?;
+		write!(f, ", ")?;
+		fmt::Display::fmt(&self.1, f)?;
+		write!(f, ")")
+	}
+}
+
+impl From<[f32; 2]> for Vec2 {
+	fn from(array: [f32; 2]) -> Vec2 {
+		Vec2(array[0], array[1])
+	}
+}
+
+impl From<Vec2> for [f32; 2] {
+	fn from(vector: Vec2) -> [f32; 2] {
+		[vector.0, vector.1]
+	}
+}
+
+impl From<(f32, f32)> for Vec2 {
+	fn from(tuple: (f32, f32)) -> Vec2 {
+		Vec2(tuple.0, tuple.1)
+	}
+}
+
+impl From<Vec2> for (f32, f32) {
+	fn from(vector: Vec2) -> (f32, f32) {
+		(vector.0, vector.1)
+	}
+}
+
+/// Places the vector in the z = 0 plane
+impl From<Vec2> for Vec3 {
+	fn from(vector: Vec2) -> Vec3 {
+		Vec3(vector.0, vector.1, 0.0)
+	}
+}
+
+impl Vec2 {
+	/// The zero vector
+	pub const ZERO: Vec2 = Vec2(0.0, 0.0);
+	/// The vector with all components set to one
+	pub const ONE: Vec2 = Vec2(1.0, 1.0);
+	/// The unit vector along the x axis
+	pub const X: Vec2 = Vec2(1.0, 0.0);
+	/// The unit vector along the y axis
+	pub const Y: Vec2 = Vec2(0.0, 1.0);
+
+	/// Create a vector from its x and y components
+	pub const fn new(x: f32, y: f32) -> Vec2 {
+		Vec2(x, y)
+	}
+
+	/// The x component
+	pub const fn x(&self) -> f32 {
+		self.0
+	}
+
+	/// The y component
+	pub const fn y(&self) -> f32 {
+		self.1
+	}
+
+	/// Append a z component, the inverse of `Vec3::truncate`
+	pub const fn extend(self, z: f32) -> Vec3 {
+		Vec3(self.0, self.1, z)
+	}
+
+	/// The dot (inner) product
+	pub fn dot(&self, right: Vec2) -> f32 {
+		self.0 * right.0 + self.1 * right.1
+	}
+
+	/// The vector rotated a quarter turn counter-clockwise
+	///
+	/// `Vec2::X.perp() == Vec2::Y`.
+	pub fn perp(&self) -> Vec2 {
+		Vec2(-self.1, self.0)
+	}
+
+	/// The planar cross product, the z component of the 3D cross product
+	///
+	/// Positive when `right` lies counter-clockwise of `self`. Equals
+	/// `self.perp().dot(right)`.
+	pub fn cross(&self, right: Vec2) -> f32 {
+		self.0 * right.1 - self.1 * right.0
+	}
+
+	/// The euclidean length (magnitude) of the vector
+	pub fn length(&self) -> f32 {
+		self.length_squared().sqrt()
+	}
+
+	/// The squared euclidean length of the vector, avoids the square root
+	pub fn length_squared(&self) -> f32 {
+		self.dot(*self)
+	}
+
+	/// The unit vector in the same direction, if there is one
+	///
+	/// Returns `None` for the zero vector and for vectors with non-finite
+	/// components, see `Vec3::try_normalized`.
+	pub fn try_normalized(&self) -> Option<Vec2> {
+		let largest = self.0.abs().max(self.1.abs());
+		if largest == 0.0 || !self.0.is_finite() || !self.1.is_finite() {
+			return None;
+		}
+		let scaled = *self / largest;
+		Some(scaled / scaled.length())
+	}
+
+	/// The unit vector in the same direction, or `Vec2::ZERO` if there is none
+	pub fn normalized(&self) -> Vec2 {
+		self.try_normalized().unwrap_or(Vec2::ZERO)
+	}
+
+	/// Normalize the vector in place, see `normalized`
+	pub fn normalize(&mut self) {
+		*self = self.normalized();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+
+	use std::f32;
+	use super::super::{Vec2, Vec3};
+	static EPS: f32 = f32::EPSILON;
+
+	#[test]
+	fn operators() {
+		let (a, b) = (Vec2(1.0, -2.0), Vec2(0.5, 4.0));
+		assert_eq![a + b, Vec2(1.5, 2.0)];
+		assert_eq![a - b, Vec2(0.5, -6.0)];
+		assert_eq![a + b - b, a];
+		assert_eq![a * 2.0, Vec2(2.0, -4.0)];
+		assert_eq![2.0 * a, a * 2.0];
+		assert_eq![a / 2.0, Vec2(0.5, -1.0)];
+		assert_eq![-a, Vec2(-1.0, 2.0)];
+		assert_eq![a + -a, Vec2::ZERO];
+		let mut v = a;
+		v += b;
+		v -= Vec2::ONE;
+		v *= 4.0;
+		v /= 2.0;
+		assert_eq![v, Vec2(1.0, 2.0)];
+		v[0] = 3.0;
+		assert_eq![(v[0], v[1]), (3.0, 2.0)];
+		assert_eq![(v.x(), v.y()), (3.0, 2.0)];
+	}
+
+	#[test]
+	#[should_panic(expected = "Vec2 index out of range: the index is 2 but must be 0 or 1")]
+	fn index_out_of_range() {
+		let _ = Vec2::ZERO[2];
+	}
+
+	#[test]
+	fn dot_perp_cross() {
+		assert_eq![Vec2(1.0, 2.0).dot(Vec2(3.0, -4.0)), -5.0];
+		assert_eq![Vec2::X.perp(), Vec2::Y];
+		assert_eq![Vec2::Y.perp(), -Vec2::X];
+		assert_eq![Vec2::X.cross(Vec2::Y), 1.0];
+		assert_eq![Vec2::Y.cross(Vec2::X), -1.0];
+		let (a, b) = (Vec2(1.0, -2.0), Vec2(0.5, 4.0));
+		assert_eq![a.cross(b), a.perp().dot(b)];
+		assert_eq![a.cross(b), Vec3::from(a).cross(Vec3::from(b)).2];
+		assert_eq![a.perp().dot(a), 0.0];
+	}
+
+	#[test]
+	fn length_and_normalize() {
+		assert_eq![Vec2(3.0, -4.0).length(), 5.0];
+		assert_eq![Vec2(3.0, -4.0).length_squared(), 25.0];
+		assert_eq![Vec2(3.0, -4.0).normalized(), Vec2(0.6, -0.8)];
+		assert_eq![Vec2::ZERO.try_normalized(), None];
+		assert_eq![Vec2(f32::NAN, 1.0).try_normalized(), None];
+		assert_eq![Vec2::ZERO.normalized(), Vec2::ZERO];
+		let mut v = Vec2(1e-40, 1e-40);
+		v.normalize();
+		assert![(v.length() - 1.0).abs() <= 2.0 * EPS];
+	}
+
+	#[test]
+	fn conversions() {
+		let v = Vec2(1.0, -2.5);
+		assert_eq![Vec2::from(<[f32; 2]>::from(v)), v];
+		assert_eq![Vec2::from(<(f32, f32)>::from(v)), v];
+		assert_eq![Vec3::from(v), Vec3(1.0, -2.5, 0.0)];
+		assert_eq![format!("{}", v), "(1, -2.5)"];
+	}
+
+}
@@ -0,0 +1,124 @@
+//! Rough measures of how much hulls overlap, by sampling.
+use super::{Float, Vector3, bgjk, point_in_hull};
+use super::batch::bounds;
+
+// The SplitMix64 generator, small and good enough to scatter samples
+struct Rng(u64);
+
+impl Rng {
+	fn next(&mut self) -> u64 {
+		self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+		let mut z = self.0;
+		z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+		z ^ (z >> 31)
+	}
+
+	// Uniform in [0, 1), from the top 24 bits, which an `f32` holds exactly
+	fn fraction<T: Float>(&mut self) -> T {
+		T::from_f32((self.next() >> 40) as f32 / (1u32 << 24) as f32)
+	}
+}
+
+/// An estimate of the volume the two hulls share
+///
+/// Scatters `samples` points uniformly over the overlap of the bounding
+/// boxes of the hulls and scales the volume of that overlap by the share of
+/// points inside both hulls, see `point_in_hull`. The error shrinks with
+/// the square root of `samples`, a few thousand give about a percent of the
+/// box. The same `seed` always gives the same estimate.
+///
+/// Hulls `bgjk` finds apart give exactly `0.0` without any sampling, as do
+/// zero `samples`. Flat hulls have no volume.
+///
+/// ```
+/// use bgjk::{overlap_volume_estimate, Vec3};
+///
+/// let cube: Vec<Vec3> = (0..8)
+///     .map(|i| Vec3::new((i & 1) as f32, (i >> 1 & 1) as f32, (i >> 2 & 1) as f32))
+///     .collect();
+/// let half: Vec<Vec3> = cube.iter().map(|v| *v + Vec3::new(0.5, 0.0, 0.0)).collect();
+/// assert_eq![overlap_volume_estimate(&cube, &half, 1000, 7), 0.5];
+/// ```
+pub fn overlap_volume_estimate<T: Float>(hull1: &[Vector3<T>],
+                                         hull2: &[Vector3<T>],
+                                         samples: u32,
+                                         seed: u64)
+                                         -> T {
+	if samples == 0 || !bgjk(hull1, hull2) {
+		return T::ZERO;
+	}
+	let (box1, box2) = (bounds(hull1), bounds(hull2));
+	let shared = match box1.intersection(&box2) {
+		Some(shared) => shared,
+		None => return T::ZERO,
+	};
+	let extent = shared.max - shared.min;
+	let mut rng = Rng(seed);
+	let mut inside = 0u32;
+	for _ in 0..samples {
+		let offset = Vector3(rng.fraction(), rng.fraction(), rng.fraction());
+		let point = shared.min + extent.mul_component(offset);
+		if point_in_hull(point, hull1) && point_in_hull(point, hull2) {
+			inside += 1;
+		}
+	}
+	let share = T::from_f32(inside as f32 / samples as f32);
+	extent.0 * extent.1 * extent.2 * share
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::super::{Vec3, overlap_volume_estimate};
+	use super::super::test_util::cube_at;
+
+	#[test]
+	fn identical_cubes() {
+		let cube = cube_at(Vec3::ZERO);
+		assert_eq![overlap_volume_estimate(&cube, &cube, 1000, 1), 1.0];
+	}
+
+	#[test]
+	fn half_overlapping() {
+		let cube = cube_at(Vec3::ZERO);
+		let half = cube_at(Vec3(0.0, 0.5, 0.0));
+		assert![(overlap_volume_estimate(&cube, &half, 1000, 2) - 0.5).abs() <= 1e-6];
+		// A unit cube turned by 45 degrees about the vertical, centered on a
+		// face. Half of it overlaps, less the corners sticking out at the
+		// bottom and the top, so the box overlap is well off.
+		let half_diagonal = 0.5f32.sqrt();
+		let diamond: Vec<Vec3> = [(0.0, -1.0), (1.0, 0.0), (0.0, 1.0), (-1.0, 0.0)]
+			.iter()
+			.flat_map(|&(x, y)| {
+				let corner = Vec3(x, y, 0.0) * half_diagonal + Vec3(1.0, 0.5, 0.0);
+				vec![corner, corner + Vec3::Z]
+			})
+			.collect();
+		let estimate = overlap_volume_estimate(&cube, &diamond, 20_000, 3);
+		let corners = (half_diagonal - 0.5) * (half_diagonal - 0.5);
+		assert![(estimate - (0.5 - corners)).abs() <= 0.01];
+	}
+
+	#[test]
+	fn tetrahedron_in_cube() {
+		let cube = cube_at(Vec3::ZERO);
+		let tetrahedron = pts![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (0.0, 0.0, 1.0)];
+		let estimate = overlap_volume_estimate(&cube, &tetrahedron, 20_000, 4);
+		assert![(estimate - 1.0 / 6.0).abs() <= 0.01];
+		assert_eq![overlap_volume_estimate(&tetrahedron, &cube, 20_000, 4), estimate];
+		assert![overlap_volume_estimate(&cube, &tetrahedron, 20_000, 5) != estimate];
+	}
+
+	#[test]
+	fn disjoint() {
+		let cube = cube_at(Vec3::ZERO);
+		assert_eq![overlap_volume_estimate(&cube, &cube_at(Vec3(1.5, 0.0, 0.0)), 1000, 6), 0.0];
+		assert_eq![overlap_volume_estimate(&cube, &cube, 0, 6), 0.0];
+		assert_eq![overlap_volume_estimate(&cube, &[], 1000, 6), 0.0];
+		// Touching, and flat, no volume to share
+		assert_eq![overlap_volume_estimate(&cube, &cube_at(Vec3::X), 1000, 6), 0.0];
+		let square = pts![(0.0, 0.0, 0.5), (1.0, 0.0, 0.5), (0.0, 1.0, 0.5), (1.0, 1.0, 0.5)];
+		assert_eq![overlap_volume_estimate(&cube, &square, 1000, 6), 0.0];
+	}
+}
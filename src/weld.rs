@@ -0,0 +1,287 @@
+//! Merging repeated and nearly repeated points of hulls, which only slow
+//! `bgjk` down.
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use super::{Float, Vector3, centroid};
+
+// The bits of the components, with -0.0 as 0.0 so that equal points share a
+// key, like the `Hash` of `Vec3`
+fn key<T: Float>(point: &Vector3<T>) -> (u64, u64, u64) {
+	// Adding zero turns -0.0 into 0.0 and leaves everything else alone
+	((point.0 + T::ZERO).to_bits(), (point.1 + T::ZERO).to_bits(), (point.2 + T::ZERO).to_bits())
+}
+
+/// The points without their exact repeats, each kept where it first appears
+///
+/// Repeated points never change the answer of `bgjk`, but every support
+/// search goes over them all. Points are repeats when they are equal, so
+/// `0.0` and `-0.0` are the same coordinate, and points with a NaN are
+/// never repeats. The number of points removed is the difference in
+/// length, see `dedup_points_in_place` for doing it in place.
+///
+/// ```
+/// use bgjk::{dedup_points, Vec3};
+///
+/// let points = [Vec3::X, Vec3::Y, Vec3::X, Vec3::Z, Vec3::Y];
+/// assert_eq![dedup_points(&points), [Vec3::X, Vec3::Y, Vec3::Z]];
+/// ```
+pub fn dedup_points<T: Float>(points: &[Vector3<T>]) -> Vec<Vector3<T>> {
+	let mut seen = HashSet::with_capacity(points.len());
+	points.iter().cloned().filter(|point| point.is_nan() || seen.insert(key(point))).collect()
+}
+
+/// Remove the exact repeats of points in place, see `dedup_points`
+///
+/// Keeps the first of each and returns how many were removed.
+///
+/// ```
+/// use bgjk::{dedup_points_in_place, Vec3};
+///
+/// let mut points = vec![Vec3::X, Vec3::Y, Vec3::X, Vec3::X];
+/// assert_eq![dedup_points_in_place(&mut points), 2];
+/// assert_eq![points, [Vec3::X, Vec3::Y]];
+/// ```
+pub fn dedup_points_in_place<T: Float>(points: &mut Vec<Vector3<T>>) -> usize {
+	let before = points.len();
+	let mut seen = HashSet::with_capacity(before);
+	points.retain(|point| point.is_nan() || seen.insert(key(point)));
+	before - points.len()
+}
+
+// Orders points by their coordinates, the same for equal points
+fn by_coordinates<T: Float>(a: &Vector3<T>, b: &Vector3<T>) -> Ordering {
+	(a.0, a.1, a.2).partial_cmp(&(b.0, b.1, b.2)).unwrap_or(Ordering::Equal)
+}
+
+/// The points with every cluster of nearby points merged into its centroid
+///
+/// Every two points of a cluster are within `tolerance` of each other, so a
+/// cluster is at most `tolerance` across and its centroid is within
+/// `tolerance` of each of its points. The lowest point by coordinates that
+/// is not in a cluster yet starts the next one, which then takes each of
+/// the remaining points within `tolerance` of all its points, again from
+/// the lowest up. So a line of points each within `tolerance` of the next
+/// becomes several points rather than one, as does a dense surface. The
+/// points are hashed into a grid of cells `tolerance` wide, each compared
+/// only with the points of the neighboring cells, which takes linear time
+/// unless most points crowd into a few cells.
+///
+/// The clusters do not depend on the order of the points, and `centroid`
+/// sums each in a fixed order of its points, so neither do the merged
+/// points. They come in the order of the first point of each
+/// cluster. A `tolerance` of zero or less merges only equal points, like
+/// `dedup_points`. The points must be finite.
+///
+/// ```
+/// use bgjk::{weld_points, Vec3};
+///
+/// let points = [Vec3::X, Vec3::new(1.0, 0.0, 1e-6), Vec3::Y, Vec3::new(0.0, 1.0, -1e-6)];
+/// let welded = weld_points(&points, 1e-5);
+/// assert_eq![welded.len(), 2];
+/// assert![welded[0].approx_eq(Vec3::X, 1e-6) && welded[1].approx_eq(Vec3::Y, 1e-6)];
+/// ```
+pub fn weld_points<T: Float>(points: &[Vector3<T>], tolerance: T) -> Vec<Vector3<T>> {
+	debug_assert![points.iter().all(Vector3::is_finite),
+	              "weld_points called with a non-finite point"];
+	if tolerance.is_nan() || tolerance <= T::ZERO {
+		return dedup_points(points);
+	}
+	let cell = |point: Vector3<T>| {
+		let scaled = point / tolerance;
+		(scaled.0.floor().to_f64() as i64,
+		 scaled.1.floor().to_f64() as i64,
+		 scaled.2.floor().to_f64() as i64)
+	};
+	let mut grid: HashMap<(i64, i64, i64), Vec<usize>> = HashMap::new();
+	for (index, point) in points.iter().enumerate() {
+		grid.entry(cell(*point)).or_default().push(index);
+	}
+	let mut order: Vec<usize> = (0..points.len()).collect();
+	order.sort_by(|&a, &b| by_coordinates(&points[a], &points[b]));
+	let mut taken = vec![false; points.len()];
+	let mut clusters: Vec<Vec<usize>> = Vec::new();
+	for &first in &order {
+		if taken[first] {
+			continue;
+		}
+		let (x, y, z) = cell(points[first]);
+		let mut near: Vec<usize> = (0..27)
+			.map(|i| (x + i % 3 - 1, y + i / 3 % 3 - 1, z + i / 9 - 1))
+			.flat_map(|neighbor| grid.get(&neighbor).into_iter().flatten().cloned())
+			.filter(|&other| {
+				!taken[other] && other != first &&
+				points[first].distance(points[other]) <= tolerance
+			})
+			.collect();
+		near.sort_by(|&a, &b| by_coordinates(&points[a], &points[b]));
+		let mut cluster = vec![first];
+		for other in near {
+			if cluster.iter().all(|&member| points[member].distance(points[other]) <= tolerance) {
+				cluster.push(other);
+			}
+		}
+		for &member in &cluster {
+			taken[member] = true;
+		}
+		clusters.push(cluster);
+	}
+	clusters.sort_by_key(|cluster| cluster.iter().min().cloned());
+	clusters.into_iter()
+		.map(|cluster| {
+			let mut members: Vec<Vector3<T>> = cluster.iter().map(|&index| points[index]).collect();
+			members.sort_by(by_coordinates);
+			centroid(&members).expect("a cluster holds at least the point that started it")
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::super::{DVec3, Vec3, bgjk, dedup_points, dedup_points_in_place, weld_points};
+	use super::super::test_util::cube_at;
+
+	#[test]
+	fn repeated_cube() {
+		let cube = cube_at(Vec3::ZERO);
+		let mut repeated: Vec<Vec3> = cube.iter().chain(&cube).chain(&cube).cloned().collect();
+		repeated.rotate_left(3);
+		let expected: Vec<Vec3> = cube[3..].iter().chain(&cube[..3]).cloned().collect();
+		assert_eq![dedup_points(&repeated), expected];
+		let mut in_place = repeated.clone();
+		assert_eq![dedup_points_in_place(&mut in_place), 16];
+		assert_eq![in_place, expected];
+		for i in 0..100 {
+			let (sin, cos) = (i as f32 * 0.61).sin_cos();
+			let other = cube_at(Vec3(cos, sin, (i % 7) as f32 * 0.3 - 1.0) * (i % 4) as f32 * 0.7);
+			assert_eq![bgjk(&in_place, &other), bgjk(&repeated, &other)];
+			assert_eq![bgjk(&other, &in_place), bgjk(&other, &repeated)];
+		}
+	}
+
+	#[test]
+	fn all_the_same() {
+		let mut same = vec![Vec3(1.0, -2.0, 0.5); 10];
+		assert_eq![dedup_points(&same), [Vec3(1.0, -2.0, 0.5)]];
+		assert_eq![dedup_points_in_place(&mut same), 9];
+		assert_eq![same.len(), 1];
+		assert![dedup_points::<f32>(&[]).is_empty()];
+		assert_eq![dedup_points_in_place::<f32>(&mut Vec::new()), 0];
+	}
+
+	#[test]
+	fn signed_zeros_and_nan() {
+		let nan = Vec3(f32::NAN, 0.0, 0.0);
+		let points = [Vec3::ZERO, Vec3(-0.0, 0.0, -0.0), nan, nan];
+		let unique = dedup_points(&points);
+		assert_eq![unique.len(), 3];
+		assert_eq![unique[0].0.to_bits(), 0.0f32.to_bits()];
+		let mut in_place = points.to_vec();
+		assert_eq![dedup_points_in_place(&mut in_place), 1];
+	}
+
+	// Each corner of the unit cube as five points within 1e-5 of it
+	fn noisy_cube() -> Vec<Vec3> {
+		let cube = cube_at(Vec3::ZERO);
+		(0..5)
+			.flat_map(|i| {
+				let (sin, cos) = (i as f32 * 1.3).sin_cos();
+				let jitter = Vec3(sin, cos, sin * cos).normalized() * 0.9e-5 * i as f32 / 4.0;
+				cube.iter().map(move |&corner| corner + jitter).collect::<Vec<_>>()
+			})
+			.collect()
+	}
+
+	#[test]
+	fn noisy_cube_welds() {
+		let cube = cube_at(Vec3::ZERO);
+		let noisy = noisy_cube();
+		let welded = weld_points(&noisy, 1e-4);
+		assert_eq![welded.len(), 8];
+		for (merged, corner) in welded.iter().zip(&cube) {
+			assert![merged.approx_eq(*corner, 1e-5)];
+		}
+		for i in 0..100 {
+			let (sin, cos) = (i as f32 * 0.61).sin_cos();
+			let by = Vec3(cos, sin, (i % 7) as f32 * 0.3 - 1.0) * (i % 4) as f32 * 0.7;
+			let other: Vec<Vec3> = cube.iter().map(|&v| v * 0.5 + by).collect();
+			if bgjk(&cube, &other) == bgjk(&noisy, &other) {
+				assert_eq![bgjk(&welded, &other), bgjk(&cube, &other)];
+			}
+		}
+	}
+
+	#[test]
+	fn any_order() {
+		let noisy = noisy_cube();
+		let welded = weld_points(&noisy, 1e-4);
+		let mut reversed = noisy.clone();
+		reversed.reverse();
+		let mut welded_reversed = weld_points(&reversed, 1e-4);
+		welded_reversed.reverse();
+		assert_eq![welded, welded_reversed];
+		let mut shuffled = noisy.clone();
+		for i in 0..shuffled.len() {
+			shuffled.swap(i, i * 7 % noisy.len());
+		}
+		let mut again = weld_points(&shuffled, 1e-4);
+		let order = |a: &Vec3, b: &Vec3| (a.0, a.1, a.2).partial_cmp(&(b.0, b.1, b.2)).unwrap();
+		again.sort_by(order);
+		let mut sorted = welded.clone();
+		sorted.sort_by(order);
+		assert_eq![again, sorted];
+	}
+
+	#[test]
+	fn chains_and_limits() {
+		// Each within the tolerance of the next, the ends well beyond it
+		let chain: Vec<Vec3> = (0..10).map(|i| Vec3::X * i as f32 * 0.09).collect();
+		let welded = weld_points(&chain, 0.1);
+		assert_eq![welded.len(), 5];
+		for (i, merged) in welded.iter().enumerate() {
+			assert![merged.approx_eq(Vec3::X * (i as f32 * 0.18 + 0.045), 1e-6)];
+		}
+		let apart = [Vec3::ZERO, Vec3::X * 0.11, Vec3::Y * 0.11];
+		assert_eq![weld_points(&apart, 0.1), apart];
+		// Across a cell boundary
+		let straddling = [Vec3(0.099, 0.0, 0.0), Vec3(0.101, 0.0, 0.0)];
+		assert_eq![weld_points(&straddling, 0.1).len(), 1];
+		let repeated = [Vec3::X, Vec3::Y, Vec3::X];
+		assert_eq![weld_points(&repeated, 0.0), [Vec3::X, Vec3::Y]];
+		assert_eq![weld_points(&repeated, -1.0), [Vec3::X, Vec3::Y]];
+		assert![weld_points(&[], 0.1).is_empty()];
+	}
+
+	#[test]
+	fn dense_sphere() {
+		// A Fibonacci lattice, each point about 0.04 from its nearest
+		let count = 8000;
+		let sphere: Vec<Vec3> = (0..count)
+			.map(|i| {
+				let z = 1.0 - (2 * i + 1) as f32 / count as f32;
+				let (sin, cos) = (i as f32 * 2.399_963).sin_cos();
+				let ring = (1.0 - z * z).sqrt();
+				Vec3(ring * cos, ring * sin, z)
+			})
+			.collect();
+		let welded = weld_points(&sphere, 0.1);
+		assert![welded.len() > 500, "{} points", welded.len()];
+		for point in &sphere {
+			assert![welded.iter().any(|merged| merged.distance(*point) <= 0.1)];
+		}
+		assert![welded.iter().all(|merged| merged.length() > 0.99)];
+	}
+
+	#[test]
+	fn double_precision() {
+		let points = [DVec3::new(1.0, 0.0, 0.0),
+		              DVec3::new(1.0, 0.0, 1e-12),
+		              DVec3::new(0.0, 1.0, 0.0),
+		              DVec3::new(1.0, 0.0, 0.0)];
+		assert_eq![dedup_points(&points).len(), 3];
+		let welded = weld_points(&points, 1e-10);
+		assert_eq![welded.len(), 2];
+		assert![welded[0].approx_eq(DVec3::new(1.0, 0.0, 1e-12 / 3.0), 1e-15)];
+		assert_eq![welded[1], DVec3::new(0.0, 1.0, 0.0)];
+	}
+}